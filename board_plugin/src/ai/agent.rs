@@ -1,4 +1,5 @@
 use core::fmt::Debug;
+use std::time::Duration;
 
 use bevy::prelude::Entity;
 
@@ -7,6 +8,23 @@ use crate::resources::{board::Board, deck::Deck, game_state::GameState, tile_map
 pub trait Agent: Debug + Sync + Send {
     // returns a card entity and a desired move
     fn generate_move(&self, board: &Board, game_state: &GameState, deck: &Deck) -> (Option<Entity>, Option<Move>, i32);
+    // Same as `generate_move`, but the search must fit into `time_budget`. Agents without a search ignore it
+    fn generate_move_in_time(&self, board: &Board, game_state: &GameState, deck: &Deck, _time_budget: Duration) -> (Option<Entity>, Option<Move>, i32) {
+        self.generate_move(board, game_state, deck)
+    }
+    // Agents searching on another thread return no move and no failure while the search runs,
+    // the move is polled every frame until it is ready
+    fn is_thinking(&self) -> bool {
+        false
+    }
+    // Same results as `generate_move` once the search has finished, `None` before
+    fn poll_move(&self, _board: &Board, _game_state: &GameState, _deck: &Deck) -> Option<(Option<Entity>, Option<Move>, i32)> {
+        None
+    }
+    // Reason why the last move was not generated. The player forfeits the game then
+    fn failure(&self) -> Option<String> {
+        None
+    }
     // To clone the agent, it requires quite awful construction: https://stackoverflow.com/a/69891769
     fn clone_dyn(&self) -> Box<dyn Agent>;
 }
@@ -1,4 +1,12 @@
-use bevy::{log, prelude::Entity};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use bevy::{log, prelude::Entity, utils::Instant};
 
 use crate::resources::{
     board::Board,
@@ -9,6 +17,9 @@ use crate::resources::{
 
 use super::{agent::Agent, evaluation::Evaluation};
 
+// Positions searched between the deadline checks, reading the clock is slow in the browsers
+const DEADLINE_CHECK_INTERVAL: i32 = 256;
+
 #[derive(Debug, Clone, Copy)]
 pub struct MoveEvaluation {
     pub mov: Move,
@@ -17,6 +28,10 @@ pub struct MoveEvaluation {
 #[derive(Debug, Clone)]
 pub struct AlphaBetaAgent {
     pub max_depth: u8,
+    /// The search is abandoned when the flag is set, e.g. when the time is up
+    pub stop: Option<Arc<AtomicBool>>,
+    /// The stop flag is set by the search itself at this moment
+    pub deadline: Option<Instant>,
 }
 
 struct CalculationResult {
@@ -25,9 +40,102 @@ struct CalculationResult {
     best_score: i32,
 }
 
+/// Result of a search with the amount of analyzed positions
+#[derive(Debug, Clone, Copy)]
+pub struct SearchResult {
+    pub card: Option<Entity>,
+    pub mov: Option<Move>,
+    pub score: i32,
+    pub positions: i32,
+}
+
 impl AlphaBetaAgent {
     pub fn new(max_depth: u8) -> Self {
-        Self { max_depth }
+        Self {
+            max_depth,
+            stop: None,
+            deadline: None,
+        }
+    }
+
+    pub fn with_stop(max_depth: u8, stop: Arc<AtomicBool>) -> Self {
+        Self {
+            max_depth,
+            stop: Some(stop),
+            deadline: None,
+        }
+    }
+
+    pub fn with_deadline(max_depth: u8, deadline: Instant) -> Self {
+        Self {
+            max_depth,
+            stop: Some(Arc::new(AtomicBool::new(false))),
+            deadline: Some(deadline),
+        }
+    }
+
+    #[inline]
+    fn is_stopped(&self, positions: i32) -> bool {
+        let stop = match self.stop.as_ref() {
+            Some(stop) => stop,
+            None => return false,
+        };
+
+        if positions % DEADLINE_CHECK_INTERVAL == 0
+            && self.deadline.map_or(false, |deadline| Instant::now() >= deadline)
+        {
+            stop.store(true, Ordering::Relaxed);
+        }
+        stop.load(Ordering::Relaxed)
+    }
+
+    /// Searches one ply deeper every iteration until `max_depth` or the deadline.
+    /// A stopped iteration is thrown away, so the result is from the deepest finished one
+    pub fn search_until(
+        &self,
+        board: &Board,
+        game_state: &GameState,
+        deck: &Deck,
+        deadline: Instant,
+    ) -> SearchResult {
+        // the first iteration is never stopped, so there is always a move
+        let mut best = AlphaBetaAgent::new(1).search(board, game_state, deck);
+
+        for depth in 2..=self.max_depth {
+            let agent = AlphaBetaAgent::with_deadline(depth, deadline);
+            let result = agent.search(board, game_state, deck);
+            if agent.is_stopped(0) {
+                log::info!("Search is stopped at depth {}", depth);
+                break;
+            }
+            best = result;
+        }
+
+        best
+    }
+
+    /// Searches the best move for the current player.
+    /// The result of a stopped search is incomplete and should not be used
+    pub fn search(&self, board: &Board, game_state: &GameState, deck: &Deck) -> SearchResult {
+        let mut positions = 0;
+
+        let result = self.alpha_beta(
+            0,
+            std::i32::MIN,
+            std::i32::MAX,
+            &mut board.clone(),
+            &mut game_state.clone(),
+            &mut deck.clone(),
+            None,
+            &mut positions,
+        );
+
+        SearchResult {
+            card: result.best_card,
+            mov: result.best_move,
+            score: result.best_score,
+            positions,
+        }
     }
 
     fn alpha_beta(
@@ -44,11 +152,15 @@ impl AlphaBetaAgent {
         *positions += 1;
         let player_color = game_state.curr_color;
 
-        if depth == self.max_depth || move_result == Some(MoveResult::Win) {
+        if depth == self.max_depth
+            || move_result == Some(MoveResult::Win)
+            || move_result == Some(MoveResult::Tie)
+            || self.is_stopped(*positions)
+        {
             return CalculationResult {
                 best_move: None,
                 best_card: None,
-                best_score: Evaluation::evaluate(&board, &player_color, &move_result),
+                best_score: Evaluation::evaluate(&board.tile_map, &player_color, &move_result),
             };
         }
 
@@ -69,16 +181,31 @@ impl AlphaBetaAgent {
 
             let possible_moves = board
                 .tile_map
-                .generate_possible_moves_for_card(&player_color, &card);
+                .generate_possible_moves_for_card(
+                    &player_color,
+                    &card,
+                    game_state.rules.as_ref(),
+                );
 
             'br: for mov in possible_moves.iter() {
                 let possible_piece_lose =
                     board.tile_map.map[mov.to.y as usize][mov.to.x as usize].clone();
 
-                let result = board.tile_map.make_a_move(mov.from, mov.to);
+                let result = board
+                    .tile_map
+                    .make_a_move(mov.from, mov.to, game_state.rules.as_ref());
 
                 game_state.next_turn();
                 deck.swap_card_with_neutral(card_idx);
+                game_state.push_position(&board.tile_map, deck);
+
+                // repeated positions are scored as draws, the switched turn counts the move
+                let is_draw = game_state.is_draw(game_state.turn);
+                let search_result = if result != MoveResult::Win && is_draw {
+                    MoveResult::Tie
+                } else {
+                    result
+                };
 
                 // go deeper the tree
                 let calc_result = self.alpha_beta(
@@ -88,7 +215,7 @@ impl AlphaBetaAgent {
                     board,
                     game_state,
                     deck,
-                    Some(result),
+                    Some(search_result),
                     positions,
                 );
 
@@ -98,6 +225,7 @@ impl AlphaBetaAgent {
                 board
                     .tile_map
                     .undo_move(mov.to, mov.from, &result, possible_piece_lose);
+                game_state.pop_position();
                 game_state.undo_next_turn();
                 deck.swap_card_with_neutral(card_idx);
 
@@ -148,26 +276,33 @@ impl Agent for AlphaBetaAgent {
         game_state: &GameState,
         deck: &Deck,
     ) -> (Option<Entity>, Option<Move>, i32) {
-        let mut positions = 0;
+        let result = self.search(board, game_state, deck);
 
-        let result = self.alpha_beta(
-            0,
-            std::i32::MIN,
-            std::i32::MAX,
-            &mut board.clone(),
-            &mut game_state.clone(),
-            &mut deck.clone(),
-            None,
-            &mut positions,
-        );
+        log::info!("Evaluation score: {:?}", result.score);
+        log::info!("Analyzed over {:?} positions", result.positions);
+
+        (
+            result.card,
+            result.mov,
+            result.score,
+        )
+    }
+
+    fn generate_move_in_time(
+        &self,
+        board: &Board,
+        game_state: &GameState,
+        deck: &Deck,
+        time_budget: Duration,
+    ) -> (Option<Entity>, Option<Move>, i32) {
+        let result = self.search_until(board, game_state, deck, Instant::now() + time_budget);
 
-        log::info!("Evaluation score: {:?}", result.best_score);
-        log::info!("Analyzed over {:?} positions", positions);
+        log::info!("Evaluation score in {:?}: {:?}", time_budget, result.score);
 
         (
-            result.best_card,
-            result.best_move,
-            result.best_score,
+            result.card,
+            result.mov,
+            result.score,
         )
     }
 
@@ -0,0 +1,223 @@
+use bevy::log;
+use rand::Rng;
+
+use crate::{
+    resources::{
+        card::{Card, CARDS},
+        deck::NEUTRAL_CARD_IDX,
+        draft::Draft,
+        game_state::PlayerColor,
+        tile_map::{MoveResult, TileMap},
+    },
+    rules::rule_set::RuleSet,
+};
+
+use super::evaluation::Evaluation;
+
+const BLUE_HAND: [usize; 2] = [0, 1];
+const RED_HAND: [usize; 2] = [3, 4];
+
+/// Picks cards in the opening draft.
+///
+/// Every possible way to finish the draft is searched with minimax and
+/// each resulting card set is scored by a shallow alpha beta search from the starting position
+#[derive(Debug, Clone)]
+pub struct DraftAgent {
+    pub max_depth: u8,
+}
+
+impl DraftAgent {
+    pub fn new(max_depth: u8) -> Self {
+        Self { max_depth }
+    }
+
+    /// Returns a random card from the pool
+    pub fn random_pick(draft: &Draft) -> u8 {
+        draft.pool[rand::thread_rng().gen_range(0..draft.pool.len())]
+    }
+
+    /// Returns the best card from the pool for the player who picks now
+    pub fn best_pick(&self, draft: &Draft, tile_map: &TileMap, rules: &dyn RuleSet) -> u8 {
+        let mut draft = draft.clone();
+        let player_color = draft.curr_color;
+
+        let mut best_score = match player_color {
+            PlayerColor::Red => std::i32::MIN,
+            PlayerColor::Blue => std::i32::MAX,
+        };
+        let mut best_card = draft.pool[0];
+
+        for card in draft.pool.clone().into_iter() {
+            draft.pick(card);
+            let score =
+                self.draft_search(&mut draft, tile_map, rules, std::i32::MIN, std::i32::MAX);
+            draft.undo_pick();
+
+            log::info!(
+                "Draft card {} has score {}",
+                CARDS[card as usize].name,
+                score
+            );
+
+            if (player_color == PlayerColor::Red && score > best_score)
+                || (player_color == PlayerColor::Blue && score < best_score)
+            {
+                best_score = score;
+                best_card = card;
+            }
+        }
+
+        best_card
+    }
+
+    fn draft_search(
+        &self,
+        draft: &mut Draft,
+        tile_map: &TileMap,
+        rules: &dyn RuleSet,
+        mut alpha: i32,
+        mut beta: i32,
+    ) -> i32 {
+        if draft.is_finished() {
+            return self.evaluate_card_sets(draft, tile_map, rules);
+        }
+
+        let player_color = draft.curr_color;
+        let mut best_score = match player_color {
+            PlayerColor::Red => std::i32::MIN,
+            PlayerColor::Blue => std::i32::MAX,
+        };
+
+        for card in draft.pool.clone().into_iter() {
+            draft.pick(card);
+            let score = self.draft_search(draft, tile_map, rules, alpha, beta);
+            draft.undo_pick();
+
+            if player_color == PlayerColor::Red {
+                best_score = std::cmp::max(best_score, score);
+                alpha = std::cmp::max(alpha, score);
+            } else {
+                best_score = std::cmp::min(best_score, score);
+                beta = std::cmp::min(beta, score);
+            }
+
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        best_score
+    }
+
+    /// Average score over every card that could become neutral
+    fn evaluate_card_sets(&self, draft: &Draft, tile_map: &TileMap, rules: &dyn RuleSet) -> i32 {
+        let mut total = 0;
+
+        for neutral in draft.pool.iter() {
+            let mut cards = [
+                CARDS[draft.blue_picks[0] as usize].clone(),
+                CARDS[draft.blue_picks[1] as usize].clone(),
+                CARDS[*neutral as usize].clone(),
+                CARDS[draft.red_picks[0] as usize].clone(),
+                CARDS[draft.red_picks[1] as usize].clone(),
+            ];
+            cards[0].is_mirrored = true;
+            cards[1].is_mirrored = true;
+
+            total += self.alpha_beta(
+                0,
+                std::i32::MIN,
+                std::i32::MAX,
+                &mut tile_map.clone(),
+                &mut cards,
+                rules,
+                rules.first_player(&cards[NEUTRAL_CARD_IDX]),
+                None,
+            );
+        }
+
+        total / draft.pool.len() as i32
+    }
+
+    fn alpha_beta(
+        &self,
+        depth: u8,
+        mut alpha: i32,
+        mut beta: i32,
+        tile_map: &mut TileMap,
+        cards: &mut [Card; 5],
+        rules: &dyn RuleSet,
+        player_color: PlayerColor,
+        move_result: Option<MoveResult>,
+    ) -> i32 {
+        if depth == self.max_depth || move_result == Some(MoveResult::Win) {
+            return Evaluation::evaluate(tile_map, &player_color, &move_result);
+        }
+
+        let hand = match player_color {
+            PlayerColor::Red => RED_HAND,
+            PlayerColor::Blue => BLUE_HAND,
+        };
+
+        let mut best_score = match player_color {
+            PlayerColor::Red => std::i32::MIN,
+            PlayerColor::Blue => std::i32::MAX,
+        };
+        let mut has_moves = false;
+
+        'cards: for card_idx in hand.into_iter() {
+            let possible_moves =
+                tile_map.generate_possible_moves_for_card(&player_color, &cards[card_idx], rules);
+
+            for mov in possible_moves.iter() {
+                has_moves = true;
+                let possible_piece_lose = tile_map.map[mov.to.y as usize][mov.to.x as usize];
+
+                let result = tile_map.make_a_move(mov.from, mov.to, rules);
+                Self::swap_card_with_neutral(cards, card_idx);
+
+                let score = self.alpha_beta(
+                    depth + 1,
+                    alpha,
+                    beta,
+                    tile_map,
+                    cards,
+                    rules,
+                    player_color.enemy(),
+                    Some(result),
+                );
+
+                tile_map.undo_move(mov.to, mov.from, &result, possible_piece_lose);
+                Self::swap_card_with_neutral(cards, card_idx);
+
+                if player_color == PlayerColor::Red {
+                    best_score = std::cmp::max(best_score, score);
+                    alpha = std::cmp::max(alpha, score);
+                } else {
+                    best_score = std::cmp::min(best_score, score);
+                    beta = std::cmp::min(beta, score);
+                }
+
+                if alpha >= beta {
+                    break 'cards;
+                }
+            }
+        }
+
+        if !has_moves {
+            return Evaluation::evaluate(tile_map, &player_color, &move_result);
+        }
+
+        best_score
+    }
+
+    // Same as `Deck::swap_card_with_neutral`, but for the cards without entities
+    #[inline]
+    fn swap_card_with_neutral(cards: &mut [Card; 5], card_idx: usize) {
+        if card_idx < 2 {
+            cards[card_idx].is_mirrored = false;
+            cards[NEUTRAL_CARD_IDX].is_mirrored = true;
+        }
+        cards.swap(card_idx, NEUTRAL_CARD_IDX);
+    }
+}
@@ -1,9 +1,8 @@
 use crate::{
     components::{coordinates::Coordinates, pieces::PieceKind},
     resources::{
-        board::Board,
         game_state::PlayerColor,
-        tile_map::{MoveResult, BLUE_TEMPLE, RED_TEMPLE},
+        tile_map::{MoveResult, TileMap, BLUE_TEMPLE, RED_TEMPLE},
     },
 };
 
@@ -21,7 +20,7 @@ pub struct Evaluation;
 
 impl Evaluation {
     pub fn evaluate(
-        board: &Board,
+        tile_map: &TileMap,
         curr_color: &PlayerColor,
         move_result: &Option<MoveResult>,
     ) -> i32 {
@@ -37,6 +36,9 @@ impl Evaluation {
             if *move_result == MoveResult::Win {
                 return -sign * 10000;
             }
+            if *move_result == MoveResult::Tie {
+                return 0;
+            }
         }
 
         let mut my_piece_score_sum = 0;
@@ -57,7 +59,7 @@ impl Evaluation {
         let mut enemy_king_coords = enemy_temple;
         let mut king_amount = 0;
 
-        for (y, line) in board.tile_map.map.iter().enumerate() {
+        for (y, line) in tile_map.map.iter().enumerate() {
             for (x, tile) in line.iter().enumerate() {
                 if let Some(piece) = tile.piece {
                     if piece.kind == PieceKind::King && piece.color == curr_color {
@@ -80,7 +82,7 @@ impl Evaluation {
             }
         }
 
-        for (y, line) in board.tile_map.map.iter().enumerate() {
+        for (y, line) in tile_map.map.iter().enumerate() {
             for (x, tile) in line.iter().enumerate() {
                 if let Some(piece) = tile.piece {
                     let piece_score = match piece.kind {
@@ -0,0 +1,608 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command, Stdio},
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use bevy::{log, prelude::Entity};
+
+use crate::{
+    components::coordinates::Coordinates,
+    resources::{
+        board::Board,
+        deck::Deck,
+        engine_options::{EngineConfig, ENGINES_FILE},
+        game_state::{GameState, PlayerColor},
+        tile_map::Move,
+    },
+    rules::rule_set::RuleSetType,
+};
+
+use super::agent::Agent;
+
+// How long a started engine may take to answer `onitama`
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+// How late `bestmove` may come after the move time, and once more after `stop`
+const MOVE_TIME_MARGIN: Duration = Duration::from_millis(1000);
+
+/// Player whose moves are searched by an external engine.
+/// The engine is started on the first move and speaks the protocol of `onitama-engine`.
+/// It runs on a worker thread, so the game is not blocked while the engine thinks
+#[derive(Debug, Clone)]
+pub struct ExternalAgent {
+    config: Option<EngineConfig>,
+    rule_set_type: RuleSetType,
+    move_time: Duration,
+    /// Shared by the clones of the agent. The engine is stopped with the last one
+    worker: Arc<Mutex<Option<EngineWorker>>>,
+    failure: Arc<Mutex<Option<String>>>,
+}
+
+/// Search sent to the engine thread
+#[derive(Debug)]
+struct EngineRequest {
+    rules: &'static str,
+    cards: String,
+    /// Rows of the board and the side to move as in `position board`
+    position: String,
+    move_time: Duration,
+}
+
+/// Answer of the engine to a request
+#[derive(Debug)]
+struct EngineReply {
+    position: String,
+    /// The words after `bestmove` and the last score of the engine
+    result: Result<(String, i32), String>,
+}
+
+/// Channels to the thread talking to the engine
+#[derive(Debug)]
+struct EngineWorker {
+    requests: Sender<EngineRequest>,
+    replies: Receiver<EngineReply>,
+    /// Position of the search which has not been answered yet
+    pending: Option<String>,
+}
+
+impl ExternalAgent {
+    pub fn new(
+        config: Option<EngineConfig>,
+        rule_set_type: RuleSetType,
+        move_time: Duration,
+    ) -> Self {
+        Self {
+            config,
+            rule_set_type,
+            move_time,
+            worker: Arc::new(Mutex::new(None)),
+            failure: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Sends the position to the engine thread, which is started for the first search
+    fn request_move(
+        &self,
+        board: &Board,
+        game_state: &GameState,
+        deck: &Deck,
+        move_time: Duration,
+    ) -> Result<(), String> {
+        let config = self
+            .config
+            .as_ref()
+            .ok_or_else(|| format!("No engine is configured in {}", ENGINES_FILE))?;
+
+        let mut worker = self.worker.lock().unwrap();
+        let worker = worker.get_or_insert_with(|| EngineWorker::spawn(config.clone()));
+
+        let rules = match self.rule_set_type {
+            RuleSetType::Standard => "standard",
+            RuleSetType::WayOfTheStream => "stream",
+        };
+        let cards = deck
+            .cards
+            .iter()
+            .map(|entity| deck.cardboards.get(entity).unwrap().card.name)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let position = engine_position(board, game_state);
+
+        worker.pending = Some(position.clone());
+        worker
+            .requests
+            .send(EngineRequest {
+                rules,
+                cards,
+                position,
+                move_time,
+            })
+            .map_err(|_| format!("{} has exited", config.name))
+    }
+
+    /// Checks the answer of the engine with the rules
+    fn read_reply(
+        &self,
+        reply: Result<(String, i32), String>,
+        board: &Board,
+        game_state: &GameState,
+        deck: &Deck,
+    ) -> Result<(Entity, Move, i32), String> {
+        let name = self
+            .config
+            .as_ref()
+            .map_or("Engine", |config| config.name.as_str());
+        let (best_move, score) = reply?;
+        let (card, mov) = parse_best_move(best_move.split_whitespace(), board, game_state, deck)
+            .map_err(|e| format!("{} {}", name, e))?;
+        // engines score for the side to move, the game shows it for red
+        let score = match game_state.curr_color {
+            PlayerColor::Red => score,
+            PlayerColor::Blue => -score,
+        };
+        Ok((card, mov, score))
+    }
+
+    fn fail(&self, e: String) -> (Option<Entity>, Option<Move>, i32) {
+        log::warn!("External engine has failed: {}", e);
+        *self.failure.lock().unwrap() = Some(e);
+        (None, None, 0)
+    }
+}
+
+impl Agent for ExternalAgent {
+    fn generate_move(
+        &self,
+        board: &Board,
+        game_state: &GameState,
+        deck: &Deck,
+    ) -> (Option<Entity>, Option<Move>, i32) {
+        self.generate_move_in_time(board, game_state, deck, self.move_time)
+    }
+
+    /// Starts the search, the move is read by `poll_move`
+    fn generate_move_in_time(
+        &self,
+        board: &Board,
+        game_state: &GameState,
+        deck: &Deck,
+        time_budget: Duration,
+    ) -> (Option<Entity>, Option<Move>, i32) {
+        match self.request_move(board, game_state, deck, time_budget) {
+            Ok(()) => (None, None, 0),
+            Err(e) => self.fail(e),
+        }
+    }
+
+    fn is_thinking(&self) -> bool {
+        self.worker
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map_or(false, |worker| worker.pending.is_some())
+    }
+
+    fn poll_move(
+        &self,
+        board: &Board,
+        game_state: &GameState,
+        deck: &Deck,
+    ) -> Option<(Option<Entity>, Option<Move>, i32)> {
+        let mut worker = self.worker.lock().unwrap();
+        let worker = worker.as_mut()?;
+        let pending = worker.pending.clone()?;
+
+        let reply = match worker.replies.try_recv() {
+            Ok(reply) => reply,
+            Err(TryRecvError::Empty) => return None,
+            Err(TryRecvError::Disconnected) => {
+                worker.pending = None;
+                return Some(self.fail("Engine thread has stopped".to_owned()));
+            }
+        };
+        // the answers to the positions left by an undo or a new game are dropped
+        if reply.position != pending || pending != engine_position(board, game_state) {
+            log::info!(
+                "Dropping the engine move for an old position {}",
+                reply.position
+            );
+            if reply.position == pending {
+                worker.pending = None;
+            }
+            return None;
+        }
+        worker.pending = None;
+
+        let result = match self.read_reply(reply.result, board, game_state, deck) {
+            Ok((card, mov, score)) => {
+                log::info!("External engine has chosen {:?} with score {}", mov, score);
+                (Some(card), Some(mov), score)
+            }
+            Err(e) => self.fail(e),
+        };
+        Some(result)
+    }
+
+    fn failure(&self) -> Option<String> {
+        self.failure.lock().unwrap().clone()
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Agent> {
+        Box::new(self.clone())
+    }
+}
+
+/// Board rows and the side to move as sent with `position board`
+fn engine_position(board: &Board, game_state: &GameState) -> String {
+    let color = match game_state.curr_color {
+        PlayerColor::Red => "red",
+        PlayerColor::Blue => "blue",
+    };
+    format!("{} {}", board.tile_map.to_rows(), color)
+}
+
+impl EngineWorker {
+    /// Starts the thread. It stops the engine when the agent is dropped
+    fn spawn(config: EngineConfig) -> Self {
+        let (requests, requests_rdr) = mpsc::channel::<EngineRequest>();
+        let (replies_wtr, replies) = mpsc::channel();
+        thread::spawn(move || {
+            let mut process = None;
+            for request in requests_rdr.iter() {
+                let result = search(&config, &mut process, &request);
+                let reply = EngineReply {
+                    position: request.position,
+                    result,
+                };
+                if replies_wtr.send(reply).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            requests,
+            replies,
+            pending: None,
+        }
+    }
+}
+
+/// Runs one search on the engine thread. Returns the words after `bestmove` and the score
+fn search(
+    config: &EngineConfig,
+    process: &mut Option<EngineProcess>,
+    request: &EngineRequest,
+) -> Result<(String, i32), String> {
+    if process.is_none() {
+        *process = Some(EngineProcess::spawn(config, HANDSHAKE_TIMEOUT)?);
+    }
+    let process = process.as_mut().unwrap();
+
+    process.send(&format!("rules {}", request.rules))?;
+    process.send(&format!("cards {}", request.cards))?;
+    process.send(&format!("position board {}", request.position))?;
+    process.send(&format!("go movetime {}", request.move_time.as_millis()))?;
+
+    let mut score = 0;
+    let mut deadline = Instant::now() + request.move_time + MOVE_TIME_MARGIN;
+    let mut is_stopped = false;
+    loop {
+        let line = match process.read_line(deadline)? {
+            Some(line) => line,
+            None if !is_stopped => {
+                log::warn!("{} is late, stopping the search", config.name);
+                process.send("stop")?;
+                is_stopped = true;
+                deadline = Instant::now() + MOVE_TIME_MARGIN;
+                continue;
+            }
+            None => return Err(format!("{} has not moved in time", config.name)),
+        };
+
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("info") => {
+                if let Some(info_score) = parse_score(words) {
+                    score = info_score;
+                }
+            }
+            Some("bestmove") => return Ok((words.collect::<Vec<_>>().join(" "), score)),
+            _ => log::info!("{}: {}", config.name, line),
+        }
+    }
+}
+
+/// Running engine. Its output is read by a separate thread, so the reads can time out
+#[derive(Debug)]
+struct EngineProcess {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+}
+
+impl EngineProcess {
+    /// Starts the engine and waits for `onitamaok` until the timeout
+    fn spawn(config: &EngineConfig, handshake_timeout: Duration) -> Result<Self, String> {
+        log::info!(
+            "Starting {}: {} {:?}",
+            config.name,
+            config.command,
+            config.args
+        );
+        let mut child = Command::new(&config.command)
+            .args(&config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Could not start {}: {}", config.name, e))?;
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            // the channel is closed when the engine exits
+            for line in BufReader::new(stdout).lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut process = Self {
+            child,
+            stdin,
+            lines,
+        };
+        process.send("onitama")?;
+
+        let deadline = Instant::now() + handshake_timeout;
+        loop {
+            match process.read_line(deadline)? {
+                Some(line) if line.trim() == "onitamaok" => return Ok(process),
+                Some(_) => {}
+                None => return Err(format!("{} has not answered to onitama", config.name)),
+            }
+        }
+    }
+
+    fn send(&mut self, command: &str) -> Result<(), String> {
+        writeln!(self.stdin, "{}", command)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|_| "Engine has exited".to_owned())
+    }
+
+    /// Returns `None` if there was no line until the deadline
+    fn read_line(&self, deadline: Instant) -> Result<Option<String>, String> {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        match self.lines.recv_timeout(timeout) {
+            Ok(line) => Ok(Some(line)),
+            Err(RecvTimeoutError::Timeout) => Ok(None),
+            Err(RecvTimeoutError::Disconnected) => Err("Engine has exited".to_owned()),
+        }
+    }
+}
+
+impl Drop for EngineProcess {
+    fn drop(&mut self) {
+        let _ = self.send("quit");
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// Reads the score of `info ... score <score> ...`
+fn parse_score<'a>(mut words: impl Iterator<Item = &'a str>) -> Option<i32> {
+    words
+        .by_ref()
+        .find(|word| *word == "score")
+        .and_then(|_| words.next())
+        .and_then(|score| score.parse().ok())
+}
+
+/// Reads `bestmove <card> <from x> <from y> <to x> <to y>` and checks it with the rules
+fn parse_best_move<'a>(
+    mut words: impl Iterator<Item = &'a str>,
+    board: &Board,
+    game_state: &GameState,
+    deck: &Deck,
+) -> Result<(Entity, Move), String> {
+    let card_name = match words.next() {
+        Some("none") | None => return Err("has no move".to_owned()),
+        Some(card_name) => card_name,
+    };
+
+    let mut coordinates = [0u8; 4];
+    for value in coordinates.iter_mut() {
+        *value = words
+            .next()
+            .and_then(|word| word.parse().ok())
+            .ok_or_else(|| "has sent a broken move".to_owned())?;
+    }
+    let mov = Move {
+        from: Coordinates {
+            x: coordinates[0],
+            y: coordinates[1],
+        },
+        to: Coordinates {
+            x: coordinates[2],
+            y: coordinates[3],
+        },
+    };
+
+    deck.get_player_cards(game_state)
+        .into_iter()
+        .find(|(_, card)| card.name.eq_ignore_ascii_case(card_name))
+        .filter(|(_, card)| {
+            board
+                .tile_map
+                .generate_possible_moves_for_card(
+                    &game_state.curr_color,
+                    card,
+                    game_state.rules.as_ref(),
+                )
+                .contains(&mov)
+        })
+        .map(|(entity, _)| (entity, mov))
+        .ok_or_else(|| format!("has played an illegal move {} {:?}", card_name, mov))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bevy::prelude::Vec2;
+
+    use super::*;
+    use crate::{
+        ai::human::Human,
+        bounds::Bounds2,
+        components::card_board::CardBoard,
+        resources::{
+            card::CARDS,
+            draw_rules::DrawRules,
+            game_state::{Player, PlayerType},
+            handicap::Handicap,
+            tile_map::TileMap,
+        },
+        rules::standard::StandardRules,
+    };
+
+    /// Start of a game with Tiger and Crab in the hand of blue, which moves first
+    fn start() -> (Board, GameState, Deck) {
+        let bounds = Bounds2 {
+            position: Vec2::ZERO,
+            size: Vec2::ZERO,
+        };
+        let board = Board {
+            bounds,
+            tile_size: 1.,
+            scale: 1.,
+            padding: 0.,
+            move_duration: Duration::ZERO,
+            tile_map: TileMap::new(),
+            entity: Entity::from_raw(0),
+            is_flipped: false,
+        };
+
+        let player = || Player {
+            agent: Box::new(Human),
+            player_type: PlayerType::Human,
+        };
+        let mut game_state = GameState::new(
+            player(),
+            player(),
+            Handicap::default(),
+            Box::new(StandardRules::new(DrawRules {
+                repetitions: None,
+                move_limit: None,
+            })),
+        );
+        game_state.curr_color = PlayerColor::Blue;
+
+        let mut deck = Deck {
+            cardboards: HashMap::new(),
+            cards: vec![],
+        };
+        for (idx, name) in ["Tiger", "Crab", "Monkey", "Crane", "Dragon"]
+            .into_iter()
+            .enumerate()
+        {
+            let mut card = CARDS.iter().find(|card| card.name == name).unwrap().clone();
+            card.is_mirrored = idx < 2;
+            let entity = Entity::from_raw(idx as u32 + 1);
+            deck.cardboards.insert(entity, CardBoard { card, bounds });
+            deck.cards.push(entity);
+        }
+        (board, game_state, deck)
+    }
+
+    fn best_move(line: &str) -> Result<(Entity, Move), String> {
+        let (board, game_state, deck) = start();
+        parse_best_move(line.split_whitespace(), &board, &game_state, &deck)
+    }
+
+    fn mov(from: (u8, u8), to: (u8, u8)) -> Move {
+        Move {
+            from: Coordinates {
+                x: from.0,
+                y: from.1,
+            },
+            to: Coordinates { x: to.0, y: to.1 },
+        }
+    }
+
+    #[test]
+    fn reads_a_legal_move() {
+        let crab = Entity::from_raw(2);
+        let forward = mov((1, 4), (1, 3));
+        assert_eq!(best_move("Crab 1 4 1 3"), Ok((crab, forward)));
+        assert_eq!(best_move("crab 1 4 1 3 extra"), Ok((crab, forward)));
+    }
+
+    #[test]
+    fn rejects_illegal_moves() {
+        // Crab does not move diagonally
+        assert!(best_move("Crab 1 4 0 3").is_err());
+        // the red pieces are not moved by blue
+        assert!(best_move("Crab 1 0 1 1").is_err());
+        assert!(best_move("Tiger 1 4 1 7").is_err());
+    }
+
+    #[test]
+    fn rejects_cards_out_of_the_hand() {
+        assert!(best_move("Unicorn 1 4 1 3").is_err());
+        // Crane is in the hand of red and Monkey is neutral
+        assert!(best_move("Crane 1 4 1 3").is_err());
+        assert!(best_move("Monkey 1 4 0 3").is_err());
+    }
+
+    #[test]
+    fn rejects_broken_moves() {
+        assert_eq!(best_move("none"), Err("has no move".to_owned()));
+        assert_eq!(best_move(""), Err("has no move".to_owned()));
+        assert!(best_move("Crab 1 4 1").is_err());
+        assert!(best_move("Crab 1 4 one 3").is_err());
+        assert!(best_move("Crab 1 4 -1 3").is_err());
+    }
+
+    #[test]
+    fn reads_the_score() {
+        let score = |line: &str| parse_score(line.split_whitespace());
+        assert_eq!(score("depth 4 score 25 nodes 1000"), Some(25));
+        assert_eq!(score("depth 4 score -300"), Some(-300));
+        assert_eq!(score("depth 4 nodes 1000"), None);
+        assert_eq!(score("depth 4 score"), None);
+        assert_eq!(score("depth 4 score mate nodes 1000"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn gives_up_on_a_silent_engine() {
+        let config = |command: &str| EngineConfig {
+            name: "Test engine".to_owned(),
+            command: command.to_owned(),
+            args: vec![],
+        };
+        let timeout = Duration::from_millis(200);
+
+        // cat repeats `onitama` instead of answering it
+        let started = Instant::now();
+        let e = EngineProcess::spawn(&config("cat"), timeout).unwrap_err();
+        assert_eq!(e, "Test engine has not answered to onitama");
+        assert!(started.elapsed() < HANDSHAKE_TIMEOUT);
+
+        assert!(EngineProcess::spawn(&config("true"), timeout).is_err());
+        assert!(EngineProcess::spawn(&config("no-such-onitama-engine"), timeout).is_err());
+    }
+}
@@ -3,3 +3,6 @@ pub mod human;
 pub mod random_agent;
 pub mod alpha_beta;
 pub mod evaluation;
+pub mod draft;
+pub mod remote;
+pub mod external;
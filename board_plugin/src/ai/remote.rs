@@ -0,0 +1,27 @@
+use bevy::{log, prelude::Entity};
+
+use crate::resources::{board::Board, deck::Deck, game_state::GameState, tile_map::Move};
+
+use super::agent::Agent;
+
+/// Player on the other side of the network connection. Its moves are applied by the network
+/// plugin, the agent never generates any
+#[derive(Debug, Clone)]
+pub struct RemoteAgent;
+
+impl Agent for RemoteAgent {
+    fn generate_move(
+        &self,
+        _board: &Board,
+        _game_state: &GameState,
+        _deck: &Deck,
+    ) -> (Option<Entity>, Option<Move>, i32) {
+        // the moves are received from the network connection, a stray request is ignored
+        log::warn!("A move of the remote player was requested from the agent");
+        (None, None, 0)
+    }
+
+    fn clone_dyn(&self) -> Box<dyn Agent> {
+        Box::new(self.clone())
+    }
+}
@@ -1,5 +1,7 @@
 use bevy::{prelude::Vec2, window::Window};
 
+use crate::resources::pointer::Pointer;
+
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
 #[derive(Debug, Clone, Copy)]
 pub struct Bounds2 {
@@ -24,4 +26,22 @@ impl Bounds2 {
         // Bounds check
         self.in_bounds(position)
     }
+
+    /// Bounds grown by `margin` on every side
+    #[inline]
+    pub fn expanded(&self, margin: f32) -> Self {
+        Self {
+            position: self.position - Vec2::splat(margin),
+            size: self.size + Vec2::splat(2. * margin),
+        }
+    }
+
+    /// Checks the pointer position, a touch may miss the bounds by a margin
+    #[inline]
+    pub fn in_bounds_pointer(&self, window: &Window, pointer: &Pointer) -> bool {
+        let margin = pointer.hit_margin(self.size.min_element());
+        pointer.position.map_or(false, |position| {
+            self.expanded(margin).in_bounds_window(window, position)
+        })
+    }
 }
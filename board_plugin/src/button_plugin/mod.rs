@@ -1,6 +1,12 @@
 use bevy::{log, prelude::*};
 
-use crate::resources::app_state::AppState;
+use crate::{
+    events::{
+        DrawOfferEvent, FlipBoardEvent, HintEvent, ProcessWinConditionEvent, SaveGameEvent,
+        UndoMoveEvent,
+    },
+    resources::{app_state::AppState, draw_offer::DrawOffer, game_state::GameState},
+};
 
 struct ButtonPluginData {
     pub camera_entity: Entity,
@@ -12,8 +18,17 @@ pub struct ButtonPlugin;
 impl Plugin for ButtonPlugin {
     fn build(&self, app: &mut App) {
         app.add_system_set(SystemSet::on_enter(AppState::InProgress).with_system(setup_ui))
-            .add_system_set(SystemSet::on_update(AppState::InProgress).with_system(input_handler))
-            .add_system_set(SystemSet::on_update(AppState::GameEnd).with_system(input_handler))
+            .add_system_set(
+                SystemSet::on_update(AppState::InProgress)
+                    .with_system(input_handler.label("game_buttons"))
+                    .with_system(keyboard_shortcuts.label("game_buttons"))
+                    .with_system(update_draw_button_text),
+            )
+            .add_system_set(
+                SystemSet::on_update(AppState::GameEnd)
+                    .with_system(input_handler)
+                    .with_system(keyboard_shortcuts),
+            )
             .add_system_set(SystemSet::on_exit(AppState::GameEnd).with_system(cleanup));
         // .add_system_set(SystemSet::on_enter(AppState::MainMenu).with_system(cleanup));
     }
@@ -26,6 +41,17 @@ impl Plugin for ButtonPlugin {
 pub enum ButtonAction {
     NewSetup,
     NewGame,
+    /// Offers a draw or accepts the offer of the opponent
+    OfferDraw,
+    Resign,
+    /// Saves the game to continue it from the main menu
+    SaveGame,
+    /// Takes back the last move of the human
+    Undo,
+    /// Shows the move the bot would make for the human
+    Hint,
+    /// Turns the board upside down
+    FlipBoard,
 }
 
 #[derive(Debug)]
@@ -42,6 +68,14 @@ fn input_handler(
         (Changed<Interaction>, With<Button>),
     >,
     mut state: ResMut<State<AppState>>,
+    game_state: Option<Res<GameState>>,
+    draw_offer: Option<Res<DrawOffer>>,
+    mut draw_offer_ewr: EventWriter<DrawOfferEvent>,
+    mut process_win_condition_ewr: EventWriter<ProcessWinConditionEvent>,
+    mut save_game_ewr: EventWriter<SaveGameEvent>,
+    mut undo_move_ewr: EventWriter<UndoMoveEvent>,
+    mut hint_ewr: EventWriter<HintEvent>,
+    mut flip_board_ewr: EventWriter<FlipBoardEvent>,
 ) {
     for (interaction, action, mut color) in interaction_query.iter_mut() {
         match *interaction {
@@ -59,15 +93,53 @@ fn input_handler(
                             state.set(AppState::MainMenu).unwrap();
                         }
                     }
-                    ButtonAction::NewGame => {
+                    ButtonAction::NewGame => new_game(&mut state),
+                    ButtonAction::SaveGame => {
                         if state.current() == &AppState::InProgress {
-                            log::info!("Creating a new game during the game");
-                            state.set(AppState::GameEnd).unwrap();
+                            log::info!("Saving the game");
+                            save_game_ewr.send(SaveGameEvent);
                         }
+                    }
+                    ButtonAction::Undo => {
+                        if state.current() == &AppState::InProgress {
+                            log::info!("Taking back the move");
+                            undo_move_ewr.send(UndoMoveEvent);
+                        }
+                    }
+                    ButtonAction::Hint => {
+                        if state.current() == &AppState::InProgress {
+                            log::info!("Asking for a hint");
+                            hint_ewr.send(HintEvent);
+                        }
+                    }
+                    ButtonAction::FlipBoard => {
+                        if state.current() == &AppState::InProgress {
+                            log::info!("Flipping the board");
+                            flip_board_ewr.send(FlipBoardEvent);
+                        }
+                    }
+                    ButtonAction::OfferDraw | ButtonAction::Resign => {
+                        if state.current() != &AppState::InProgress {
+                            continue;
+                        }
+                        // the buttons are used by the human, bots and remote players decide on their own
+                        let color = match game_state.as_ref().and_then(|gs| gs.acting_human()) {
+                            Some(color) => color,
+                            None => continue,
+                        };
 
-                        if state.current() == &AppState::GameEnd {
-                            log::info!("Creating a new game after a game end!");
-                            state.set(AppState::InProgress).unwrap();
+                        if *action == ButtonAction::Resign {
+                            log::info!("{:?} resigns", color);
+                            process_win_condition_ewr.send(ProcessWinConditionEvent::Resign(color));
+                        } else if draw_offer
+                            .as_ref()
+                            .map_or(false, |d| d.is_offered_to(&color))
+                        {
+                            log::info!("{:?} accepts the draw", color);
+                            draw_offer_ewr.send(DrawOfferEvent::Accept(color));
+                        } else {
+                            log::info!("{:?} offers a draw", color);
+                            draw_offer_ewr.send(DrawOfferEvent::Offer(color));
                         }
                     }
                 }
@@ -82,6 +154,77 @@ fn input_handler(
     }
 }
 
+fn new_game(state: &mut State<AppState>) {
+    if state.current() == &AppState::InProgress {
+        log::info!("Creating a new game during the game");
+        state.set(AppState::GameEnd).unwrap();
+    }
+
+    if state.current() == &AppState::GameEnd {
+        log::info!("Creating a new game after a game end!");
+        state.set(AppState::InProgress).unwrap();
+    }
+}
+
+/// N starts a new game like the button, U takes back a move, H shows a hint and F flips the board
+fn keyboard_shortcuts(
+    keys: Res<Input<KeyCode>>,
+    mut state: ResMut<State<AppState>>,
+    mut undo_move_ewr: EventWriter<UndoMoveEvent>,
+    mut hint_ewr: EventWriter<HintEvent>,
+    mut flip_board_ewr: EventWriter<FlipBoardEvent>,
+) {
+    if keys.just_pressed(KeyCode::N) {
+        new_game(&mut state);
+    } else if state.current() == &AppState::InProgress {
+        if keys.just_pressed(KeyCode::U) {
+            log::info!("Taking back the move");
+            undo_move_ewr.send(UndoMoveEvent);
+        } else if keys.just_pressed(KeyCode::H) {
+            log::info!("Asking for a hint");
+            hint_ewr.send(HintEvent);
+        } else if keys.just_pressed(KeyCode::F) {
+            log::info!("Flipping the board");
+            flip_board_ewr.send(FlipBoardEvent);
+        }
+    }
+}
+
+/// The draw button accepts the draw when the opponent of the human has offered it
+fn update_draw_button_text(
+    game_state: Option<Res<GameState>>,
+    draw_offer: Option<Res<DrawOffer>>,
+    button_query: Query<(&ButtonAction, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    let (game_state, draw_offer) = match (game_state, draw_offer) {
+        (Some(game_state), Some(draw_offer)) => (game_state, draw_offer),
+        _ => return,
+    };
+    if !game_state.is_changed() && !draw_offer.is_changed() {
+        return;
+    }
+
+    let is_offered = game_state
+        .acting_human()
+        .map_or(false, |color| draw_offer.is_offered_to(&color));
+    let value = match is_offered {
+        true => "ACCEPT DRAW",
+        false => "OFFER DRAW",
+    };
+
+    for (action, children) in button_query.iter() {
+        if *action != ButtonAction::OfferDraw {
+            continue;
+        }
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = value.to_owned();
+            }
+        }
+    }
+}
+
 fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
     let camera_entity = commands.spawn_bundle(UiCameraBundle::default()).id();
 
@@ -106,6 +249,48 @@ fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
         .insert(Name::new("UI"))
         .with_children(|parent| {
             let font = asset_server.load("fonts/pixeled.ttf");
+            setup_single_menu(
+                parent,
+                "RESIGN",
+                button_materials.normal.into(),
+                font.clone(),
+                ButtonAction::Resign,
+            );
+            setup_single_menu(
+                parent,
+                "OFFER DRAW",
+                button_materials.normal.into(),
+                font.clone(),
+                ButtonAction::OfferDraw,
+            );
+            setup_single_menu(
+                parent,
+                "UNDO",
+                button_materials.normal.into(),
+                font.clone(),
+                ButtonAction::Undo,
+            );
+            setup_single_menu(
+                parent,
+                "HINT",
+                button_materials.normal.into(),
+                font.clone(),
+                ButtonAction::Hint,
+            );
+            setup_single_menu(
+                parent,
+                "FLIP BOARD",
+                button_materials.normal.into(),
+                font.clone(),
+                ButtonAction::FlipBoard,
+            );
+            setup_single_menu(
+                parent,
+                "SAVE GAME",
+                button_materials.normal.into(),
+                font.clone(),
+                ButtonAction::SaveGame,
+            );
             setup_single_menu(
                 parent,
                 "NEW GAME",
@@ -0,0 +1,13 @@
+use bevy::{core::Timer, math::Vec3, prelude::Component};
+
+/// Deck card which slides to its new slot after a move. A card which changes sides between
+/// a hand of the blue player and the middle is turned over its horizontal axis on the way,
+/// its grid is redrawn from `Card::is_mirrored` when the card is seen edge-on
+#[derive(Debug, Clone, Component)]
+pub struct CardTween {
+    pub start: Vec3,
+    pub end: Vec3,
+    /// If the card is turned over on the way
+    pub is_turned: bool,
+    pub timer: Timer,
+}
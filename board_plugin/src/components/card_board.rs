@@ -2,7 +2,7 @@ use bevy::prelude::*;
 
 use crate::{
     bounds::Bounds2,
-    resources::{card::Card, game_state::PlayerColor},
+    resources::{card::Card, game_state::PlayerColor, pointer::Pointer},
 };
 
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
@@ -41,4 +41,9 @@ impl CardBoard {
         // Bounds check
         self.bounds.in_bounds(position)
     }
+
+    #[inline]
+    pub fn in_pointer_bounds(&self, window: &Window, pointer: &Pointer) -> bool {
+        self.bounds.in_bounds_pointer(window, pointer)
+    }
 }
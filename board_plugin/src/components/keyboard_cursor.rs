@@ -0,0 +1,9 @@
+use bevy::prelude::Component;
+
+use super::coordinates::Coordinates;
+
+/// Tile of the board chosen with the arrow keys, Enter presses on it
+#[derive(Debug, Clone, Copy, Component)]
+pub struct KeyboardCursor {
+    pub coordinates: Coordinates,
+}
@@ -0,0 +1,13 @@
+use bevy::prelude::Component;
+
+use super::coordinates::Coordinates;
+
+/// Sprite above a tile of the last move, the origin or the destination
+#[derive(Debug, Clone, Copy, Component)]
+pub struct LastMoveTile {
+    pub coordinates: Coordinates,
+}
+
+/// Frame behind the card played for the last move
+#[derive(Debug, Clone, Copy, Component)]
+pub struct LastMoveCard;
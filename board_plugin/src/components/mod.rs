@@ -6,4 +6,10 @@ pub mod card_board;
 pub mod background;
 pub mod guide_text_timer;
 pub mod board_tile;
-pub mod allowed_move;
\ No newline at end of file
+pub mod allowed_move;
+pub mod piece_animation;
+pub mod card_animation;
+
+pub mod keyboard_cursor;
+pub mod last_move;
+pub mod move_preview;
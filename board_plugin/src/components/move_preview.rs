@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// Sprite above a tile reached by the card the player holds the pointer on
+#[derive(Debug, Clone, Copy, Component)]
+pub struct MovePreviewTile;
@@ -0,0 +1,16 @@
+use bevy::{core::Timer, math::Vec3, prelude::Component};
+
+/// Piece sprite which slides from the tile it has left to its new tile.
+/// Translations are relative to the new tile
+#[derive(Debug, Clone, Component)]
+pub struct PieceTween {
+    pub start: Vec3,
+    pub end: Vec3,
+    pub timer: Timer,
+}
+
+/// Sprite of a captured piece which fades out and is despawned afterwards
+#[derive(Debug, Clone, Component)]
+pub struct FadingPiece {
+    pub timer: Timer,
+}
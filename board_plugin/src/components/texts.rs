@@ -8,4 +8,11 @@ pub struct GuideText;
 pub struct TurnText;
 
 #[derive(Debug, Clone, Component)]
-pub struct EvaluationText;
\ No newline at end of file
+pub struct EvaluationText;
+
+#[derive(Debug, Clone, Component)]
+pub struct ClockText;
+
+/// Number of the board row printed to the left of the board
+#[derive(Debug, Clone, Component)]
+pub struct RankLabel(pub u8);
@@ -0,0 +1,410 @@
+use bevy::{
+    log,
+    prelude::*,
+    window::{WindowId, WindowResized},
+};
+
+use crate::{
+    ai::draft::DraftAgent,
+    bounds::Bounds2,
+    components::{background::Background, card_index::CardIndex},
+    resources::{
+        app_state::AppState,
+        board_assets::BoardAssets,
+        board_options::TileSize,
+        card::CARDS,
+        deck_options::DeckOptions,
+        draft::Draft,
+        draw_rules::DrawRules,
+        game_state::{PlayerColor, PlayerType},
+        handicap::Handicap,
+        physical_deck::PhysicalDeck,
+        pointer::Pointer,
+        selected::SelectedPlayers,
+        tile_map::TileMap,
+    },
+    rules::rule_set::RuleSetType,
+    BoardPlugin,
+};
+
+// Depth of the search which scores the card sets for the bot
+const DRAFT_SEARCH_DEPTH: u8 = 2;
+
+struct DraftData {
+    camera_entity: Entity,
+    title_root: Entity,
+    // bounds are needed to pick a card with the mouse or a touch
+    cards: Vec<(Entity, Bounds2)>,
+    /// Size of a card before it is scaled to the window
+    card_size: Vec2,
+}
+
+pub struct DraftPlugin;
+
+impl Plugin for DraftPlugin {
+    fn build(&self, app: &mut App) {
+        log::info!("Loaded draft plugin!");
+        app.add_system_set(SystemSet::on_enter(AppState::Draft).with_system(setup_ui::<AppState>))
+            .add_system_set(
+                SystemSet::on_update(AppState::Draft)
+                    .with_system(human_pick_system.label("draft_pick"))
+                    .with_system(bot_pick_system.label("draft_pick"))
+                    .with_system(color_picked_card.after("draft_pick"))
+                    .with_system(update_draft_title)
+                    .with_system(relayout_draft_cards)
+                    .with_system(finish_draft.after("draft_pick")),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::Draft).with_system(cleanup));
+
+        app.add_event::<CardPickedEvent>();
+    }
+}
+
+#[derive(Component)]
+struct DraftTitle;
+
+struct CardPickedEvent {
+    entity: Entity,
+    color: PlayerColor,
+}
+
+#[inline]
+fn picker_type(draft: &Draft, selected_players: &SelectedPlayers) -> PlayerType {
+    match draft.curr_color {
+        PlayerColor::Red => selected_players.red_player,
+        PlayerColor::Blue => selected_players.blue_player,
+    }
+}
+
+fn human_pick_system(
+    windows: Res<Windows>,
+    draft_data: Res<DraftData>,
+    selected_players: Res<SelectedPlayers>,
+    mut draft: ResMut<Draft>,
+    pointer: Res<Pointer>,
+    cards_q: Query<&CardIndex>,
+    mut card_picked_ewr: EventWriter<CardPickedEvent>,
+) {
+    if draft.is_finished() || picker_type(&draft, &selected_players) != PlayerType::Human {
+        return;
+    }
+
+    if !pointer.just_pressed {
+        return;
+    }
+
+    let window = windows.get_primary().unwrap();
+    for (entity, bounds) in draft_data.cards.iter() {
+        if !bounds.in_bounds_pointer(window, &pointer) {
+            continue;
+        }
+
+        if let Ok(card_index) = cards_q.get(*entity) {
+            if !draft.pool.contains(&card_index.0) {
+                log::info!(
+                    "Card {} is already picked",
+                    CARDS[card_index.0 as usize].name
+                );
+                return;
+            }
+
+            let color = draft.curr_color;
+            draft.pick(card_index.0);
+            card_picked_ewr.send(CardPickedEvent {
+                entity: *entity,
+                color,
+            });
+            return;
+        }
+    }
+}
+
+fn bot_pick_system(
+    time: Res<Time>,
+    draft_data: Res<DraftData>,
+    selected_players: Res<SelectedPlayers>,
+    handicap: Res<Handicap>,
+    draw_rules: Res<DrawRules>,
+    rule_set_type: Res<RuleSetType>,
+    mut draft: ResMut<Draft>,
+    cards_q: Query<&CardIndex>,
+    mut card_picked_ewr: EventWriter<CardPickedEvent>,
+) {
+    let player_type = picker_type(&draft, &selected_players);
+    if draft.is_finished() || player_type == PlayerType::Human {
+        return;
+    }
+
+    draft.bot_timer.tick(time.delta());
+    if !draft.bot_timer.finished() {
+        return;
+    }
+    draft.bot_timer.reset();
+
+    let card = match player_type {
+        PlayerType::AlphaBeta => DraftAgent::new(DRAFT_SEARCH_DEPTH).best_pick(
+            &draft,
+            &TileMap::with_handicap(&handicap),
+            rule_set_type.build(*draw_rules).as_ref(),
+        ),
+        _ => DraftAgent::random_pick(&draft),
+    };
+
+    log::info!(
+        "Bot {:?} picked a card {}",
+        draft.curr_color,
+        CARDS[card as usize].name
+    );
+
+    let color = draft.curr_color;
+    draft.pick(card);
+
+    for (entity, _) in draft_data.cards.iter() {
+        if let Ok(card_index) = cards_q.get(*entity) {
+            if card_index.0 == card {
+                card_picked_ewr.send(CardPickedEvent {
+                    entity: *entity,
+                    color,
+                });
+                break;
+            }
+        }
+    }
+}
+
+fn color_picked_card(
+    board_assets: Res<BoardAssets>,
+    cards_q: Query<&Children, With<CardIndex>>,
+    mut sprites: Query<&mut Sprite, With<Background>>,
+    mut card_picked_rdr: EventReader<CardPickedEvent>,
+) {
+    for event in card_picked_rdr.iter() {
+        if let Ok(children) = cards_q.get(event.entity) {
+            'child: for child in children.iter() {
+                if let Ok(mut sprite) = sprites.get_mut(*child) {
+                    sprite.color = match event.color {
+                        PlayerColor::Red => board_assets.selected_red_card_material.color,
+                        PlayerColor::Blue => board_assets.selected_blue_card_material.color,
+                    };
+                    break 'child;
+                }
+            }
+        }
+    }
+}
+
+fn update_draft_title(
+    draft: Res<Draft>,
+    board_assets: Res<BoardAssets>,
+    mut texts: Query<&mut Text, With<DraftTitle>>,
+) {
+    if !draft.is_changed() {
+        return;
+    }
+
+    let (value, color) = match draft.curr_color {
+        PlayerColor::Red => ("Red picks a card", Color::RED),
+        PlayerColor::Blue => ("Blue picks a card", Color::CYAN),
+    };
+
+    for mut text in texts.iter_mut() {
+        *text = Text::with_section(
+            value,
+            TextStyle {
+                font: board_assets.font.clone(),
+                font_size: 60.,
+                color,
+            },
+            TextAlignment {
+                vertical: VerticalAlign::Center,
+                horizontal: HorizontalAlign::Center,
+            },
+        );
+    }
+}
+
+fn finish_draft(
+    draft: Res<Draft>,
+    mut physical_deck: ResMut<PhysicalDeck>,
+    mut state: ResMut<State<AppState>>,
+) {
+    if !draft.is_changed() || !draft.is_finished() {
+        return;
+    }
+
+    let indices = draft.deal();
+    log::info!("Draft is finished with the cards {:?}", indices);
+    physical_deck.clear();
+    physical_deck.take_cards_from_indices(&indices);
+    state.set(AppState::InProgress).unwrap();
+}
+
+fn setup_ui<T>(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    deck_options: Res<DeckOptions>,
+    windows: Res<Windows>,
+    board_assets: Res<BoardAssets>,
+    draft: Res<Draft>,
+) {
+    let camera_entity = commands.spawn_bundle(UiCameraBundle::default()).id();
+
+    let window = windows.get_primary().unwrap();
+    let window_size = Vec2::new(window.width(), window.height());
+
+    let tile_size = match deck_options.tile_size {
+        TileSize::Fixed(size) => size,
+        TileSize::Adaptive { min, max } => {
+            // (5, 5) - board size
+            BoardPlugin::<T>::adaptive_tile_size(window_size, (min, max), (5, 5))
+        }
+    };
+
+    let board_size = Vec2::new(5. * tile_size, 5. * tile_size);
+
+    let title_root = commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(15.)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(0.0),
+                    top: Val::Px(0.0),
+                    ..Default::default()
+                },
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .insert(Name::new("Draft title"))
+        .with_children(|parent| {
+            let font = asset_server.load("fonts/pixeled.ttf");
+
+            parent
+                .spawn_bundle(TextBundle {
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            font,
+                            font_size: 60.,
+                            color: Color::WHITE,
+                        },
+                        TextAlignment {
+                            vertical: VerticalAlign::Center,
+                            horizontal: HorizontalAlign::Center,
+                        },
+                    ),
+                    ..Default::default()
+                })
+                .insert(DraftTitle);
+        })
+        .id();
+
+    // spawning the card pool in one row in the center of the screen
+    let mut cards: Vec<(Entity, Bounds2)> = Vec::with_capacity(draft.pool.len());
+    let (scale, positions) = draft_card_places(window_size, board_size, draft.pool.len());
+
+    for (card_idx, position) in draft.pool.iter().zip(positions) {
+        let card = &CARDS[*card_idx as usize];
+
+        let card_entity = commands
+            .spawn()
+            .insert(Transform {
+                translation: position.extend(0.),
+                scale: Vec3::new(scale, scale, 1.),
+                ..Default::default()
+            })
+            .insert(GlobalTransform::default())
+            .insert(CardIndex(*card_idx))
+            .insert(Name::new(card.name))
+            .with_children(|builder| {
+                BoardPlugin::<T>::spawn_deck_card_board(
+                    builder,
+                    board_size,
+                    card,
+                    &board_assets,
+                    deck_options.tile_padding,
+                    tile_size,
+                    false,
+                );
+            })
+            .id();
+
+        let bounds = Bounds2 {
+            position: position - board_size * scale / 2.,
+            size: board_size * scale,
+        };
+        cards.push((card_entity, bounds));
+    }
+
+    commands.insert_resource(DraftData {
+        camera_entity,
+        title_root,
+        cards,
+        card_size: board_size,
+    });
+}
+
+/// Centers of the cards in the pool and the scale of the cards, the row fits the window
+fn draft_card_places(window_size: Vec2, card_size: Vec2, amount: usize) -> (f32, Vec<Vec2>) {
+    let offset = card_size.x / 2.;
+    let row_width = amount as f32 * (card_size.x + offset) - offset;
+    let scale = (window_size.x * 0.95 / row_width).min(1.);
+
+    let starting_x = -((amount - 1) as f32) * (card_size.x + offset) * scale / 2.;
+    let positions = (0..amount)
+        .map(|i| Vec2::new(starting_x + (card_size.x + offset) * scale * i as f32, 0.))
+        .collect();
+
+    (scale, positions)
+}
+
+/// Places the pool again when the window is resized
+fn relayout_draft_cards(
+    mut draft_data: ResMut<DraftData>,
+    mut transforms_q: Query<&mut Transform, With<CardIndex>>,
+    mut window_resized_rdr: EventReader<WindowResized>,
+) {
+    let event = match window_resized_rdr
+        .iter()
+        .filter(|event| event.id == WindowId::primary())
+        .last()
+    {
+        Some(event) => event,
+        None => return,
+    };
+
+    let card_size = draft_data.card_size;
+    let (scale, positions) = draft_card_places(
+        Vec2::new(event.width, event.height),
+        card_size,
+        draft_data.cards.len(),
+    );
+    for ((entity, bounds), position) in draft_data.cards.iter_mut().zip(positions) {
+        *bounds = Bounds2 {
+            position: position - card_size * scale / 2.,
+            size: card_size * scale,
+        };
+        if let Ok(mut transform) = transforms_q.get_mut(*entity) {
+            transform.translation = position.extend(transform.translation.z);
+            transform.scale = Vec3::new(scale, scale, 1.);
+        }
+    }
+}
+
+fn cleanup(mut commands: Commands, draft_data: Res<DraftData>) {
+    commands.entity(draft_data.title_root).despawn_recursive();
+    commands
+        .entity(draft_data.camera_entity)
+        .despawn_recursive();
+
+    for (entity, _) in draft_data.cards.iter() {
+        commands.entity(*entity).despawn_recursive();
+    }
+
+    commands.remove_resource::<DraftData>();
+    commands.remove_resource::<Draft>();
+}
@@ -1,4 +1,4 @@
-use crate::{components::{card_index::CardIndex, coordinates::Coordinates}, resources::tile_map::{MoveResult, Move}};
+use crate::{components::{card_index::CardIndex, coordinates::Coordinates}, resources::{game_state::PlayerColor, tile_map::{MoveResult, Move}}};
 use bevy::prelude::*;
 
 // TODO: describe each event action
@@ -53,11 +53,59 @@ pub struct CardSwapEvent(pub Entity);
 #[derive(Debug, Clone, Copy)]
 pub struct MirrorCardEvent(pub Entity);
 
+#[derive(Debug, Clone)]
+pub enum ProcessWinConditionEvent {
+    /// Result of the move which has just been made
+    Move(MoveResult),
+    /// The player has run out of time
+    FlagFall(PlayerColor),
+    /// The player has resigned
+    Resign(PlayerColor),
+    /// The player can not continue the game and loses it
+    Forfeit { color: PlayerColor, reason: String },
+    /// Both players have agreed to a draw
+    DrawAgreed,
+}
+
+/// Draw offers and answers of the players
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawOfferEvent {
+    Offer(PlayerColor),
+    Accept(PlayerColor),
+    Decline(PlayerColor),
+}
+
+/// Saves the unfinished game to continue it from the main menu
 #[derive(Debug, Clone, Copy)]
-pub struct ProcessWinConditionEvent(pub MoveResult);
+pub struct SaveGameEvent;
+
+/// Takes back the moves made since the previous turn of the human
+#[derive(Debug, Clone, Copy)]
+pub struct UndoMoveEvent;
+
+/// Shows the move the bot would make for the human
+#[derive(Debug, Clone, Copy)]
+pub struct HintEvent;
+
+/// Turns the board upside down
+#[derive(Debug, Clone, Copy)]
+pub struct FlipBoardEvent;
+
+/// Sent after `BoardAssets` are skinned with another theme
+#[derive(Debug, Clone, Copy)]
+pub struct ThemeChangedEvent;
 
 #[derive(Debug, Clone, Copy)]
 pub struct BotMakeMoveEvent {
     pub mov: Move,
     pub card_used: Entity
-}
\ No newline at end of file
+}
+
+/// Sent after any player has made a move
+#[derive(Debug, Clone, Copy)]
+pub struct MoveMadeEvent {
+    pub mov: Move,
+    pub card_used: Entity,
+    pub color: PlayerColor,
+}
+
@@ -2,17 +2,23 @@ pub mod ai;
 pub mod bounds;
 pub mod button_plugin;
 pub mod components;
+pub mod draft_plugin;
 pub mod events;
 pub mod menu_plugin;
+pub mod network_plugin;
 pub mod resources;
+pub mod rules;
+pub mod stats_plugin;
+pub mod storage;
 pub mod systems;
+pub mod theme_plugin;
 
 // Overall structure is inherited from this tutorial: https://dev.to/qongzi/bevy-minesweeper-introduction-4l7f
 
 use bevy::ecs::schedule::StateData;
 use bevy::ecs::system::EntityCommands;
+use bevy::input::InputSystem;
 use bevy::log;
-use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
 use bevy::utils::{AHashExt, HashMap};
 use components::background::Background;
@@ -26,25 +32,43 @@ use resources::board_options::BoardOptions;
 use resources::card::Card;
 use resources::deck_options::DeckOptions;
 use resources::depth::Depth;
+use resources::draw_rules::DrawRules;
+use resources::engine_options::EngineOptions;
+use resources::time_control::TimeControl;
+use resources::clocks::Clocks;
+use resources::draw_offer::DrawOffer;
+use resources::game_result::GameResult;
 use resources::game_state::{GameState, PlayerColor};
+use resources::handicap::Handicap;
+use resources::pending_turn::PendingTurn;
+use resources::dragged_piece::{DraggedPiece, DropChoice};
+use resources::undo_history::UndoHistory;
+use resources::last_move::LastMove;
+use resources::layout::Layout;
 use resources::physical_deck::PhysicalDeck;
+use resources::pointer::Pointer;
+use resources::saved_game::{SavedGame, SavedGameInUse};
 use resources::selected::SelectedPlayers;
 use resources::tile::TempleTile;
 use resources::tile_map::{BLUE_TEMPLE, RED_TEMPLE};
+use rules::rule_set::RuleSetType;
 
 use crate::ai::agent::Agent;
 use crate::ai::alpha_beta::AlphaBetaAgent;
+use crate::ai::external::ExternalAgent;
 use crate::ai::human::Human;
 use crate::ai::random_agent::RandomAgent;
+use crate::ai::remote::RemoteAgent;
 use crate::bounds::Bounds2;
 use crate::components::card_board::{CardBoard, CardOwner};
 use crate::components::card_index::CardIndex;
-use crate::components::texts::{EvaluationText, GuideText, TurnText};
+use crate::components::texts::{ClockText, EvaluationText, GuideText, RankLabel, TurnText};
 use crate::events::{
     BotMakeMoveEvent, CardSwapEvent, ChangeGuideTextEvent, ColorSelectedCardEvent,
-    ColorSelectedPieceEvent, GenerateAllowedMovesEvent, GenerateBotMoveEvent, MirrorCardEvent,
-    MovePieceEvent, NextTurnEvent, NoCardSelectedEvent, PieceSelectEvent, ProcessWinConditionEvent,
-    ResetAllowedMovesEvent, ResetSelectedCardColorEvent, ResetSelectedPieceColorEvent,
+    ColorSelectedPieceEvent, DrawOfferEvent, GenerateAllowedMovesEvent, GenerateBotMoveEvent,
+    MirrorCardEvent, MoveMadeEvent, MovePieceEvent, NextTurnEvent, NoCardSelectedEvent, PieceSelectEvent,
+    ProcessWinConditionEvent, ResetAllowedMovesEvent, ResetSelectedCardColorEvent,
+    ResetSelectedPieceColorEvent, SaveGameEvent, UndoMoveEvent, HintEvent, FlipBoardEvent,
 };
 #[cfg(feature = "debug")]
 use crate::menu_plugin::ListElement;
@@ -71,11 +95,17 @@ impl<T> BoardPlugin<T> {
         mut commands: Commands,
         board_options: Option<Res<BoardOptions>>,
         deck_options: Option<Res<DeckOptions>>,
-        window: Res<WindowDescriptor>,
+        windows: Res<Windows>,
         selected_players: Res<SelectedPlayers>,
         board_assets: Res<BoardAssets>,
         physical_deck: Res<PhysicalDeck>,
         depth: Res<Depth>,
+        handicap: Res<Handicap>,
+        draw_rules: Res<DrawRules>,
+        rule_set_type: Res<RuleSetType>,
+        engine_options: Res<EngineOptions>,
+        time_control: Res<TimeControl>,
+        saved_game: Option<Res<SavedGame>>,
     ) {
         let options = match board_options {
             Some(opt) => opt.clone(),
@@ -87,14 +117,21 @@ impl<T> BoardPlugin<T> {
             None => Default::default(),
         };
 
-        let tile_map = TileMap::new();
+        // a continued game is restored from the save, the menu has already set the rest of the options
+        let tile_map = match saved_game {
+            Some(ref saved_game) => saved_game.tile_map,
+            None => TileMap::with_handicap(&handicap),
+        };
         #[cfg(feature = "debug")]
         log::info!("{}", tile_map.console_output());
 
+        let window = windows.get_primary().unwrap();
+        let window_size = Vec2::new(window.width(), window.height());
+
         let tile_size = match options.tile_size {
             TileSize::Fixed(size) => size,
             TileSize::Adaptive { min, max } => {
-                Self::adaptive_tile_size(&window, (min, max), (tile_map.width(), tile_map.height()))
+                Self::adaptive_tile_size(window_size, (min, max), (tile_map.width(), tile_map.height()))
             }
         };
 
@@ -104,10 +141,35 @@ impl<T> BoardPlugin<T> {
         );
         log::info!("board size: {}", board_size);
 
+        // the cards are placed around the neutral card
+        let card_tile_size = match deck_options.tile_size {
+            TileSize::Fixed(size) => size,
+            TileSize::Adaptive { min, max } => {
+                Self::adaptive_tile_size(window_size, (min, max), (tile_map.width(), tile_map.height()))
+            }
+        };
+        let card_size = Vec2::new(
+            tile_map.width() as f32 * card_tile_size,
+            tile_map.height() as f32 * card_tile_size,
+        );
+        let offset = card_size / 4.;
+        let mut deck_pos = Vec2::new(-card_size.x / 2. - offset.x, card_size.y + offset.y);
+
+        let layout = Layout::new(window_size, board_size, deck_pos.abs() * 2. + card_size);
+        log::info!("layout: {:?}", layout);
+
+        // the side of the only human is at the bottom, Red is there otherwise
+        let is_flipped = selected_players.blue_player == PlayerType::Human
+            && selected_players.red_player != PlayerType::Human;
+
         let board_entity = commands
             .spawn()
             .insert(Name::new("GameBoard"))
-            .insert(Transform::from_translation(options.position))
+            .insert(Transform {
+                translation: layout.board_position.extend(0.),
+                scale: Vec3::new(layout.scale, layout.scale, 1.),
+                ..Default::default()
+            })
             .insert(GlobalTransform::default())
             .with_children(|parent| {
                 parent
@@ -128,40 +190,34 @@ impl<T> BoardPlugin<T> {
                     tile_size,
                     options.tile_padding,
                     &board_assets,
+                    options.show_coordinates,
+                    is_flipped,
                 );
             })
             .id();
 
         commands.insert_resource(Board {
             bounds: Bounds2 {
-                position: options.position.xy(),
-                size: board_size,
+                position: layout.board_position,
+                size: board_size * layout.scale,
             },
             tile_size,
+            scale: layout.scale,
             padding: options.tile_padding,
+            move_duration: options.move_duration,
             tile_map,
             entity: board_entity,
+            is_flipped,
         });
 
         // Spawn boards with cards movements
-        let tile_size = match deck_options.tile_size {
-            TileSize::Fixed(size) => size,
-            TileSize::Adaptive { min, max } => {
-                Self::adaptive_tile_size(&window, (min, max), (tile_map.width(), tile_map.height()))
-            }
-        };
-
-        log::info!("deck card tile size: {}", tile_size);
+        log::info!("deck card tile size: {}", card_tile_size);
+        log::info!("one board size from the deck: {}", card_size);
 
-        let board_size = Vec2::new(
-            tile_map.width() as f32 * tile_size,
-            tile_map.height() as f32 * tile_size,
-        );
-
-        log::info!("one board size from the deck: {}", board_size);
-
-        let offset = board_size / 4.;
-        let deck_pos = Vec2::new(-board_size.x / 2. - offset.x, board_size.y + offset.y);
+        // the cards of Blue are next to its pieces
+        if is_flipped {
+            deck_pos.y = -deck_pos.y;
+        }
 
         let positions = [
             Vec2::new(deck_pos.x, deck_pos.y),
@@ -181,32 +237,42 @@ impl<T> BoardPlugin<T> {
 
         let mut cards = physical_deck.cards.clone();
 
-        cards[0].is_mirrored = true;
-        cards[1].is_mirrored = true;
+        match saved_game {
+            Some(ref saved_game) => {
+                for (card, is_mirrored) in cards.iter_mut().zip(saved_game.mirrored) {
+                    card.is_mirrored = is_mirrored;
+                }
+            }
+            None => {
+                cards[0].is_mirrored = true;
+                cards[1].is_mirrored = true;
+            }
+        }
 
         let mut deck_container = HashMap::with_capacity(5);
         let mut card_entities = Vec::with_capacity(5);
 
         for i in 0..5 {
-            let position = deck_options.position.xy() + positions[i];
+            let position = layout.deck_position + positions[i] * layout.scale;
             let card_board_entity = commands
                 .spawn()
                 .insert(Name::new(format!("Card {}", cards[i].name)))
                 .insert(CardIndex(i as u8))
-                .insert(Transform::from_translation(Vec3::new(
-                    position.x,
-                    position.y,
-                    deck_options.position.z,
-                )))
+                .insert(Transform {
+                    translation: position.extend(0.),
+                    scale: Vec3::new(layout.scale, layout.scale, 1.),
+                    ..Default::default()
+                })
                 .insert(GlobalTransform::default())
                 .with_children(|parent| {
                     Self::spawn_deck_card_board(
                         parent,
-                        board_size,
+                        card_size,
                         &cards[i],
                         &board_assets,
                         deck_options.tile_padding,
-                        tile_size,
+                        card_tile_size,
+                        is_flipped,
                     );
                 })
                 .id();
@@ -226,8 +292,8 @@ impl<T> BoardPlugin<T> {
                 CardBoard {
                     card: cards[i].clone(),
                     bounds: Bounds2 {
-                        size: board_size,
-                        position: positions[i] + deck_options.position.xy() - board_size / 2.,
+                        size: card_size * layout.scale,
+                        position: position - card_size * layout.scale / 2.,
                     },
                 },
             );
@@ -235,29 +301,79 @@ impl<T> BoardPlugin<T> {
             card_entities.push(card_board_entity);
         }
 
-        commands.insert_resource(Deck {
+        let deck = Deck {
             cardboards: deck_container,
             cards: card_entities,
-        });
+        };
+
+        let red_agent: Box<dyn Agent> = match selected_players.red_player {
+            PlayerType::Human => Box::new(Human),
+            PlayerType::Random => Box::new(RandomAgent),
+            PlayerType::AlphaBeta => Box::new(AlphaBetaAgent::new(depth.0)),
+            PlayerType::Remote => Box::new(RemoteAgent),
+            PlayerType::External => Box::new(ExternalAgent::new(
+                engine_options.selected_engine().cloned(),
+                *rule_set_type,
+                engine_options.move_time,
+            )),
+        };
+
+        let blue_agent: Box<dyn Agent> = match selected_players.blue_player {
+            PlayerType::Human => Box::new(Human),
+            PlayerType::Random => Box::new(RandomAgent),
+            PlayerType::AlphaBeta => Box::new(AlphaBetaAgent::new(depth.0)),
+            PlayerType::Remote => Box::new(RemoteAgent),
+            PlayerType::External => Box::new(ExternalAgent::new(
+                engine_options.selected_engine().cloned(),
+                *rule_set_type,
+                engine_options.move_time,
+            )),
+        };
+
+        let red_player = Player {
+            agent: red_agent,
+            player_type: selected_players.red_player,
+        };
+
+        let blue_player = Player {
+            agent: blue_agent,
+            player_type: selected_players.blue_player,
+        };
+
+        let mut game_state = GameState::new(
+            red_player,
+            blue_player,
+            *handicap,
+            rule_set_type.build(*draw_rules),
+        );
+        game_state.set_first_player(&deck);
+        if let Some(ref saved_game) = saved_game {
+            log::info!("Continuing the saved game from turn {}", saved_game.turn);
+            game_state.restore_turn(saved_game.turn, saved_game.curr_color, saved_game.first_color);
+            commands.remove_resource::<SavedGame>();
+            commands.insert_resource(SavedGameInUse);
+        }
+        game_state.push_position(&tile_map, &deck);
 
         commands.insert_resource(SelectedCard::default());
         commands.insert_resource(SelectedPiece::default());
+        commands.insert_resource(UndoHistory::default());
 
         // Spawn a guide text
         let guide_text = commands
             .spawn()
             .insert(Name::new("Guide text"))
             .insert(GuideText)
-            .insert(Transform::from_translation(Vec3::new(
-                0.0,
-                window.height / 2.4,
-                1.,
-            )))
+            .insert(Transform {
+                translation: layout.guide_text_position.extend(1.),
+                scale: Vec3::new(layout.scale, layout.scale, 1.),
+                ..Default::default()
+            })
             .insert(GlobalTransform::default())
             .with_children(|parent| {
                 Self::spawn_text(
                     parent,
-                    "Red to move. Select a card".to_owned(),
+                    format!("{} to move. Select a card", game_state.curr_color.name()),
                     &board_assets,
                     board_assets.guide_text_size,
                     Vec2::new(0., 0.),
@@ -271,20 +387,23 @@ impl<T> BoardPlugin<T> {
             .spawn()
             .insert(Name::new("Turn text"))
             .insert(TurnText)
-            .insert(Transform::from_translation(Vec3::new(
-                -window.width / 2. + window.width / 8.,
-                window.height / 2.4,
-                1.,
-            )))
+            .insert(Transform {
+                translation: layout.turn_text_position.extend(1.),
+                scale: Vec3::new(layout.scale, layout.scale, 1.),
+                ..Default::default()
+            })
             .insert(GlobalTransform::default())
             .with_children(|parent| {
                 Self::spawn_text(
                     parent,
-                    "Red turn: 0".to_owned(),
+                    format!("{} turn: {}", game_state.curr_color.name(), game_state.turn),
                     &board_assets,
                     board_assets.turn_text_size,
                     Vec2::new(0., 0.),
-                    Color::RED,
+                    match game_state.curr_color {
+                        Red => Color::RED,
+                        Blue => Color::BLUE,
+                    },
                 );
             })
             .id();
@@ -296,11 +415,13 @@ impl<T> BoardPlugin<T> {
             .spawn()
             .insert(Name::new("Evaluation text"))
             .insert(EvaluationText)
-            .insert(Transform::from_translation(Vec3::new(
-                -window.width / 2. + window.width / 8. + 10.,
-                window.height / 2.4 - board_assets.turn_text_size,
-                1.,
-            )))
+            .insert(Transform {
+                translation: layout
+                    .evaluation_text_position(board_assets.turn_text_size)
+                    .extend(1.),
+                scale: Vec3::new(layout.scale, layout.scale, 1.),
+                ..Default::default()
+            })
             .insert(GlobalTransform::default())
             .with_children(|parent| {
                 Self::spawn_text(
@@ -316,44 +437,59 @@ impl<T> BoardPlugin<T> {
 
         commands.insert_resource(evaluation_result);
 
+        // clocks are not synchronized over the network, so the network games are untimed
+        let clocks = match selected_players.remote_color() {
+            Some(_) => Clocks::default(),
+            None => Clocks::new(*time_control),
+        };
+
+        // create clock text, it is filled by the system when the game is timed
+        let clock_text = commands
+            .spawn()
+            .insert(Name::new("Clock text"))
+            .insert(ClockText)
+            .insert(Transform {
+                translation: layout
+                    .clock_text_position(board_assets.turn_text_size)
+                    .extend(1.),
+                scale: Vec3::new(layout.scale, layout.scale, 1.),
+                ..Default::default()
+            })
+            .insert(GlobalTransform::default())
+            .with_children(|parent| {
+                Self::spawn_text(
+                    parent,
+                    String::new(),
+                    &board_assets,
+                    board_assets.turn_text_size,
+                    Vec2::new(0., 0.),
+                    Color::WHITE,
+                );
+            })
+            .id();
+
+        commands.insert_resource(clocks);
+        commands.insert_resource(DrawOffer::default());
+
         commands.insert_resource(TextHandler {
             turn_text,
             guide_text,
             evaluation_text,
+            clock_text,
         });
 
-        let red_agent: Box<dyn Agent> = match selected_players.red_player {
-            PlayerType::Human => Box::new(Human),
-            PlayerType::Random => Box::new(RandomAgent),
-            PlayerType::AlphaBeta => Box::new(AlphaBetaAgent { max_depth: depth.0 }),
-        };
-
-        let blue_agent: Box<dyn Agent> = match selected_players.blue_player {
-            PlayerType::Human => Box::new(Human),
-            PlayerType::Random => Box::new(RandomAgent),
-            PlayerType::AlphaBeta => Box::new(AlphaBetaAgent { max_depth: depth.0 }),
-        };
-
-        let red_player = Player {
-            agent: red_agent,
-            player_type: selected_players.red_player,
-        };
-
-        let blue_player = Player {
-            agent: blue_agent,
-            player_type: selected_players.blue_player,
-        };
-
-        commands.insert_resource(GameState::new(red_player, blue_player));
+        commands.insert_resource(deck);
+        commands.insert_resource(game_state);
+        commands.insert_resource(layout);
     }
 
     pub fn adaptive_tile_size(
-        window: &WindowDescriptor,
+        window_size: Vec2,
         (min, max): (f32, f32),
         (width, height): (u8, u8),
     ) -> f32 {
-        let max_width = window.width / width as f32;
-        let max_height = window.height / height as f32;
+        let max_width = window_size.x / width as f32;
+        let max_height = window_size.y / height as f32;
         max_width.min(max_height).clamp(min, max)
     }
 
@@ -363,9 +499,17 @@ impl<T> BoardPlugin<T> {
         tile_size: f32,
         padding: f32,
         board_assets: &BoardAssets,
+        show_coordinates: bool,
+        is_flipped: bool,
     ) {
         // reversing here, because bevy starts (0, 0) from the left bottom corner
         for (y, line) in board.iter().enumerate() {
+            // a flipped board shows the rows from the top
+            let row = match is_flipped {
+                true => board.height() as usize - 1 - y,
+                false => y,
+            };
+
             for (x, tile) in line.iter().enumerate() {
                 let coordinates = Coordinates {
                     x: x as u8,
@@ -373,7 +517,7 @@ impl<T> BoardPlugin<T> {
                 };
 
                 // print numbers to the left of the board
-                if coordinates.x == 0 {
+                if show_coordinates && coordinates.x == 0 {
                     Self::spawn_text(
                         parent,
                         (y + 1).to_string(),
@@ -381,14 +525,15 @@ impl<T> BoardPlugin<T> {
                         tile_size,
                         Vec2::new(
                             (x as f32 * tile_size) - (tile_size / 4.),
-                            (y as f32 * tile_size) + (tile_size / 8.),
+                            (row as f32 * tile_size) + (tile_size / 8.),
                         ),
                         Color::WHITE,
-                    );
+                    )
+                    .insert(RankLabel(coordinates.y));
                 }
 
                 // print letters below the board
-                if coordinates.y == 0 {
+                if show_coordinates && coordinates.y == 0 {
                     Self::spawn_text(
                         parent,
                         String::from((97 + x) as u8 as char),
@@ -402,9 +547,9 @@ impl<T> BoardPlugin<T> {
                     );
                 }
 
-                let tile_color = match coordinates {
-                    RED_TEMPLE | BLUE_TEMPLE => board_assets.temple_tile_material.color,
-                    _ => board_assets.tile_material.color,
+                let tile_material = match coordinates {
+                    RED_TEMPLE | BLUE_TEMPLE => &board_assets.temple_tile_material,
+                    _ => &board_assets.tile_material,
                 };
 
                 // Creating a tile on the board
@@ -412,13 +557,14 @@ impl<T> BoardPlugin<T> {
                 let tile_builder = cmd
                     .insert_bundle(SpriteBundle {
                         sprite: Sprite {
-                            color: tile_color,
+                            color: tile_material.color,
                             custom_size: Some(Vec2::splat(tile_size - padding)),
                             ..Default::default()
                         },
+                        texture: tile_material.texture.clone(),
                         transform: Transform::from_xyz(
                             (x as f32 * tile_size) + (tile_size / 2.),
-                            (y as f32 * tile_size) + (tile_size / 2.),
+                            (row as f32 * tile_size) + (tile_size / 2.),
                             1.,
                         ),
                         ..Default::default()
@@ -511,14 +657,14 @@ impl<T> BoardPlugin<T> {
         }; // if let ends
     }
 
-    fn spawn_text(
-        parent: &mut ChildBuilder,
+    fn spawn_text<'w, 's, 'a>(
+        parent: &'a mut ChildBuilder<'w, 's, '_>,
         text: String,
         board_assets: &BoardAssets,
         tile_size: f32,
         position: Vec2,
         color: Color,
-    ) {
+    ) -> EntityCommands<'w, 's, 'a> {
         parent.spawn_bundle(Text2dBundle {
             text: Text {
                 sections: vec![TextSection {
@@ -536,7 +682,7 @@ impl<T> BoardPlugin<T> {
             },
             transform: Transform::from_xyz(position.x, position.y, 1.),
             ..Default::default()
-        });
+        })
     }
 
     fn spawn_deck_card_board(
@@ -546,6 +692,7 @@ impl<T> BoardPlugin<T> {
         board_assets: &BoardAssets,
         padding: f32,
         tile_size: f32,
+        is_flipped: bool,
     ) {
         // spawn background for the card
         parent
@@ -583,13 +730,13 @@ impl<T> BoardPlugin<T> {
             })
             .insert(Name::new(format!("Card title: {}", card.name)));
 
-        // Calculate the coordinates for the possible moves
+        // Calculate the coordinates for the possible moves, they are shown from the bottom side
         let center = Coordinates { x: 2, y: 2 };
         let move_tiles = card
             .directions
             .iter()
             .map(|tuple| {
-                if card.is_mirrored {
+                if card.is_mirrored != is_flipped {
                     center + (tuple.0, -tuple.1)
                 } else {
                     center + *tuple
@@ -662,6 +809,7 @@ impl<T> BoardPlugin<T> {
         commands
             .entity(text_handler.evaluation_text)
             .despawn_recursive();
+        commands.entity(text_handler.clock_text).despawn_recursive();
         commands.remove_resource::<TextHandler>();
 
         commands.remove_resource::<SelectedPiece>();
@@ -669,6 +817,16 @@ impl<T> BoardPlugin<T> {
 
         commands.remove_resource::<GameState>();
         commands.remove_resource::<EvaluationResult>();
+        commands.remove_resource::<Clocks>();
+        commands.remove_resource::<DrawOffer>();
+        commands.remove_resource::<GameResult>();
+        commands.remove_resource::<SavedGameInUse>();
+        commands.remove_resource::<PendingTurn>();
+        commands.remove_resource::<DraggedPiece>();
+        commands.remove_resource::<DropChoice>();
+        commands.remove_resource::<UndoHistory>();
+        commands.remove_resource::<LastMove>();
+        commands.remove_resource::<Layout>();
     }
 }
 
@@ -681,20 +839,33 @@ impl<T: StateData> Plugin for BoardPlugin<T> {
         );
         app.add_system_set(
             SystemSet::on_update(self.running_state.clone())
+                .with_system(
+                    systems::clocks::update_clocks
+                        .label("update_clocks")
+                        .before("process_win_condition"),
+                )
                 .with_system(
                     systems::game_state_process::process_win_condition
-                        .label("process_win_condition"),
+                        .label("process_win_condition")
+                        .after("card_swap"),
+                )
+                .with_system(
+                    systems::game_state_process::process_draw_offer
+                        .label("process_draw_offer")
+                        .after("game_buttons")
+                        .after("card_swap")
+                        .before("process_win_condition"),
                 )
                 .with_system(
                     systems::text_change::process_guide_text
                         .label("process_guide_text")
                         .after("process_win_condition"),
                 )
+                // the turn is switched when the piece arrives, frames after the move is judged
                 .with_system(
                     systems::game_state_process::next_turn_event
                         .label("next_turn_event")
-                        .after("process_win_condition")
-                        .after("process_guide_text"),
+                        .before("process_win_condition"),
                 )
                 .with_system(
                     systems::game_state_process::turn_process
@@ -707,21 +878,100 @@ impl<T: StateData> Plugin for BoardPlugin<T> {
                         .after("next_turn_event"),
                 )
                 .with_system(
-                    systems::ai_input::bot_make_move::<T>
-                        .label("bot_make_move")
+                    systems::ai_input::poll_bot_move
+                        .label("bot_poll_move")
                         .after("bot_generate_move"),
                 )
+                .with_system(
+                    systems::ai_input::bot_make_move
+                        .label("bot_make_move")
+                        .after("bot_generate_move")
+                        .after("bot_poll_move"),
+                )
                 .with_system(systems::text_change::change_evaluation_text)
                 .with_system(
                     systems::board_input::input_handling
                         .label("input_handling")
                         .after("next_turn_event"),
                 )
-                .with_system(systems::card_input::card_selection_handling.after("input_handling"))
+                .with_system(
+                    systems::card_input::card_selection_handling
+                        .label("card_selection_handling")
+                        .after("input_handling"),
+                )
+                .with_system(
+                    systems::piece_drag::start_piece_drag
+                        .label("start_piece_drag")
+                        .after("next_turn_event"),
+                )
+                .with_system(
+                    systems::piece_drag::drag_piece
+                        .label("drag_piece")
+                        .after("start_piece_drag")
+                        .before("reset_selected_card_color")
+                        .before("reset_selected_piece_color")
+                        .before("reset_allowed_moves"),
+                )
+                .with_system(
+                    systems::piece_drag::drop_piece
+                        .label("drop_piece")
+                        .after("drag_piece")
+                        .before("reset_selected_card_color")
+                        .before("reset_allowed_moves")
+                        .before("move_piece"),
+                )
+                .with_system(
+                    systems::piece_drag::choose_drop_card
+                        .after("card_selection_handling")
+                        .after("keyboard_input")
+                        .before("move_piece"),
+                )
+                .with_system(
+                    systems::keyboard_input::keyboard_input
+                        .label("keyboard_input")
+                        .after("next_turn_event")
+                        .before("color_selected_tile"),
+                )
+                .with_system(
+                    systems::hint::show_hint
+                        .after("game_buttons")
+                        .after("next_turn_event")
+                        .before("color_selected_tile"),
+                )
+                .with_system(
+                    systems::undo::record_turn
+                        .label("record_turn")
+                        .after("next_turn_event"),
+                )
+                .with_system(
+                    systems::undo::take_back_move::<T>
+                        .after("game_buttons")
+                        .after("record_turn")
+                        .before("move_piece")
+                        .before("bot_make_move"),
+                )
+                .with_system(systems::last_move::record_last_move.label("record_last_move"))
+                .with_system(systems::last_move::show_last_move.after("record_last_move"))
+                .with_system(systems::layout::update_layout.before("flip_board"))
+                .with_system(systems::move_preview::preview_card_moves)
+                .with_system(
+                    systems::board_flip::flip_board
+                        .label("flip_board")
+                        .after("game_buttons")
+                        .after("drop_piece")
+                        .before("mirror_card"),
+                )
+                .with_system(
+                    systems::piece_drag::color_dragged_piece_moves
+                        .after("drag_piece")
+                        .after("reset_selected_card_color")
+                        .after("reset_allowed_moves"),
+                )
                 .with_system(
                     systems::board_input::process_selected_tile.label("color_selected_tile"),
                 )
                 .with_system(systems::text_change::change_turn_text)
+                .with_system(systems::text_change::change_clock_text.after("update_clocks"))
                 .with_system(systems::card_input::color_selected_card.label("color_selected_card"))
                 .with_system(
                     systems::card_input::reset_selected_card_color
@@ -731,20 +981,75 @@ impl<T: StateData> Plugin for BoardPlugin<T> {
                 )
                 .with_system(systems::text_change::process_guide_text_change_timer)
                 .with_system(systems::board_input::color_selected_piece) // .with_system(systems::card_input::blink_non_selected_card),
-                .with_system(systems::board_input::reset_selected_piece_color)
                 .with_system(
-                    systems::board_input::reset_allowed_moves.before("generate_allowed_moves"),
+                    systems::board_input::reset_selected_piece_color
+                        .label("reset_selected_piece_color")
+                        .after("color_selected_tile"),
+                )
+                .with_system(
+                    systems::board_input::reset_allowed_moves
+                        .label("reset_allowed_moves")
+                        .before("generate_allowed_moves"),
                 )
                 .with_system(
                     systems::board_input::generate_allowed_moves.label("generate_allowed_moves"),
                 )
-                .with_system(systems::board_input::move_piece::<T>.after("next_turn_event"))
-                .with_system(systems::card_input::card_swap)
-                .with_system(systems::card_input::mirror_card),
+                .with_system(
+                    systems::board_input::move_piece
+                        .label("move_piece")
+                        .after("next_turn_event")
+                        .after("reset_selected_piece_color"),
+                )
+                // the position after the move is recorded with the swapped cards
+                .with_system(
+                    systems::card_input::card_swap
+                        .label("card_swap")
+                        .after("move_piece")
+                        .after("bot_make_move"),
+                )
+                .with_system(systems::card_input::mirror_card.label("mirror_card"))
+                .with_system(
+                    systems::card_input::animate_cards
+                        .label("animate_cards")
+                        .before("mirror_card"),
+                )
+                .with_system(systems::piece_animation::animate_pieces.label("animate_pieces"))
+                .with_system(
+                    systems::piece_animation::finish_pending_turn
+                        .after("animate_pieces")
+                        .after("animate_cards")
+                        .before("next_turn_event"),
+                )
+                .with_system(
+                    systems::saved_game::save_game
+                        .after("game_buttons")
+                        .after("next_turn_event")
+                        .before("move_piece")
+                        .before("bot_make_move"),
+                ),
+        );
+        app.add_system_set(
+            SystemSet::on_update(self.cleanup_state.clone())
+                .with_system(systems::saved_game::forget_finished_save)
+                // the last move is still shown when the game is over
+                .with_system(systems::piece_animation::animate_pieces)
+                .with_system(systems::last_move::record_last_move.label("record_last_move"))
+                .with_system(systems::last_move::show_last_move.after("record_last_move"))
+                .with_system(systems::layout::update_layout)
+                .with_system(systems::card_input::animate_cards.before("mirror_card"))
+                .with_system(systems::card_input::mirror_card.label("mirror_card")),
         );
         app.add_system_set(
             SystemSet::on_exit(self.cleanup_state.clone()).with_system(Self::cleanup_game),
         );
+        // the mouse and the touches are read by every state, the menus are tapped as well
+        app.init_resource::<Pointer>().add_system_to_stage(
+            CoreStage::PreUpdate,
+            systems::pointer::update_pointer.after(InputSystem),
+        );
+        // the canvas follows the browser window, so the layout works for portrait screens
+        #[cfg(target_arch = "wasm32")]
+        app.add_system(systems::layout::fit_browser_window);
         app.add_event::<PieceSelectEvent>();
         app.add_event::<ColorSelectedCardEvent>();
         app.add_event::<ResetSelectedCardColorEvent>();
@@ -759,9 +1064,15 @@ impl<T: StateData> Plugin for BoardPlugin<T> {
         app.add_event::<GenerateBotMoveEvent>();
         app.add_event::<MovePieceEvent>();
         app.add_event::<CardSwapEvent>();
+        app.add_event::<UndoMoveEvent>();
+        app.add_event::<HintEvent>();
+        app.add_event::<FlipBoardEvent>();
         app.add_event::<MirrorCardEvent>();
         app.add_event::<ProcessWinConditionEvent>();
         app.add_event::<BotMakeMoveEvent>();
+        app.add_event::<MoveMadeEvent>();
+        app.add_event::<DrawOfferEvent>();
+        app.add_event::<SaveGameEvent>();
 
         log::info!("Loaded Board Plugin");
 
@@ -0,0 +1,289 @@
+use bevy::{
+    log,
+    prelude::*,
+    window::{WindowId, WindowResized},
+};
+
+use crate::{
+    bounds::Bounds2,
+    components::{background::Background, card_index::CardIndex, coordinates::Coordinates},
+    events::ThemeChangedEvent,
+    resources::{
+        board_assets::BoardAssets, card::CARDS, menu_settings::MenuSettings,
+        physical_deck::PhysicalDeck, pointer::Pointer,
+    },
+};
+
+use super::{MainMenuData, SelectedCards};
+
+pub(super) struct CardColors(pub Vec<Color>);
+
+impl CardColors {
+    /// Colors in the order of selection: two red cards, the neutral card and two blue cards
+    pub(super) fn new(board_assets: &BoardAssets) -> Self {
+        let red = board_assets.selected_red_card_material.color;
+        let blue = board_assets.selected_blue_card_material.color;
+        let neutral = board_assets.selected_neutral_card_material.color;
+        Self(vec![red, red, neutral, blue, blue])
+    }
+}
+
+pub(super) fn color_selected_cards(
+    windows: Res<Windows>,
+    menu_data: Res<MainMenuData>,
+    mut colors: ResMut<CardColors>,
+    mut selected_cards: ResMut<SelectedCards>,
+    pointer: Res<Pointer>,
+    cards_q: Query<(&CardIndex, &Children)>,
+    mut sprites: Query<&mut Sprite, With<Background>>,
+) {
+    if !pointer.just_pressed {
+        return;
+    }
+
+    if colors.0.is_empty() {
+        log::info!("All cards are colored!");
+        return;
+    }
+
+    let window = windows.get_primary().unwrap();
+
+    for (entity, bounds) in menu_data.cards.iter() {
+        if !bounds.in_bounds_pointer(window, &pointer) {
+            continue;
+        }
+
+        if let Ok((card_index, children)) = cards_q.get(*entity) {
+            for child in children.iter() {
+                match sprites.get_mut(*child) {
+                    Ok(mut sprite) => {
+                        let color = colors.0.remove(0);
+                        sprite.color = color;
+                        log::info!(
+                            "Selectd a card with index {} and given the color {:?}",
+                            card_index.0,
+                            color
+                        );
+
+                        selected_cards.0.push((*entity, card_index.0));
+                        // the enlarged areas of the touched cards may overlap
+                        return;
+                    }
+                    Err(_) => log::warn!("Sprite for background was not found"),
+                }
+            }
+        }
+    }
+}
+
+pub(super) struct ResetSelectedCardsEvent;
+
+pub(super) fn reset_selected_cards(
+    board_assets: Res<BoardAssets>,
+    mut colors: ResMut<CardColors>,
+    mut selected_cards: ResMut<SelectedCards>,
+    cards_q: Query<&Children, With<CardIndex>>,
+    mut sprites: Query<&mut Sprite, With<Background>>,
+    mut reset_selected_cards_rdr: EventReader<ResetSelectedCardsEvent>,
+) {
+    for _ in reset_selected_cards_rdr.iter() {
+        *colors = CardColors::new(&board_assets);
+        for (entity, _) in selected_cards.0.iter() {
+            if let Ok(children) = cards_q.get(*entity) {
+                'child: for child in children.iter() {
+                    match sprites.get_mut(*child) {
+                        Ok(mut sprite) => {
+                            sprite.color = Color::WHITE;
+                            break 'child;
+                        }
+                        Err(_) => log::warn!("Error while resetting card background!"),
+                    }
+                }
+            }
+        }
+        selected_cards.0.clear();
+        log::info!("Card colors after reset: {:?}", colors.0);
+        log::info!("Card colors after reset: {:?}", selected_cards);
+    }
+}
+
+/// Colors the cards selected in the last session once the menu is spawned
+pub(super) fn restore_selected_cards(
+    menu_data: Res<MainMenuData>,
+    menu_settings: Res<MenuSettings>,
+    board_assets: Res<BoardAssets>,
+    mut colors: ResMut<CardColors>,
+    mut selected_cards: ResMut<SelectedCards>,
+    cards_q: Query<(&CardIndex, &Children)>,
+    mut sprites: Query<&mut Sprite, With<Background>>,
+) {
+    if !menu_data.is_added() {
+        return;
+    }
+
+    *colors = CardColors::new(&board_assets);
+    selected_cards.0.clear();
+    for idx in menu_settings.selected_cards.iter() {
+        for (entity, _) in menu_data.cards.iter() {
+            let (card_index, children) = match cards_q.get(*entity) {
+                Ok((card_index, children)) if card_index.0 == *idx => (card_index, children),
+                _ => continue,
+            };
+
+            let color = colors.0.remove(0);
+            for child in children.iter() {
+                if let Ok(mut sprite) = sprites.get_mut(*child) {
+                    sprite.color = color;
+                    break;
+                }
+            }
+            selected_cards.0.push((*entity, card_index.0));
+        }
+    }
+    log::info!("Restored selected cards: {:?}", selected_cards.0);
+}
+
+/// Colors the grids and the backgrounds of the selected cards with the new theme
+pub(super) fn reskin_menu_cards(
+    board_assets: Res<BoardAssets>,
+    menu_data: Option<Res<MainMenuData>>,
+    selected_cards: Option<Res<SelectedCards>>,
+    colors: Option<ResMut<CardColors>>,
+    cards_q: Query<(&CardIndex, &Children)>,
+    mut tiles_q: Query<(&Coordinates, &mut Sprite), Without<Background>>,
+    mut backgrounds_q: Query<&mut Sprite, With<Background>>,
+    mut theme_changed_rdr: EventReader<ThemeChangedEvent>,
+) {
+    let menu_data = match menu_data {
+        Some(menu_data) => menu_data,
+        None => return,
+    };
+    if theme_changed_rdr.iter().last().is_none() {
+        return;
+    }
+
+    let center = Coordinates { x: 2, y: 2 };
+    for (entity, _) in menu_data.cards.iter() {
+        let (card_index, children) = match cards_q.get(*entity) {
+            Ok(card) => card,
+            Err(_) => continue,
+        };
+        let card = &CARDS[card_index.0 as usize];
+        let move_tiles = card
+            .directions
+            .iter()
+            .map(|tuple| center + *tuple)
+            .collect::<Vec<_>>();
+
+        for child in children.iter() {
+            if let Ok((coordinates, mut sprite)) = tiles_q.get_mut(*child) {
+                sprite.color = if move_tiles.contains(coordinates) {
+                    board_assets.deck_card_allowed_move_material.color
+                } else if *coordinates == center {
+                    board_assets.deck_card_center_material.color
+                } else {
+                    board_assets.tile_material.color
+                };
+            }
+        }
+    }
+
+    // the selected cards are colored again in the order of the selection
+    let (selected_cards, mut colors) = match (selected_cards, colors) {
+        (Some(selected_cards), Some(colors)) => (selected_cards, colors),
+        _ => return,
+    };
+    let mut new_colors = CardColors::new(&board_assets);
+    for (entity, _) in selected_cards.0.iter() {
+        let color = new_colors.0.remove(0);
+        if let Ok((_, children)) = cards_q.get(*entity) {
+            for child in children.iter() {
+                if let Ok(mut sprite) = backgrounds_q.get_mut(*child) {
+                    sprite.color = color;
+                    break;
+                }
+            }
+        }
+    }
+    *colors = new_colors;
+}
+
+/// Centers of the cards in the menu and the scale of the cards. The cards are put in rows
+/// between the options and the buttons, a portrait window gets fewer cards in a row
+pub(super) fn menu_card_places(window_size: Vec2, card_size: Vec2) -> (f32, Vec<Vec2>) {
+    let cards_in_row = if window_size.x > window_size.y { 7 } else { 4 };
+    let rows = (CARDS.len() + cards_in_row - 1) / cards_in_row;
+    let offset = card_size / 2.;
+    let grid_size = Vec2::new(
+        cards_in_row as f32 * (card_size.x + offset.x) - offset.x,
+        rows as f32 * (card_size.y + offset.y) - offset.y,
+    );
+
+    // 60% at the top are taken by the options and 10% at the bottom by the buttons
+    let area = Vec2::new(window_size.x * 0.95, window_size.y * 0.45);
+    let area_center = Vec2::new(0., -window_size.y * 0.15);
+    let scale = (area / grid_size).min_element().min(1.);
+
+    let top_left = area_center + Vec2::new(-grid_size.x, grid_size.y) * scale / 2.;
+    let positions = (0..CARDS.len())
+        .map(|i| {
+            let (column, row) = ((i % cards_in_row) as f32, (i / cards_in_row) as f32);
+            top_left
+                + Vec2::new(
+                    card_size.x / 2. + column * (card_size.x + offset.x),
+                    -card_size.y / 2. - row * (card_size.y + offset.y),
+                ) * scale
+        })
+        .collect();
+
+    (scale, positions)
+}
+
+/// Places the cards again when the window is resized
+pub(super) fn relayout_menu_cards(
+    menu_data: Option<ResMut<MainMenuData>>,
+    mut transforms_q: Query<&mut Transform, With<CardIndex>>,
+    mut window_resized_rdr: EventReader<WindowResized>,
+) {
+    let mut menu_data = match menu_data {
+        Some(menu_data) => menu_data,
+        None => return,
+    };
+    let event = match window_resized_rdr
+        .iter()
+        .filter(|event| event.id == WindowId::primary())
+        .last()
+    {
+        Some(event) => event,
+        None => return,
+    };
+
+    let card_size = menu_data.card_size;
+    let (scale, positions) = menu_card_places(Vec2::new(event.width, event.height), card_size);
+    for ((entity, bounds), position) in menu_data.cards.iter_mut().zip(positions) {
+        *bounds = Bounds2 {
+            position: position - card_size * scale / 2.,
+            size: card_size * scale,
+        };
+        if let Ok(mut transform) = transforms_q.get_mut(*entity) {
+            transform.translation = position.extend(transform.translation.z);
+            transform.scale = Vec3::new(scale, scale, 1.);
+        }
+    }
+}
+
+/// Fills the deck with the selected cards, the missing cards are random
+pub(super) fn deal_selected_cards(
+    physical_deck: &mut PhysicalDeck,
+    selected_cards: &SelectedCards,
+) {
+    if selected_cards.0.len() == 5 {
+        let res = selected_cards.0.iter().map(|v| v.1).collect::<Vec<_>>();
+        physical_deck.take_cards_from_indices(&res);
+    } else if selected_cards.0.len() == 0 {
+        physical_deck.take_random_cards();
+    } else {
+        let res = selected_cards.0.iter().map(|v| v.1).collect::<Vec<_>>();
+        physical_deck.take_some_random_cards(&res);
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::{log, prelude::*};
+
+use crate::resources::{board_assets::BoardAssets, time_control::TimeControl};
+
+use super::{
+    selector::{label_text, setup_selector},
+    MenuMaterials,
+};
+
+#[derive(Debug, Component)]
+pub(super) enum TimeControlButtonAction {
+    Previous,
+    Next,
+}
+
+#[derive(Default, Component)]
+pub(super) struct TimeControlLabel;
+
+pub(super) struct UpdateTimeControlLabelEvent;
+
+pub(super) fn time_control_button_press_system(
+    buttons: Query<(&Interaction, &TimeControlButtonAction), (Changed<Interaction>, With<Button>)>,
+    mut time_control: ResMut<TimeControl>,
+    mut update_time_control_label_ewr: EventWriter<UpdateTimeControlLabelEvent>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction == Interaction::Clicked {
+            match button {
+                TimeControlButtonAction::Next => time_control.next(),
+                TimeControlButtonAction::Previous => time_control.prev(),
+            };
+            log::info!("Changed time control to {:?}", time_control);
+            update_time_control_label_ewr.send(UpdateTimeControlLabelEvent);
+        }
+    }
+}
+
+pub(super) fn update_time_control_label(
+    time_control: Res<TimeControl>,
+    mut texts: Query<&mut Text, With<TimeControlLabel>>,
+    board_assets: Res<BoardAssets>,
+    mut update_time_control_label_rdr: EventReader<UpdateTimeControlLabelEvent>,
+) {
+    for _ in update_time_control_label_rdr.iter() {
+        let mut text = texts
+            .get_single_mut()
+            .expect("Expected one time control label");
+        *text = label_text(time_control.to_string(), &board_assets);
+    }
+}
+
+pub(super) fn setup_time_control_selector(
+    time_control: &TimeControl,
+    button_materials: &MenuMaterials,
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+) {
+    setup_selector::<TimeControlLabel, _>(
+        parent,
+        button_materials,
+        font,
+        "Clock: ",
+        40.,
+        time_control.to_string(),
+        [
+            TimeControlButtonAction::Previous,
+            TimeControlButtonAction::Next,
+        ],
+    );
+}
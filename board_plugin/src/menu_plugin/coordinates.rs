@@ -0,0 +1,35 @@
+use bevy::{log, prelude::*};
+
+use crate::resources::board_options::BoardOptions;
+
+use super::set_button_text;
+
+#[derive(Component)]
+pub(super) struct CoordinatesButton;
+
+#[inline]
+pub(super) fn coordinates_button_text(board_options: &BoardOptions) -> &'static str {
+    if board_options.show_coordinates {
+        "COORDINATES: ON"
+    } else {
+        "COORDINATES: OFF"
+    }
+}
+
+pub(super) fn coordinates_button_press_system(
+    buttons: Query<(&Interaction, &Children), (Changed<Interaction>, With<CoordinatesButton>)>,
+    mut board_options: ResMut<BoardOptions>,
+    mut texts: Query<&mut Text>,
+) {
+    for (interaction, children) in buttons.iter() {
+        if *interaction == Interaction::Clicked {
+            board_options.show_coordinates = !board_options.show_coordinates;
+            log::info!("Coordinates: {:?}", board_options.show_coordinates);
+            set_button_text(
+                children,
+                &mut texts,
+                coordinates_button_text(&board_options),
+            );
+        }
+    }
+}
@@ -0,0 +1,219 @@
+#[cfg(feature = "debug")]
+use bevy::log;
+use bevy::prelude::*;
+
+use crate::resources::{board_assets::BoardAssets, depth::Depth};
+
+use super::{MenuMaterials, SimpleButton};
+
+pub(super) struct UpdateDepthCounterEvent;
+
+pub(super) fn depth_button_press_system(
+    buttons: Query<(&Interaction, &DepthButtonAction), (Changed<Interaction>, With<Button>)>,
+    mut depth: ResMut<Depth>,
+    mut update_depth_counter_ewr: EventWriter<UpdateDepthCounterEvent>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction == Interaction::Clicked {
+            match button {
+                DepthButtonAction::Increase => {
+                    #[cfg(feature = "debug")]
+                    log::info!("Added 1 to the depth {:?}", depth);
+                    depth.add();
+                }
+                DepthButtonAction::Decrease => {
+                    #[cfg(feature = "debug")]
+                    log::info!("Subtracted 1 from the depth {:?}", depth);
+                    depth.sub();
+                }
+            };
+            update_depth_counter_ewr.send(UpdateDepthCounterEvent);
+        }
+    }
+}
+
+pub(super) fn update_depth_counter(
+    depth: Res<Depth>,
+    mut texts: Query<&mut Text, With<DepthCounter>>,
+    board_assets: Res<BoardAssets>,
+    mut update_depth_counter_rdr: EventReader<UpdateDepthCounterEvent>,
+) {
+    for _ in update_depth_counter_rdr.iter() {
+        let mut text = texts.get_single_mut().expect("Expected one depth counter");
+        *text = Text::with_section(
+            depth.to_string(),
+            TextStyle {
+                font: board_assets.font.clone(),
+                font_size: 30.,
+                color: Color::WHITE,
+            },
+            Default::default(),
+        );
+    }
+}
+
+#[derive(Debug, Component)]
+enum DepthButtonAction {
+    Increase,
+    Decrease,
+}
+
+#[derive(Component)]
+struct DepthCounter;
+
+pub(super) fn setup_depth_counter(
+    depth: &Depth,
+    button_materials: &MenuMaterials,
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+) {
+    // Depth text
+    parent
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(50.), Val::Auto),
+                margin: Rect::all(Val::Px(10.)),
+                // horizontally center child text
+                justify_content: JustifyContent::FlexStart,
+                // vertically center child text
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .insert(Name::new("Depth counter"))
+        .with_children(|builder| {
+            builder.spawn_bundle(TextBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: "Search depth: ".to_owned(),
+                        style: TextStyle {
+                            font: font.clone(),
+                            font_size: 40.,
+                            color: Color::WHITE,
+                        },
+                    }],
+                    alignment: TextAlignment {
+                        vertical: VerticalAlign::Center,
+                        horizontal: HorizontalAlign::Center,
+                    },
+                },
+                ..Default::default()
+            });
+
+            // "-" button
+            builder
+                .spawn_bundle(ButtonBundle {
+                    style: Style {
+                        flex_shrink: 0.,
+                        size: Size::new(Val::Percent(10.), Val::Px(20.)),
+                        margin: Rect {
+                            left: Val::Auto,
+                            right: Val::Auto,
+                            ..Default::default()
+                        },
+                        justify_content: JustifyContent::Center,
+                        ..Default::default()
+                    },
+                    color: button_materials.button_normal.into(),
+                    ..Default::default()
+                })
+                .insert(DepthButtonAction::Decrease)
+                .insert(SimpleButton)
+                .with_children(|parent| {
+                    parent.spawn_bundle(TextBundle {
+                        style: Style {
+                            flex_shrink: 0.,
+                            size: Size::new(Val::Undefined, Val::Px(20.)),
+                            margin: Rect {
+                                left: Val::Auto,
+                                right: Val::Auto,
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::with_section(
+                            "-",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 30.,
+                                color: Color::WHITE,
+                            },
+                            Default::default(),
+                        ),
+                        ..Default::default()
+                    });
+                });
+
+            // depth counter
+            builder
+                .spawn_bundle(TextBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(20.), Val::Px(20.)),
+                        margin: Rect {
+                            left: Val::Auto,
+                            right: Val::Auto,
+                            ..Default::default()
+                        },
+                        justify_content: JustifyContent::Center,
+                        ..Default::default()
+                    },
+                    text: Text::with_section(
+                        depth.to_string(),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 30.,
+                            color: Color::WHITE,
+                        },
+                        Default::default(),
+                    ),
+                    ..Default::default()
+                })
+                .insert(DepthCounter);
+
+            // "+" button
+            builder
+                .spawn_bundle(ButtonBundle {
+                    style: Style {
+                        flex_shrink: 0.,
+                        size: Size::new(Val::Percent(10.), Val::Px(20.)),
+                        margin: Rect {
+                            left: Val::Auto,
+                            right: Val::Auto,
+                            ..Default::default()
+                        },
+                        justify_content: JustifyContent::Center,
+                        ..Default::default()
+                    },
+                    color: button_materials.button_normal.into(),
+                    ..Default::default()
+                })
+                .insert(DepthButtonAction::Increase)
+                .insert(SimpleButton)
+                .with_children(|parent| {
+                    parent.spawn_bundle(TextBundle {
+                        style: Style {
+                            flex_shrink: 0.,
+                            size: Size::new(Val::Undefined, Val::Px(20.)),
+                            margin: Rect {
+                                left: Val::Auto,
+                                right: Val::Auto,
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        text: Text::with_section(
+                            "+",
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 30.,
+                                color: Color::WHITE,
+                            },
+                            Default::default(),
+                        ),
+                        ..Default::default()
+                    });
+                });
+        });
+}
@@ -0,0 +1,31 @@
+use bevy::{log, prelude::*};
+
+use crate::resources::draft_options::DraftOptions;
+
+use super::set_button_text;
+
+#[derive(Component)]
+pub(super) struct DraftButton;
+
+#[inline]
+pub(super) fn draft_button_text(draft_options: &DraftOptions) -> String {
+    if draft_options.enabled {
+        format!("DRAFT: {} CARDS", draft_options.pool_size())
+    } else {
+        "DRAFT: OFF".to_owned()
+    }
+}
+
+pub(super) fn draft_button_press_system(
+    buttons: Query<(&Interaction, &Children), (Changed<Interaction>, With<DraftButton>)>,
+    mut draft_options: ResMut<DraftOptions>,
+    mut texts: Query<&mut Text>,
+) {
+    for (interaction, children) in buttons.iter() {
+        if *interaction == Interaction::Clicked {
+            draft_options.next();
+            log::info!("Draft options: {:?}", draft_options);
+            set_button_text(children, &mut texts, &draft_button_text(&draft_options));
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::{log, prelude::*};
+
+use crate::resources::{board_assets::BoardAssets, engine_options::EngineOptions};
+
+use super::{
+    selector::{label_text, setup_selector},
+    MenuMaterials,
+};
+
+#[derive(Debug, Component)]
+pub(super) enum EngineButtonAction {
+    Previous,
+    Next,
+}
+
+#[derive(Default, Component)]
+pub(super) struct EngineLabel;
+
+pub(super) struct UpdateEngineLabelEvent;
+
+pub(super) fn engine_button_press_system(
+    buttons: Query<(&Interaction, &EngineButtonAction), (Changed<Interaction>, With<Button>)>,
+    mut engine_options: ResMut<EngineOptions>,
+    mut update_engine_label_ewr: EventWriter<UpdateEngineLabelEvent>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction == Interaction::Clicked {
+            match button {
+                EngineButtonAction::Next => engine_options.next(),
+                EngineButtonAction::Previous => engine_options.prev(),
+            };
+            log::info!(
+                "Changed the engine to {:?}",
+                engine_options.selected_engine()
+            );
+            update_engine_label_ewr.send(UpdateEngineLabelEvent);
+        }
+    }
+}
+
+pub(super) fn update_engine_label(
+    engine_options: Res<EngineOptions>,
+    mut texts: Query<&mut Text, With<EngineLabel>>,
+    board_assets: Res<BoardAssets>,
+    mut update_engine_label_rdr: EventReader<UpdateEngineLabelEvent>,
+) {
+    for _ in update_engine_label_rdr.iter() {
+        let mut text = texts.get_single_mut().expect("Expected one engine label");
+        *text = label_text(engine_options.to_string(), &board_assets);
+    }
+}
+
+pub(super) fn setup_engine_selector(
+    engine_options: &EngineOptions,
+    button_materials: &MenuMaterials,
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+) {
+    setup_selector::<EngineLabel, _>(
+        parent,
+        button_materials,
+        font,
+        "Engine: ",
+        40.,
+        engine_options.to_string(),
+        [EngineButtonAction::Previous, EngineButtonAction::Next],
+    );
+}
@@ -0,0 +1,65 @@
+use bevy::{log, prelude::*};
+
+use crate::resources::{board_assets::BoardAssets, handicap::Handicap};
+
+use super::{
+    selector::{label_text, setup_selector},
+    MenuMaterials,
+};
+
+#[derive(Debug, Component)]
+pub(super) enum HandicapButtonAction {
+    Previous,
+    Next,
+}
+
+#[derive(Default, Component)]
+pub(super) struct HandicapLabel;
+
+pub(super) struct UpdateHandicapLabelEvent;
+
+pub(super) fn handicap_button_press_system(
+    buttons: Query<(&Interaction, &HandicapButtonAction), (Changed<Interaction>, With<Button>)>,
+    mut handicap: ResMut<Handicap>,
+    mut update_handicap_label_ewr: EventWriter<UpdateHandicapLabelEvent>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction == Interaction::Clicked {
+            match button {
+                HandicapButtonAction::Next => handicap.next(),
+                HandicapButtonAction::Previous => handicap.prev(),
+            };
+            log::info!("Changed handicap to {:?}", handicap);
+            update_handicap_label_ewr.send(UpdateHandicapLabelEvent);
+        }
+    }
+}
+
+pub(super) fn update_handicap_label(
+    handicap: Res<Handicap>,
+    mut texts: Query<&mut Text, With<HandicapLabel>>,
+    board_assets: Res<BoardAssets>,
+    mut update_handicap_label_rdr: EventReader<UpdateHandicapLabelEvent>,
+) {
+    for _ in update_handicap_label_rdr.iter() {
+        let mut text = texts.get_single_mut().expect("Expected one handicap label");
+        *text = label_text(handicap.to_string(), &board_assets);
+    }
+}
+
+pub(super) fn setup_handicap_selector(
+    handicap: &Handicap,
+    button_materials: &MenuMaterials,
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+) {
+    setup_selector::<HandicapLabel, _>(
+        parent,
+        button_materials,
+        font,
+        "Handicap: ",
+        50.,
+        handicap.to_string(),
+        [HandicapButtonAction::Previous, HandicapButtonAction::Next],
+    );
+}
@@ -1,8 +1,25 @@
+mod cards;
+mod clock;
+mod coordinates;
+mod depth;
+mod draft;
+mod engine;
+mod handicap;
+mod network;
+mod palette;
+mod rules;
+mod selector;
+mod theme;
+
 use bevy::{log, prelude::*};
 
 use crate::{
     bounds::Bounds2,
-    components::{background::Background, card_index::CardIndex},
+    components::card_index::CardIndex,
+    network_plugin::{
+        protocol::Message,
+        session::{NetworkRole, NetworkSession},
+    },
     resources::{
         app_state::AppState,
         board_assets::BoardAssets,
@@ -10,13 +27,60 @@ use crate::{
         card::CARDS,
         deck_options::DeckOptions,
         depth::Depth,
+        draft::Draft,
+        draft_options::DraftOptions,
+        draw_rules::DrawRules,
+        engine_options::EngineOptions,
         game_state::{PlayerColor, PlayerType},
+        handicap::Handicap,
+        menu_settings::MenuSettings,
+        network_options::NetworkOptions,
         physical_deck::PhysicalDeck,
+        saved_game::SavedGame,
         selected::SelectedPlayers,
+        theme_options::ThemeOptions,
+        time_control::TimeControl,
     },
+    rules::rule_set::RuleSetType,
     BoardPlugin,
 };
 
+use self::{
+    cards::{
+        color_selected_cards, deal_selected_cards, menu_card_places, relayout_menu_cards,
+        reset_selected_cards, reskin_menu_cards, restore_selected_cards, CardColors,
+        ResetSelectedCardsEvent,
+    },
+    clock::{
+        setup_time_control_selector, time_control_button_press_system, update_time_control_label,
+        UpdateTimeControlLabelEvent,
+    },
+    coordinates::{coordinates_button_press_system, coordinates_button_text, CoordinatesButton},
+    depth::{
+        depth_button_press_system, setup_depth_counter, update_depth_counter,
+        UpdateDepthCounterEvent,
+    },
+    draft::{draft_button_press_system, draft_button_text, DraftButton},
+    engine::{
+        engine_button_press_system, setup_engine_selector, update_engine_label,
+        UpdateEngineLabelEvent,
+    },
+    handicap::{
+        handicap_button_press_system, setup_handicap_selector, update_handicap_label,
+        UpdateHandicapLabelEvent,
+    },
+    network::{
+        address_input_system, network_button_press_system, network_status, setup_network_row,
+        update_network_labels,
+    },
+    palette::{palette_button_press_system, palette_button_text, PaletteButton},
+    rules::{
+        draw_rules_button_press_system, rules_button_press_system, rules_button_text,
+        setup_draw_rules_selectors, RulesButton,
+    },
+    theme::{theme_button_press_system, theme_button_text, ThemeButton},
+};
+
 struct MainMenuData {
     camera_entity: Entity,
     title_root: Entity,
@@ -24,8 +88,13 @@ struct MainMenuData {
     player_list_root: Entity,
     // bounds are needed to color the background
     cards: Vec<(Entity, Bounds2)>,
+    /// Size of a card before it is scaled to the window
+    card_size: Vec2,
     button_root: Entity,
     depth_counter_root: Entity,
+    handicap_root: Entity,
+    network_root: Entity,
+    rules_root: Entity,
 }
 
 pub struct MainMenuPlugin;
@@ -34,24 +103,53 @@ impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
         log::info!("Loaded main menu plugin!");
         app.add_system_set(
-            SystemSet::on_enter(AppState::MainMenu).with_system(setup_ui::<AppState>),
+            SystemSet::on_enter(AppState::MainMenu)
+                .with_system(apply_menu_settings.label("apply_menu_settings"))
+                .with_system(setup_ui::<AppState>.after("apply_menu_settings")),
         )
         .add_system_set(
             SystemSet::on_update(AppState::MainMenu)
                 .with_system(button_system)
                 .with_system(color_selected_cards)
                 .with_system(reset_selected_cards)
-                .with_system(button_press_system)
+                .with_system(restore_selected_cards.label("restore_selected_cards"))
+                .with_system(
+                    save_menu_settings
+                        .label("save_menu_settings")
+                        .after("restore_selected_cards")
+                        .before("menu_buttons"),
+                )
+                .with_system(button_press_system.label("menu_buttons"))
                 .with_system(list_press_system)
                 .with_system(update_button_color)
                 .with_system(depth_button_press_system)
-                .with_system(update_depth_counter),
+                .with_system(update_depth_counter)
+                .with_system(handicap_button_press_system)
+                .with_system(update_handicap_label)
+                .with_system(time_control_button_press_system)
+                .with_system(update_time_control_label)
+                .with_system(engine_button_press_system)
+                .with_system(update_engine_label)
+                .with_system(draft_button_press_system)
+                .with_system(rules_button_press_system)
+                .with_system(draw_rules_button_press_system)
+                .with_system(coordinates_button_press_system)
+                .with_system(theme_button_press_system)
+                .with_system(palette_button_press_system)
+                .with_system(reskin_menu_cards)
+                .with_system(relayout_menu_cards)
+                .with_system(network_button_press_system)
+                .with_system(address_input_system)
+                .with_system(update_network_labels),
         )
         .add_system_set(SystemSet::on_exit(AppState::MainMenu).with_system(cleanup));
 
         app.add_event::<ResetSelectedCardsEvent>();
         app.add_event::<UpdateButtonColorEvent>();
         app.add_event::<UpdateDepthCounterEvent>();
+        app.add_event::<UpdateHandicapLabelEvent>();
+        app.add_event::<UpdateTimeControlLabelEvent>();
+        app.add_event::<UpdateEngineLabelEvent>();
     }
 }
 
@@ -74,20 +172,6 @@ impl Default for SelectedCards {
     }
 }
 
-struct CardColors(pub Vec<Color>);
-
-impl Default for CardColors {
-    fn default() -> Self {
-        Self(vec![
-            Color::RED,
-            Color::RED,
-            Color::LIME_GREEN,
-            Color::CYAN,
-            Color::CYAN,
-        ])
-    }
-}
-
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
 #[derive(Debug, Clone, Component)]
 pub struct ListElement {
@@ -100,6 +184,8 @@ pub struct ListElement {
 enum ButtonAction {
     StartGame,
     ClearSelectedCards,
+    ShowStatistics,
+    ContinueGame,
 }
 
 #[derive(Component)]
@@ -121,159 +207,209 @@ fn button_system(
     }
 }
 
-struct UpdateDepthCounterEvent;
-
-fn depth_button_press_system(
-    buttons: Query<(&Interaction, &DepthButtonAction), (Changed<Interaction>, With<Button>)>,
+/// Restores the options saved in the last session before the menu is spawned
+fn apply_menu_settings(
+    menu_settings: Res<MenuSettings>,
+    mut selected_players: ResMut<SelectedPlayers>,
     mut depth: ResMut<Depth>,
-    mut update_depth_counter_ewr: EventWriter<UpdateDepthCounterEvent>,
+    mut handicap: ResMut<Handicap>,
+    mut time_control: ResMut<TimeControl>,
+    mut rule_set_type: ResMut<RuleSetType>,
+    mut draw_rules: ResMut<DrawRules>,
+    mut draft_options: ResMut<DraftOptions>,
+    mut board_options: ResMut<BoardOptions>,
+    mut engine_options: ResMut<EngineOptions>,
+    mut theme_options: ResMut<ThemeOptions>,
+    mut update_button_color_ewr: EventWriter<UpdateButtonColorEvent>,
 ) {
-    for (interaction, button) in buttons.iter() {
-        if *interaction == Interaction::Clicked {
-            match button {
-                DepthButtonAction::Increase => {
-                    #[cfg(feature = "debug")]
-                    log::info!("Added 1 to the depth {:?}", depth);
-                    depth.add();
-                }
-                DepthButtonAction::Decrease => {
-                    #[cfg(feature = "debug")]
-                    log::info!("Subtracted 1 from the depth {:?}", depth);
-                    depth.sub();
-                }
-            };
-            update_depth_counter_ewr.send(UpdateDepthCounterEvent);
-        }
+    selected_players.red_player = menu_settings.red_player;
+    selected_players.blue_player = menu_settings.blue_player;
+    *depth = Depth(menu_settings.depth);
+    *handicap = menu_settings.handicap;
+    *time_control = menu_settings.time_control;
+    *rule_set_type = menu_settings.rule_set_type;
+    *draw_rules = menu_settings.draw_rules;
+    draft_options.enabled = menu_settings.draft;
+    draft_options.set_pool_size(menu_settings.draft_pool);
+    board_options.show_coordinates = menu_settings.coordinates;
+    if let Some(engine) = &menu_settings.engine {
+        engine_options.select(engine);
     }
-}
-
-fn update_depth_counter(
-    depth: Res<Depth>,
-    mut texts: Query<&mut Text, With<DepthCounter>>,
-    board_assets: Res<BoardAssets>,
-    mut update_depth_counter_rdr: EventReader<UpdateDepthCounterEvent>,
-) {
-    for _ in update_depth_counter_rdr.iter() {
-        let mut text = texts.get_single_mut().expect("Expected one depth counter");
-        *text = Text::with_section(
-            depth.to_string(),
-            TextStyle {
-                font: board_assets.font.clone(),
-                font_size: 30.,
-                color: Color::WHITE,
-            },
-            Default::default(),
-        );
+    if let Some(theme) = &menu_settings.theme {
+        theme_options.select(theme);
     }
+    theme_options.palette = menu_settings.palette;
+    // the player lists are colored once they are spawned
+    update_button_color_ewr.send(UpdateButtonColorEvent);
 }
 
-fn color_selected_cards(
-    windows: Res<Windows>,
-    menu_data: Res<MainMenuData>,
-    mut colors: ResMut<CardColors>,
-    mut selected_cards: ResMut<SelectedCards>,
-    mouse_button_inputs: Res<Input<MouseButton>>,
-    cards_q: Query<(&CardIndex, &Children)>,
-    mut sprites: Query<&mut Sprite, With<Background>>,
+/// Saves the options as soon as they are changed in the menu. Runs before the menu buttons,
+/// so the options changed only for the started game, e.g. by a continued game, are not saved
+fn save_menu_settings(
+    selected_players: Res<SelectedPlayers>,
+    depth: Res<Depth>,
+    handicap: Res<Handicap>,
+    time_control: Res<TimeControl>,
+    rule_set_type: Res<RuleSetType>,
+    draw_rules: Res<DrawRules>,
+    draft_options: Res<DraftOptions>,
+    board_options: Res<BoardOptions>,
+    engine_options: Res<EngineOptions>,
+    theme_options: Res<ThemeOptions>,
+    selected_cards: Res<SelectedCards>,
+    mut menu_settings: ResMut<MenuSettings>,
 ) {
-    if !mouse_button_inputs.just_pressed(MouseButton::Left) {
-        return;
-    }
+    let settings = MenuSettings {
+        red_player: selected_players.red_player,
+        blue_player: selected_players.blue_player,
+        depth: depth.0,
+        handicap: *handicap,
+        time_control: *time_control,
+        rule_set_type: *rule_set_type,
+        draw_rules: *draw_rules,
+        draft: draft_options.enabled,
+        draft_pool: draft_options.pool_size(),
+        coordinates: board_options.show_coordinates,
+        engine: engine_options
+            .selected_engine()
+            .map(|engine| engine.name.clone()),
+        theme: Some(theme_options.selected_theme().0.clone()),
+        palette: theme_options.palette,
+        selected_cards: selected_cards.0.iter().map(|(_, idx)| *idx).collect(),
+    };
 
-    if colors.0.is_empty() {
-        log::info!("All cards are colored!");
-        return;
-    }
-
-    let window = windows.get_primary().unwrap();
-    let position = window.cursor_position();
-
-    for (entity, bounds) in menu_data.cards.iter() {
-        if let Some(pos) = position {
-            if !bounds.in_bounds_window(&window, pos) {
-                continue;
-            }
-
-            if let Ok((card_index, children)) = cards_q.get(*entity) {
-                'child: for child in children.iter() {
-                    match sprites.get_mut(*child) {
-                        Ok(mut sprite) => {
-                            let color = colors.0.remove(0);
-                            sprite.color = color;
-                            log::info!(
-                                "Selectd a card with index {} and given the color {:?}",
-                                card_index.0,
-                                color
-                            );
-
-                            selected_cards.0.push((*entity, card_index.0));
-                            break 'child;
-                        }
-                        Err(_) => log::warn!("Sprite for background was not found"),
-                    }
-                }
-            }
-        }
-    }
-}
-
-struct ResetSelectedCardsEvent;
-
-fn reset_selected_cards(
-    mut colors: ResMut<CardColors>,
-    mut selected_cards: ResMut<SelectedCards>,
-    cards_q: Query<&Children, With<CardIndex>>,
-    mut sprites: Query<&mut Sprite, With<Background>>,
-    mut reset_selected_cards_rdr: EventReader<ResetSelectedCardsEvent>,
-) {
-    for _ in reset_selected_cards_rdr.iter() {
-        *colors = CardColors::default();
-        for (entity, _) in selected_cards.0.iter() {
-            if let Ok(children) = cards_q.get(*entity) {
-                'child: for child in children.iter() {
-                    match sprites.get_mut(*child) {
-                        Ok(mut sprite) => {
-                            sprite.color = Color::WHITE;
-                            break 'child;
-                        }
-                        Err(_) => log::warn!("Error while resetting card background!"),
-                    }
-                }
-            }
-        }
-        selected_cards.0.clear();
-        log::info!("Card colors after reset: {:?}", colors.0);
-        log::info!("Card colors after reset: {:?}", selected_cards);
+    if settings != *menu_settings {
+        settings.save();
+        *menu_settings = settings;
     }
 }
 
 fn button_press_system(
+    mut commands: Commands,
     buttons: Query<(&Interaction, &ButtonAction), (Changed<Interaction>, With<Button>)>,
     mut physical_deck: ResMut<PhysicalDeck>,
     selected_cards: Res<SelectedCards>,
+    mut selected_players: ResMut<SelectedPlayers>,
+    mut depth: ResMut<Depth>,
+    mut handicap: ResMut<Handicap>,
+    draft_options: Res<DraftOptions>,
+    mut rule_set_type: ResMut<RuleSetType>,
+    mut draw_rules: ResMut<DrawRules>,
+    mut network_session: Option<ResMut<NetworkSession>>,
     mut state: ResMut<State<AppState>>,
     mut reset_selected_cards_ewr: EventWriter<ResetSelectedCardsEvent>,
 ) {
     for (interaction, button) in buttons.iter() {
         if *interaction == Interaction::Clicked {
             match button {
+                ButtonAction::StartGame if selected_players.remote_color().is_some() => {
+                    // the host deals the cards and tells the guest its side
+                    let session = match network_session.as_mut() {
+                        Some(session) if session.role == NetworkRole::Host && session.is_ready => {
+                            session
+                        }
+                        _ => {
+                            log::info!("Remote player needs a connected guest. Host a game first");
+                            continue;
+                        }
+                    };
+                    if selected_players.red_player == selected_players.blue_player {
+                        log::info!("Only one side can be played remotely");
+                        continue;
+                    }
+
+                    log::info!("New network game");
+                    // network games are played without handicaps and with the default draw rules
+                    *handicap = Handicap::None;
+                    *draw_rules = DrawRules::default();
+                    deal_selected_cards(&mut physical_deck, &selected_cards);
+
+                    let remote_color = selected_players.remote_color().unwrap();
+                    let mut cards = [0; 5];
+                    cards.copy_from_slice(&physical_deck.indices());
+                    let start = Message::Start {
+                        color: remote_color,
+                        rules: *rule_set_type,
+                        cards,
+                    };
+                    if let Err(e) = session.send(&start) {
+                        log::warn!("Could not start the network game: {}", e);
+                        continue;
+                    }
+                    session.local_color = Some(remote_color.enemy());
+                    state.set(AppState::InProgress).unwrap();
+                }
+                ButtonAction::StartGame
+                    if network_session.as_ref().map_or(false, |session| {
+                        session.role == NetworkRole::Host && session.in_room() && !session.is_ready
+                    }) =>
+                {
+                    // nobody has joined the room, so both sides are played here and the game is only watched
+                    log::info!("New watched game");
+                    *handicap = Handicap::None;
+                    *draw_rules = DrawRules::default();
+                    deal_selected_cards(&mut physical_deck, &selected_cards);
+
+                    let mut cards = [0; 5];
+                    cards.copy_from_slice(&physical_deck.indices());
+                    let start = Message::Start {
+                        // there is no guest, the server lets the host play both sides
+                        color: PlayerColor::Blue,
+                        rules: *rule_set_type,
+                        cards,
+                    };
+                    if let Err(e) = network_session.as_mut().unwrap().send(&start) {
+                        log::warn!("Could not start the watched game: {}", e);
+                        continue;
+                    }
+                    state.set(AppState::InProgress).unwrap();
+                }
+                ButtonAction::StartGame if draft_options.enabled || handicap.needs_draft() => {
+                    log::info!("New Game with a card draft");
+                    // selected cards are revealed in the draft pool
+                    let res = selected_cards.0.iter().map(|v| v.1).collect::<Vec<_>>();
+                    commands.insert_resource(Draft::new(
+                        draft_options.pool_size(),
+                        &res,
+                        handicap.first_card_choice(),
+                    ));
+                    state.set(AppState::Draft).unwrap();
+                }
                 ButtonAction::StartGame => {
                     log::info!("New Game");
-                    if selected_cards.0.len() == 5 {
-                        let res = selected_cards.0.iter().map(|v| v.1).collect::<Vec<_>>();
-                        physical_deck.take_cards_from_indices(&res);
-                    } else if selected_cards.0.len() == 0 {
-                        physical_deck.take_random_cards();
-                    } else {
-                        let res = selected_cards.0.iter().map(|v| v.1).collect::<Vec<_>>();
-                        physical_deck.take_some_random_cards(&res);
-                    }
+                    deal_selected_cards(&mut physical_deck, &selected_cards);
                     state.set(AppState::InProgress).unwrap();
                 }
                 ButtonAction::ClearSelectedCards => {
                     log::info!("Clear selected");
                     reset_selected_cards_ewr.send(ResetSelectedCardsEvent);
                 }
+                ButtonAction::ShowStatistics => {
+                    log::info!("Statistics");
+                    state.set(AppState::Statistics).unwrap();
+                }
+                ButtonAction::ContinueGame => {
+                    let saved_game = match SavedGame::load() {
+                        Some(saved_game) => saved_game,
+                        None => {
+                            log::info!("There is no saved game to continue");
+                            continue;
+                        }
+                    };
+
+                    log::info!("Continuing the saved game");
+                    // the board and the turn are restored by the board plugin
+                    selected_players.red_player = saved_game.red_player;
+                    selected_players.blue_player = saved_game.blue_player;
+                    *depth = Depth(saved_game.depth);
+                    *handicap = saved_game.handicap;
+                    *rule_set_type = saved_game.rule_set_type;
+                    *draw_rules = saved_game.draw_rules;
+                    physical_deck.clear();
+                    physical_deck.take_cards_in_deck_order(&saved_game.cards);
+                    commands.insert_resource(saved_game);
+                    state.set(AppState::InProgress).unwrap();
+                }
             };
         }
     }
@@ -361,14 +497,25 @@ fn setup_ui<T>(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     deck_options: Res<DeckOptions>,
-    window: Res<WindowDescriptor>,
+    windows: Res<Windows>,
     board_assets: Res<BoardAssets>,
     board_options: Res<BoardOptions>,
-    mut depth: ResMut<Depth>,
+    draft_options: Res<DraftOptions>,
+    theme_options: Res<ThemeOptions>,
+    rule_set_type: Res<RuleSetType>,
+    network_options: Res<NetworkOptions>,
+    network_session: Option<Res<NetworkSession>>,
+    engine_options: Res<EngineOptions>,
+    // a system takes at most 16 parameters, so the values of the selectors come in a tuple
+    (depth, handicap, time_control, draw_rules): (
+        Res<Depth>,
+        Res<Handicap>,
+        Res<TimeControl>,
+        Res<DrawRules>,
+    ),
     mut physical_deck: ResMut<PhysicalDeck>,
 ) {
     physical_deck.clear();
-    *depth = Depth::default();
     let camera_entity = commands.spawn_bundle(UiCameraBundle::default()).id();
 
     let button_materials = MenuMaterials {
@@ -381,15 +528,18 @@ fn setup_ui<T>(
         button_text: Color::WHITE,
     };
 
+    let window = windows.get_primary().unwrap();
+    let window_size = Vec2::new(window.width(), window.height());
+
     let mut tile_size = match deck_options.tile_size {
         TileSize::Fixed(size) => size,
         TileSize::Adaptive { min, max } => {
             // (5, 5) - board size
-            BoardPlugin::<T>::adaptive_tile_size(&window, (min, max), (5, 5))
+            BoardPlugin::<T>::adaptive_tile_size(window_size, (min, max), (5, 5))
         }
     };
 
-    tile_size /= 1.3;
+    tile_size /= 1.45;
 
     let board_size = Vec2::new(5. * tile_size, 5. * tile_size);
 
@@ -456,7 +606,7 @@ fn setup_ui<T>(
                 position_type: PositionType::Absolute,
                 position: Rect {
                     left: Val::Px(0.0),
-                    top: Val::Px(window.height * 0.08),
+                    top: Val::Percent(8.),
                     ..Default::default()
                 },
                 align_items: AlignItems::Center,
@@ -488,8 +638,8 @@ fn setup_ui<T>(
                             sections: vec![TextSection {
                                 value: "
 Welcome to the game of Onitama! Rules of the game can be found online. The author is cyoq and the game is on a Github: https://github.com/cyoq/onitama-rs
-In this menu you can choose the first and the second player types. Search depth is appliable only to AlphaBeta.
-Select the cards for the red and blue players and one neutral card. If the amount of cards is not 5, it will be replenished with the random cards. Have a good game!
+In this menu you can choose the first and the second player types. Search depth is appliable only to AlphaBeta. External plays the selected engine program, the engines are listed in engines.txt. With a clock every side has its own time and loses when it runs out, bots spend it carefully.
+Select the cards for the red and blue players and one neutral card. If the amount of cards is not 5, it will be replenished with the random cards. The side shown by the stamp on the neutral card moves first. In draft mode the selected cards are revealed in the pool and the players pick their cards in turns, DRAFT sets the size of the pool from 5 cards to every card. The handicap takes pawns from the stronger side or starts a draft where the weaker side picks first. To play over the network, type the address, host or join a game and select the Remote player for the opponent side on the host. Games in a room on the game server can be watched with WATCH. With the Way of the Stream rules the game is won only by bringing the master into the enemy temple. The game is drawn when a position repeats the chosen number of times or the move limit is passed, both draw rules can be turned off. Results and ratings of your profiles are on the STATISTICS screen. A game saved with SAVE GAME is resumed with CONTINUE. Have a good game!
                                 ".to_string(),
                                 style: TextStyle {
                                     font: font.clone(),
@@ -514,7 +664,7 @@ Select the cards for the red and blue players and one neutral card. If the amoun
                 position_type: PositionType::Absolute,
                 position: Rect {
                     left: Val::Px(0.0),
-                    top: Val::Px(window.height * 0.16),
+                    top: Val::Percent(16.),
                     ..Default::default()
                 },
                 align_items: AlignItems::Center,
@@ -552,7 +702,7 @@ Select the cards for the red and blue players and one neutral card. If the amoun
                 position_type: PositionType::Absolute,
                 position: Rect {
                     left: Val::Px(0.0),
-                    top: Val::Px(window.height * 0.26),
+                    top: Val::Percent(26.),
                     ..Default::default()
                 },
                 align_items: AlignItems::Center,
@@ -566,34 +716,103 @@ Select the cards for the red and blue players and one neutral card. If the amoun
         .with_children(|parent| {
             let font = asset_server.load("fonts/pixeled.ttf");
             setup_depth_counter(&depth, &button_materials, parent, font.clone());
+            setup_engine_selector(&engine_options, &button_materials, parent, font.clone());
         })
         .id();
 
-    // generating the cards
-    let mut cards: Vec<(Entity, Bounds2)> = Vec::with_capacity(CARDS.len());
-    let offset = window.width / 18.;
+    let handicap_root = commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(4.)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(0.0),
+                    top: Val::Percent(30.),
+                    ..Default::default()
+                },
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .insert(Name::new("Handicap root"))
+        .with_children(|parent| {
+            let font = asset_server.load("fonts/pixeled.ttf");
+            setup_handicap_selector(&handicap, &button_materials, parent, font.clone());
+            setup_time_control_selector(&time_control, &button_materials, parent, font.clone());
+        })
+        .id();
 
-    log::info!("board size: {:?}", board_size);
+    let network_root = commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(4.)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(0.0),
+                    top: Val::Percent(34.),
+                    ..Default::default()
+                },
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .insert(Name::new("Network root"))
+        .with_children(|parent| {
+            let font = asset_server.load("fonts/pixeled.ttf");
+            setup_network_row(
+                &network_options,
+                &network_status(&network_session),
+                &button_materials,
+                parent,
+                font.clone(),
+            );
+        })
+        .id();
 
-    // moving pivot from the center to the left bottom part of the screen from screen center
-    let pivot = Vec2::new(-window.width / 2., -window.height / 2.);
-    // 36% are taken by the UI
-    let starting_y = pivot.y + window.height * 0.64 - board_size.y + offset;
-    let starting_x = pivot.x + 1.5 * offset;
+    let rules_root = commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(4.)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(0.0),
+                    top: Val::Percent(38.),
+                    ..Default::default()
+                },
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .insert(Name::new("Rules root"))
+        .with_children(|parent| {
+            let font = asset_server.load("fonts/pixeled.ttf");
+            setup_draw_rules_selectors(&draw_rules, &button_materials, parent, font.clone());
+        })
+        .id();
 
-    let cards_in_row = 7;
+    // generating the cards
+    let mut cards: Vec<(Entity, Bounds2)> = Vec::with_capacity(CARDS.len());
 
-    for (i, card) in CARDS.iter().enumerate() {
-        let position = Vec2::new(
-            starting_x + (offset + board_size.x) * (i % cards_in_row) as f32,
-            starting_y - (offset + board_size.y) * (i / cards_in_row) as f32,
-        );
+    log::info!("board size: {:?}", board_size);
 
+    let (scale, positions) = menu_card_places(window_size, board_size);
+    for (i, (card, position)) in CARDS.iter().zip(positions).enumerate() {
         let card_entity = commands
             .spawn()
-            .insert(Transform::from_translation(Vec3::new(
-                position.x, position.y, 0.,
-            )))
+            .insert(Transform {
+                translation: position.extend(0.),
+                scale: Vec3::new(scale, scale, 1.),
+                ..Default::default()
+            })
             .insert(GlobalTransform::default())
             .insert(CardIndex(i as u8))
             .insert(Name::new(card.name))
@@ -605,12 +824,13 @@ Select the cards for the red and blue players and one neutral card. If the amoun
                     &board_assets,
                     board_options.tile_padding,
                     tile_size,
+                    false,
                 );
             })
             .id();
         let bounds = Bounds2 {
-            position: position - board_size / 2.,
-            size: board_size,
+            position: position - board_size * scale / 2.,
+            size: board_size * scale,
         };
         cards.push((card_entity, bounds));
     }
@@ -639,6 +859,62 @@ Select the cards for the red and blue players and one neutral card. If the amoun
                 ButtonAction::ClearSelectedCards,
             );
 
+            setup_single_button(
+                parent,
+                &draft_button_text(&draft_options),
+                button_materials.button_normal.into(),
+                font.clone(),
+                DraftButton,
+            );
+
+            setup_single_button(
+                parent,
+                rules_button_text(&rule_set_type),
+                button_materials.button_normal.into(),
+                font.clone(),
+                RulesButton,
+            );
+
+            setup_single_button(
+                parent,
+                coordinates_button_text(&board_options),
+                button_materials.button_normal.into(),
+                font.clone(),
+                CoordinatesButton,
+            );
+
+            setup_single_button(
+                parent,
+                &theme_button_text(&theme_options),
+                button_materials.button_normal.into(),
+                font.clone(),
+                ThemeButton,
+            );
+
+            setup_single_button(
+                parent,
+                &palette_button_text(&theme_options),
+                button_materials.button_normal.into(),
+                font.clone(),
+                PaletteButton,
+            );
+
+            setup_single_button(
+                parent,
+                "STATISTICS",
+                button_materials.button_normal.into(),
+                font.clone(),
+                ButtonAction::ShowStatistics,
+            );
+
+            setup_single_button(
+                parent,
+                "CONTINUE",
+                button_materials.button_normal.into(),
+                font.clone(),
+                ButtonAction::ContinueGame,
+            );
+
             setup_single_button(
                 parent,
                 "START A GAME",
@@ -652,7 +928,7 @@ Select the cards for the red and blue players and one neutral card. If the amoun
     commands.insert_resource(button_materials);
 
     commands.insert_resource(SelectedCards::default());
-    commands.insert_resource(CardColors::default());
+    commands.insert_resource(CardColors::new(&board_assets));
 
     commands.insert_resource(MainMenuData {
         camera_entity,
@@ -660,177 +936,15 @@ Select the cards for the red and blue players and one neutral card. If the amoun
         description_root,
         player_list_root,
         cards,
+        card_size: board_size,
         button_root,
         depth_counter_root,
+        handicap_root,
+        network_root,
+        rules_root,
     });
 }
 
-#[derive(Debug, Component)]
-enum DepthButtonAction {
-    Increase,
-    Decrease,
-}
-
-#[derive(Component)]
-struct DepthCounter;
-
-fn setup_depth_counter(
-    depth: &Depth,
-    button_materials: &MenuMaterials,
-    parent: &mut ChildBuilder,
-    font: Handle<Font>,
-) {
-    // Depth text
-    parent
-        .spawn_bundle(NodeBundle {
-            style: Style {
-                size: Size::new(Val::Percent(50.), Val::Auto),
-                margin: Rect::all(Val::Px(10.)),
-                // horizontally center child text
-                justify_content: JustifyContent::FlexStart,
-                // vertically center child text
-                align_items: AlignItems::Center,
-                ..Default::default()
-            },
-            color: Color::rgb(0.1, 0.1, 0.1).into(),
-            ..Default::default()
-        })
-        .insert(Name::new("Depth counter"))
-        .with_children(|builder| {
-            builder.spawn_bundle(TextBundle {
-                text: Text {
-                    sections: vec![TextSection {
-                        value: "Search depth: ".to_owned(),
-                        style: TextStyle {
-                            font: font.clone(),
-                            font_size: 40.,
-                            color: Color::WHITE,
-                        },
-                    }],
-                    alignment: TextAlignment {
-                        vertical: VerticalAlign::Center,
-                        horizontal: HorizontalAlign::Center,
-                    },
-                },
-                ..Default::default()
-            });
-
-            // "-" button
-            builder
-                .spawn_bundle(ButtonBundle {
-                    style: Style {
-                        flex_shrink: 0.,
-                        size: Size::new(Val::Percent(10.), Val::Px(20.)),
-                        margin: Rect {
-                            left: Val::Auto,
-                            right: Val::Auto,
-                            ..Default::default()
-                        },
-                        justify_content: JustifyContent::Center,
-                        ..Default::default()
-                    },
-                    color: button_materials.button_normal.into(),
-                    ..Default::default()
-                })
-                .insert(DepthButtonAction::Decrease)
-                .insert(SimpleButton)
-                .with_children(|parent| {
-                    parent.spawn_bundle(TextBundle {
-                        style: Style {
-                            flex_shrink: 0.,
-                            size: Size::new(Val::Undefined, Val::Px(20.)),
-                            margin: Rect {
-                                left: Val::Auto,
-                                right: Val::Auto,
-                                ..Default::default()
-                            },
-                            ..Default::default()
-                        },
-                        text: Text::with_section(
-                            "-",
-                            TextStyle {
-                                font: font.clone(),
-                                font_size: 30.,
-                                color: Color::WHITE,
-                            },
-                            Default::default(),
-                        ),
-                        ..Default::default()
-                    });
-                });
-
-            // depth counter
-            builder
-                .spawn_bundle(TextBundle {
-                    style: Style {
-                        size: Size::new(Val::Px(20.), Val::Px(20.)),
-                        margin: Rect {
-                            left: Val::Auto,
-                            right: Val::Auto,
-                            ..Default::default()
-                        },
-                        justify_content: JustifyContent::Center,
-                        ..Default::default()
-                    },
-                    text: Text::with_section(
-                        depth.to_string(),
-                        TextStyle {
-                            font: font.clone(),
-                            font_size: 30.,
-                            color: Color::WHITE,
-                        },
-                        Default::default(),
-                    ),
-                    ..Default::default()
-                })
-                .insert(DepthCounter);
-
-            // "+" button
-            builder
-                .spawn_bundle(ButtonBundle {
-                    style: Style {
-                        flex_shrink: 0.,
-                        size: Size::new(Val::Percent(10.), Val::Px(20.)),
-                        margin: Rect {
-                            left: Val::Auto,
-                            right: Val::Auto,
-                            ..Default::default()
-                        },
-                        justify_content: JustifyContent::Center,
-                        ..Default::default()
-                    },
-                    color: button_materials.button_normal.into(),
-                    ..Default::default()
-                })
-                .insert(DepthButtonAction::Increase)
-                .insert(SimpleButton)
-                .with_children(|parent| {
-                    parent.spawn_bundle(TextBundle {
-                        style: Style {
-                            flex_shrink: 0.,
-                            size: Size::new(Val::Undefined, Val::Px(20.)),
-                            margin: Rect {
-                                left: Val::Auto,
-                                right: Val::Auto,
-                                ..Default::default()
-                            },
-                            ..Default::default()
-                        },
-                        text: Text::with_section(
-                            "+",
-                            TextStyle {
-                                font: font.clone(),
-                                font_size: 30.,
-                                color: Color::WHITE,
-                            },
-                            Default::default(),
-                        ),
-                        ..Default::default()
-                    });
-                });
-        });
-}
-
 fn setup_single_list(
     parent: &mut ChildBuilder,
     player_color: PlayerColor,
@@ -842,12 +956,14 @@ fn setup_single_list(
         ("Human", PlayerType::Human),
         ("Random", PlayerType::Random),
         ("AlphaBeta", PlayerType::AlphaBeta),
+        ("Remote", PlayerType::Remote),
+        ("External", PlayerType::External),
     ];
 
     parent
         .spawn_bundle(NodeBundle {
             style: Style {
-                size: Size::new(Val::Percent(30.), Val::Auto),
+                size: Size::new(Val::Percent(22.), Val::Auto),
                 margin: Rect::all(Val::Px(10.)),
                 // horizontally center child text
                 justify_content: JustifyContent::Center,
@@ -954,12 +1070,12 @@ fn setup_single_button(
     text: &str,
     color: UiColor,
     font: Handle<Font>,
-    action: ButtonAction,
+    action: impl Component,
 ) {
     parent
         .spawn_bundle(ButtonBundle {
             style: Style {
-                size: Size::new(Val::Percent(30.), Val::Auto),
+                size: Size::new(Val::Percent(22.), Val::Auto),
                 margin: Rect::all(Val::Px(10.)),
                 // horizontally center child text
                 justify_content: JustifyContent::Center,
@@ -994,6 +1110,15 @@ fn setup_single_button(
         });
 }
 
+/// Replaces the text of a button spawned by `setup_single_button`
+fn set_button_text(children: &Children, texts: &mut Query<&mut Text>, value: &str) {
+    for child in children.iter() {
+        if let Ok(mut text) = texts.get_mut(*child) {
+            text.sections[0].value = value.to_owned();
+        }
+    }
+}
+
 fn cleanup(mut commands: Commands, menu_data: Res<MainMenuData>) {
     commands.entity(menu_data.title_root).despawn_recursive();
     commands
@@ -1006,6 +1131,9 @@ fn cleanup(mut commands: Commands, menu_data: Res<MainMenuData>) {
     commands
         .entity(menu_data.depth_counter_root)
         .despawn_recursive();
+    commands.entity(menu_data.handicap_root).despawn_recursive();
+    commands.entity(menu_data.network_root).despawn_recursive();
+    commands.entity(menu_data.rules_root).despawn_recursive();
     commands.entity(menu_data.camera_entity).despawn_recursive();
 
     for (entity, _) in menu_data.cards.iter() {
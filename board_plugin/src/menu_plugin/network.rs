@@ -0,0 +1,240 @@
+use bevy::{log, prelude::*};
+
+use crate::{network_plugin::session::NetworkSession, resources::network_options::NetworkOptions};
+
+use super::{MenuMaterials, SimpleButton};
+
+// Longest address which can be typed in the menu
+const MAX_ADDRESS_LENGTH: usize = 64;
+
+#[derive(Debug, Component)]
+enum NetworkButtonAction {
+    Host,
+    Join,
+    Watch,
+}
+
+#[derive(Component)]
+struct NetworkAddressLabel;
+
+#[derive(Component)]
+struct NetworkStatusLabel;
+
+#[inline]
+pub(super) fn network_status(network_session: &Option<Res<NetworkSession>>) -> String {
+    match network_session {
+        Some(session) => session.status.clone(),
+        None => "Offline".to_owned(),
+    }
+}
+
+pub(super) fn network_button_press_system(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &NetworkButtonAction), (Changed<Interaction>, With<Button>)>,
+    network_options: Res<NetworkOptions>,
+    mut network_session: Option<ResMut<NetworkSession>>,
+    mut status_labels: Query<&mut Text, With<NetworkStatusLabel>>,
+) {
+    for (interaction, action) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+
+        // only one session at a time
+        if let Some(session) = network_session.as_mut() {
+            session.close();
+        }
+
+        let result = match action {
+            NetworkButtonAction::Host => NetworkSession::host(&network_options.address),
+            NetworkButtonAction::Join => NetworkSession::join(&network_options.address),
+            NetworkButtonAction::Watch => NetworkSession::watch(&network_options.address),
+        };
+
+        match result {
+            Ok(session) => {
+                log::info!("Network session: {}", session.status);
+                commands.insert_resource(session);
+            }
+            Err(e) => {
+                log::warn!("Could not create a network session: {}", e);
+                commands.remove_resource::<NetworkSession>();
+                for mut text in status_labels.iter_mut() {
+                    text.sections[0].value = format!("Failed: {}", e);
+                }
+            }
+        }
+    }
+}
+
+pub(super) fn address_input_system(
+    keys: Res<Input<KeyCode>>,
+    mut network_options: ResMut<NetworkOptions>,
+    mut received_character_rdr: EventReader<ReceivedCharacter>,
+) {
+    if keys.just_pressed(KeyCode::Back) {
+        network_options.address.pop();
+    }
+
+    for event in received_character_rdr.iter() {
+        let is_allowed = event.char.is_ascii_alphanumeric() || ".:-/".contains(event.char);
+        if is_allowed && network_options.address.len() < MAX_ADDRESS_LENGTH {
+            network_options.address.push(event.char);
+        }
+    }
+}
+
+pub(super) fn update_network_labels(
+    network_options: Res<NetworkOptions>,
+    network_session: Option<Res<NetworkSession>>,
+    mut address_labels: Query<&mut Text, (With<NetworkAddressLabel>, Without<NetworkStatusLabel>)>,
+    mut status_labels: Query<&mut Text, (With<NetworkStatusLabel>, Without<NetworkAddressLabel>)>,
+) {
+    if network_options.is_changed() {
+        for mut text in address_labels.iter_mut() {
+            text.sections[0].value = network_options.address.clone();
+        }
+    }
+
+    if let Some(session) = network_session.as_ref() {
+        if session.is_changed() {
+            for mut text in status_labels.iter_mut() {
+                text.sections[0].value = session.status.clone();
+            }
+        }
+    }
+}
+
+pub(super) fn setup_network_row(
+    network_options: &NetworkOptions,
+    status: &str,
+    button_materials: &MenuMaterials,
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+) {
+    parent
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(80.), Val::Auto),
+                margin: Rect::all(Val::Px(10.)),
+                justify_content: JustifyContent::FlexStart,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .insert(Name::new("Network row"))
+        .with_children(|builder| {
+            builder.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    "Network: ",
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 40.,
+                        color: Color::WHITE,
+                    },
+                    Default::default(),
+                ),
+                ..Default::default()
+            });
+
+            // address is typed with the keyboard
+            builder
+                .spawn_bundle(TextBundle {
+                    style: Style {
+                        size: Size::new(Val::Undefined, Val::Px(20.)),
+                        margin: Rect {
+                            left: Val::Auto,
+                            right: Val::Auto,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    text: Text::with_section(
+                        network_options.address.clone(),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 30.,
+                            color: Color::YELLOW,
+                        },
+                        Default::default(),
+                    ),
+                    ..Default::default()
+                })
+                .insert(NetworkAddressLabel);
+
+            for (text, action) in [
+                ("HOST", NetworkButtonAction::Host),
+                ("JOIN", NetworkButtonAction::Join),
+                ("WATCH", NetworkButtonAction::Watch),
+            ] {
+                builder
+                    .spawn_bundle(ButtonBundle {
+                        style: Style {
+                            flex_shrink: 0.,
+                            size: Size::new(Val::Percent(10.), Val::Px(20.)),
+                            margin: Rect {
+                                left: Val::Auto,
+                                right: Val::Auto,
+                                ..Default::default()
+                            },
+                            justify_content: JustifyContent::Center,
+                            ..Default::default()
+                        },
+                        color: button_materials.button_normal.into(),
+                        ..Default::default()
+                    })
+                    .insert(action)
+                    .insert(SimpleButton)
+                    .with_children(|parent| {
+                        parent.spawn_bundle(TextBundle {
+                            style: Style {
+                                flex_shrink: 0.,
+                                size: Size::new(Val::Undefined, Val::Px(20.)),
+                                margin: Rect {
+                                    left: Val::Auto,
+                                    right: Val::Auto,
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            text: Text::with_section(
+                                text,
+                                TextStyle {
+                                    font: font.clone(),
+                                    font_size: 30.,
+                                    color: Color::WHITE,
+                                },
+                                Default::default(),
+                            ),
+                            ..Default::default()
+                        });
+                    });
+            }
+
+            builder
+                .spawn_bundle(TextBundle {
+                    style: Style {
+                        size: Size::new(Val::Undefined, Val::Px(20.)),
+                        margin: Rect {
+                            left: Val::Auto,
+                            right: Val::Auto,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    text: Text::with_section(
+                        status.to_owned(),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 30.,
+                            color: Color::WHITE,
+                        },
+                        Default::default(),
+                    ),
+                    ..Default::default()
+                })
+                .insert(NetworkStatusLabel);
+        });
+}
@@ -0,0 +1,30 @@
+use bevy::{log, prelude::*};
+
+use crate::resources::theme_options::ThemeOptions;
+
+use super::set_button_text;
+
+#[derive(Component)]
+pub(super) struct PaletteButton;
+
+#[inline]
+pub(super) fn palette_button_text(theme_options: &ThemeOptions) -> String {
+    format!(
+        "COLORS: {}",
+        theme_options.palette.to_string().to_uppercase()
+    )
+}
+
+pub(super) fn palette_button_press_system(
+    buttons: Query<(&Interaction, &Children), (Changed<Interaction>, With<PaletteButton>)>,
+    mut theme_options: ResMut<ThemeOptions>,
+    mut texts: Query<&mut Text>,
+) {
+    for (interaction, children) in buttons.iter() {
+        if *interaction == Interaction::Clicked {
+            theme_options.palette.next();
+            log::info!("Palette: {}", theme_options.palette.to_string());
+            set_button_text(children, &mut texts, &palette_button_text(&theme_options));
+        }
+    }
+}
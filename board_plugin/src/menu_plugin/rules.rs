@@ -0,0 +1,120 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    resources::{board_assets::BoardAssets, draw_rules::DrawRules},
+    rules::rule_set::RuleSetType,
+};
+
+use super::{
+    selector::{label_text, setup_selector},
+    set_button_text, MenuMaterials,
+};
+
+#[derive(Component)]
+pub(super) struct RulesButton;
+
+#[derive(Debug, Component)]
+pub(super) enum DrawRulesButtonAction {
+    PreviousRepetitions,
+    NextRepetitions,
+    PreviousMoveLimit,
+    NextMoveLimit,
+}
+
+#[derive(Default, Component)]
+pub(super) struct RepetitionsLabel;
+
+#[derive(Default, Component)]
+pub(super) struct MoveLimitLabel;
+
+#[inline]
+pub(super) fn rules_button_text(rule_set_type: &RuleSetType) -> &'static str {
+    match rule_set_type {
+        RuleSetType::Standard => "RULES: STANDARD",
+        RuleSetType::WayOfTheStream => "RULES: STREAM",
+    }
+}
+
+#[inline]
+fn limit_label<T: ToString>(limit: Option<T>) -> String {
+    match limit {
+        Some(limit) => limit.to_string(),
+        None => "Off".to_owned(),
+    }
+}
+
+pub(super) fn rules_button_press_system(
+    buttons: Query<(&Interaction, &Children), (Changed<Interaction>, With<RulesButton>)>,
+    mut rule_set_type: ResMut<RuleSetType>,
+    mut texts: Query<&mut Text>,
+) {
+    for (interaction, children) in buttons.iter() {
+        if *interaction == Interaction::Clicked {
+            *rule_set_type = match *rule_set_type {
+                RuleSetType::Standard => RuleSetType::WayOfTheStream,
+                RuleSetType::WayOfTheStream => RuleSetType::Standard,
+            };
+            log::info!("Rules: {}", rule_set_type.to_string());
+            set_button_text(children, &mut texts, rules_button_text(&rule_set_type));
+        }
+    }
+}
+
+pub(super) fn draw_rules_button_press_system(
+    buttons: Query<(&Interaction, &DrawRulesButtonAction), (Changed<Interaction>, With<Button>)>,
+    board_assets: Res<BoardAssets>,
+    mut draw_rules: ResMut<DrawRules>,
+    mut repetitions_labels: Query<&mut Text, (With<RepetitionsLabel>, Without<MoveLimitLabel>)>,
+    mut move_limit_labels: Query<&mut Text, (With<MoveLimitLabel>, Without<RepetitionsLabel>)>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction == Interaction::Clicked {
+            match button {
+                DrawRulesButtonAction::PreviousRepetitions => draw_rules.prev_repetitions(),
+                DrawRulesButtonAction::NextRepetitions => draw_rules.next_repetitions(),
+                DrawRulesButtonAction::PreviousMoveLimit => draw_rules.prev_move_limit(),
+                DrawRulesButtonAction::NextMoveLimit => draw_rules.next_move_limit(),
+            };
+            log::info!("Changed the draw rules to {:?}", draw_rules);
+
+            for mut text in repetitions_labels.iter_mut() {
+                *text = label_text(limit_label(draw_rules.repetitions), &board_assets);
+            }
+            for mut text in move_limit_labels.iter_mut() {
+                *text = label_text(limit_label(draw_rules.move_limit), &board_assets);
+            }
+        }
+    }
+}
+
+pub(super) fn setup_draw_rules_selectors(
+    draw_rules: &DrawRules,
+    button_materials: &MenuMaterials,
+    parent: &mut ChildBuilder,
+    font: Handle<Font>,
+) {
+    setup_selector::<RepetitionsLabel, _>(
+        parent,
+        button_materials,
+        font.clone(),
+        "Repetitions: ",
+        50.,
+        limit_label(draw_rules.repetitions),
+        [
+            DrawRulesButtonAction::PreviousRepetitions,
+            DrawRulesButtonAction::NextRepetitions,
+        ],
+    );
+    setup_selector::<MoveLimitLabel, _>(
+        parent,
+        button_materials,
+        font,
+        "Move limit: ",
+        40.,
+        limit_label(draw_rules.move_limit),
+        [
+            DrawRulesButtonAction::PreviousMoveLimit,
+            DrawRulesButtonAction::NextMoveLimit,
+        ],
+    );
+}
@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+
+use crate::resources::board_assets::BoardAssets;
+
+use super::{MenuMaterials, SimpleButton};
+
+/// Spawns an option row with a title and an `L` value label between the "<" and ">" buttons
+/// with the `actions`. `width` is the part of the row in percents, so two selectors fit in a row
+pub(super) fn setup_selector<L: Component + Default, A: Component>(
+    parent: &mut ChildBuilder,
+    button_materials: &MenuMaterials,
+    font: Handle<Font>,
+    title: &str,
+    width: f32,
+    value: String,
+    actions: [A; 2],
+) {
+    let [previous, next] = actions;
+    parent
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(width), Val::Auto),
+                margin: Rect::all(Val::Px(10.)),
+                // horizontally center child text
+                justify_content: JustifyContent::FlexStart,
+                // vertically center child text
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .insert(Name::new(format!(
+            "{} selector",
+            title.trim_end_matches(": ")
+        )))
+        .with_children(|builder| {
+            builder.spawn_bundle(TextBundle {
+                text: Text {
+                    sections: vec![TextSection {
+                        value: title.to_owned(),
+                        style: TextStyle {
+                            font: font.clone(),
+                            font_size: 40.,
+                            color: Color::WHITE,
+                        },
+                    }],
+                    alignment: TextAlignment {
+                        vertical: VerticalAlign::Center,
+                        horizontal: HorizontalAlign::Center,
+                    },
+                },
+                ..Default::default()
+            });
+
+            setup_arrow_button(builder, button_materials, font.clone(), "<", previous);
+
+            builder
+                .spawn_bundle(TextBundle {
+                    style: Style {
+                        size: Size::new(Val::Undefined, Val::Px(20.)),
+                        margin: Rect {
+                            left: Val::Auto,
+                            right: Val::Auto,
+                            ..Default::default()
+                        },
+                        justify_content: JustifyContent::Center,
+                        ..Default::default()
+                    },
+                    text: Text::with_section(
+                        value,
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 30.,
+                            color: Color::WHITE,
+                        },
+                        Default::default(),
+                    ),
+                    ..Default::default()
+                })
+                .insert(L::default());
+
+            setup_arrow_button(builder, button_materials, font, ">", next);
+        });
+}
+
+fn setup_arrow_button(
+    builder: &mut ChildBuilder,
+    button_materials: &MenuMaterials,
+    font: Handle<Font>,
+    text: &str,
+    action: impl Component,
+) {
+    builder
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                flex_shrink: 0.,
+                size: Size::new(Val::Percent(10.), Val::Px(20.)),
+                margin: Rect {
+                    left: Val::Auto,
+                    right: Val::Auto,
+                    ..Default::default()
+                },
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            color: button_materials.button_normal.into(),
+            ..Default::default()
+        })
+        .insert(action)
+        .insert(SimpleButton)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                style: Style {
+                    flex_shrink: 0.,
+                    size: Size::new(Val::Undefined, Val::Px(20.)),
+                    margin: Rect {
+                        left: Val::Auto,
+                        right: Val::Auto,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                text: Text::with_section(
+                    text,
+                    TextStyle {
+                        font,
+                        font_size: 30.,
+                        color: Color::WHITE,
+                    },
+                    Default::default(),
+                ),
+                ..Default::default()
+            });
+        });
+}
+
+/// Text of a value label of a selector
+pub(super) fn label_text(value: String, board_assets: &BoardAssets) -> Text {
+    Text::with_section(
+        value,
+        TextStyle {
+            font: board_assets.font.clone(),
+            font_size: 30.,
+            color: Color::WHITE,
+        },
+        Default::default(),
+    )
+}
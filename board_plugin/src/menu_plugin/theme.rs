@@ -0,0 +1,27 @@
+use bevy::{log, prelude::*};
+
+use crate::resources::theme_options::ThemeOptions;
+
+use super::set_button_text;
+
+#[derive(Component)]
+pub(super) struct ThemeButton;
+
+#[inline]
+pub(super) fn theme_button_text(theme_options: &ThemeOptions) -> String {
+    format!("THEME: {}", theme_options.selected_theme().0.to_uppercase())
+}
+
+pub(super) fn theme_button_press_system(
+    buttons: Query<(&Interaction, &Children), (Changed<Interaction>, With<ThemeButton>)>,
+    mut theme_options: ResMut<ThemeOptions>,
+    mut texts: Query<&mut Text>,
+) {
+    for (interaction, children) in buttons.iter() {
+        if *interaction == Interaction::Clicked {
+            theme_options.next();
+            log::info!("Theme: {}", theme_options.selected_theme().0);
+            set_button_text(children, &mut texts, &theme_button_text(&theme_options));
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    io::{self, ErrorKind, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use super::{protocol::Message, websocket::WebSocketConnection};
+
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug)]
+pub enum NetworkError {
+    Io(io::Error),
+    /// The peer has sent a message which does not follow the protocol
+    Protocol(String),
+    /// The peer has said goodbye
+    Left,
+    Closed,
+}
+
+impl Display for NetworkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkError::Io(e) => write!(f, "IO error: {}", e),
+            NetworkError::Protocol(e) => write!(f, "Protocol error: {}", e),
+            NetworkError::Left => write!(f, "Opponent has left"),
+            NetworkError::Closed => write!(f, "Connection is closed"),
+        }
+    }
+}
+
+impl From<io::Error> for NetworkError {
+    fn from(e: io::Error) -> Self {
+        NetworkError::Io(e)
+    }
+}
+
+/// Connection to the peer: directly over TCP or through the game server over WebSockets
+#[derive(Debug)]
+pub enum Connection {
+    Tcp(TcpConnection),
+    WebSocket(WebSocketConnection),
+}
+
+impl Connection {
+    pub fn send(&mut self, message: &Message) -> Result<(), NetworkError> {
+        match self {
+            Connection::Tcp(connection) => connection.send(message),
+            Connection::WebSocket(connection) => connection.send(message),
+        }
+    }
+
+    /// Returns all messages which were fully received since the last call
+    pub fn receive(&mut self) -> Result<Vec<Message>, NetworkError> {
+        match self {
+            Connection::Tcp(connection) => connection.receive(),
+            Connection::WebSocket(connection) => connection.receive(),
+        }
+    }
+}
+
+/// Non-blocking TCP connection which sends and receives the protocol messages line by line
+#[derive(Debug)]
+pub struct TcpConnection {
+    stream: TcpStream,
+    // received bytes of a line which is not finished yet
+    buffer: Vec<u8>,
+}
+
+impl TcpConnection {
+    pub fn new(stream: TcpStream) -> Result<Self, NetworkError> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        Ok(Self {
+            stream,
+            buffer: Vec::with_capacity(128),
+        })
+    }
+
+    pub fn connect(address: &str) -> Result<Self, NetworkError> {
+        let address = address
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| NetworkError::Protocol(format!("Bad address: {}", address)))?;
+        let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+        Self::new(stream)
+    }
+
+    pub fn send(&mut self, message: &Message) -> Result<(), NetworkError> {
+        let line = format!("{}\n", message.to_string());
+        // messages are tiny, so the socket buffer is never full and the write does not block
+        self.stream.write_all(line.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn receive(&mut self) -> Result<Vec<Message>, NetworkError> {
+        let mut chunk = [0u8; 512];
+        loop {
+            match self.stream.read(&mut chunk) {
+                Ok(0) => return Err(NetworkError::Closed),
+                Ok(n) => self.buffer.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let mut messages = vec![];
+        while let Some(end) = self.buffer.iter().position(|b| *b == b'\n') {
+            let line = self.buffer.drain(..=end).collect::<Vec<_>>();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            messages.push(Message::parse(line).map_err(NetworkError::Protocol)?);
+        }
+        Ok(messages)
+    }
+}
+
+/// Non-blocking listener which waits for the guest to connect
+#[derive(Debug)]
+pub struct Host {
+    listener: TcpListener,
+}
+
+impl Host {
+    pub fn bind(address: &str) -> Result<Self, NetworkError> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self { listener })
+    }
+
+    /// Address the guests connect to, the port is known here when it was bound as 0
+    pub fn local_addr(&self) -> Result<SocketAddr, NetworkError> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Returns a connection if somebody has connected
+    pub fn accept(&self) -> Result<Option<TcpConnection>, NetworkError> {
+        match self.listener.accept() {
+            Ok((stream, address)) => {
+                bevy::log::info!("Accepted a connection from {}", address);
+                Ok(Some(TcpConnection::new(stream)?))
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Instant};
+
+    use super::*;
+
+    #[test]
+    fn receives_lines_split_between_reads() {
+        let host = Host::bind("127.0.0.1:0").unwrap();
+        let mut peer = TcpStream::connect(host.local_addr().unwrap()).unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut connection = loop {
+            if let Some(connection) = host.accept().unwrap() {
+                break connection;
+            }
+            assert!(Instant::now() < deadline, "the peer has not connected");
+            thread::sleep(Duration::from_millis(10));
+        };
+
+        let mut received = vec![];
+        let mut receive_until = |count: usize, received: &mut Vec<Message>| {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while received.len() < count {
+                received.extend(connection.receive().unwrap());
+                assert!(Instant::now() < deadline, "the lines have not arrived");
+                thread::sleep(Duration::from_millis(10));
+            }
+        };
+
+        peer.write_all(b"HELLO 1\n\nMOVE Crab").unwrap();
+        receive_until(1, &mut received);
+        peer.write_all(b" 1 4 1 3\nBYE\n").unwrap();
+        receive_until(3, &mut received);
+
+        assert_eq!(
+            received.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            ["HELLO 1", "MOVE Crab 1 4 1 3", "BYE"]
+        );
+    }
+}
@@ -0,0 +1,443 @@
+pub mod connection;
+pub mod protocol;
+pub mod session;
+pub mod websocket;
+
+use bevy::{log, prelude::*};
+
+use crate::{
+    ai::evaluation::Evaluation,
+    events::{
+        BotMakeMoveEvent, ChangeGuideTextEvent, DrawOfferEvent, MoveMadeEvent,
+        ProcessWinConditionEvent,
+    },
+    resources::{
+        app_state::AppState,
+        board::Board,
+        deck::Deck,
+        draw_rules::DrawRules,
+        game_state::{GameState, PlayerColor, PlayerType},
+        handicap::Handicap,
+        pending_turn::PendingTurn,
+        physical_deck::PhysicalDeck,
+        selected::SelectedPlayers,
+        text_handler::EvaluationResult,
+    },
+    rules::rule_set::RuleSetType,
+};
+
+use self::{
+    connection::NetworkError,
+    protocol::Message,
+    session::{NetworkRole, NetworkSession},
+};
+
+const MAX_RECONNECT_ATTEMPTS: u8 = 5;
+
+/// Plays the games against the remote player over TCP or through the game server.
+/// See `protocol` for the message format
+pub struct NetworkPlugin;
+
+impl Plugin for NetworkPlugin {
+    fn build(&self, app: &mut App) {
+        log::info!("Loaded network plugin!");
+        app.add_system_set(
+            // the options changed for the started game are not saved in the menu settings
+            SystemSet::on_update(AppState::MainMenu)
+                .with_system(receive_game_start.after("save_menu_settings")),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::InProgress)
+                .with_system(send_local_moves)
+                .with_system(
+                    send_local_decisions
+                        .after("game_buttons")
+                        .after("process_draw_offer")
+                        .before("process_win_condition"),
+                )
+                .with_system(receive_remote_messages)
+                .with_system(reconnect_session)
+                .with_system(evaluate_watched_position),
+        )
+        .add_system_set(SystemSet::on_exit(AppState::GameEnd).with_system(close_session));
+    }
+}
+
+/// Sets the state to the game end if it is not set yet by other systems
+fn end_game(app_state: &mut State<AppState>) {
+    if let Err(e) = app_state.set(AppState::GameEnd) {
+        log::info!("Game is already ending: {:?}", e);
+    }
+}
+
+/// Guest waits in the main menu for the host to start the game
+fn receive_game_start(
+    session: Option<ResMut<NetworkSession>>,
+    mut selected_players: ResMut<SelectedPlayers>,
+    mut physical_deck: ResMut<PhysicalDeck>,
+    mut rule_set_type: ResMut<RuleSetType>,
+    mut handicap: ResMut<Handicap>,
+    mut draw_rules: ResMut<DrawRules>,
+    mut state: ResMut<State<AppState>>,
+) {
+    let mut session = match session {
+        Some(session) if session.is_connected() => session,
+        _ => return,
+    };
+
+    let messages = match session.poll() {
+        Ok(messages) => messages,
+        Err(e) => {
+            log::warn!("Network session has ended: {}", e);
+            session.close();
+            session.status = format!("Disconnected: {}", e);
+            return;
+        }
+    };
+
+    let mut messages = messages.into_iter();
+    while let Some(message) = messages.next() {
+        match message {
+            Message::Start { rules, cards, .. } if session.role == NetworkRole::Spectator => {
+                log::info!("Watching the game");
+                selected_players.set(PlayerColor::Red, PlayerType::Remote);
+                selected_players.set(PlayerColor::Blue, PlayerType::Remote);
+
+                *rule_set_type = rules;
+                *handicap = Handicap::None;
+                *draw_rules = DrawRules::default();
+                physical_deck.clear();
+                physical_deck.take_cards_in_deck_order(&cards);
+
+                // the moves played before the spectator has come are handled in the game
+                session.unread(messages.collect());
+                state.set(AppState::InProgress).unwrap();
+                return;
+            }
+            Message::Start {
+                color,
+                rules,
+                cards,
+            } => {
+                log::info!("Host has started the game. We play {:?}", color);
+                let local_type = match selected_players.get(&color) {
+                    PlayerType::Remote => PlayerType::Human,
+                    player_type => player_type,
+                };
+                selected_players.set(color, local_type);
+                selected_players.set(color.enemy(), PlayerType::Remote);
+
+                *rule_set_type = rules;
+                *handicap = Handicap::None;
+                *draw_rules = DrawRules::default();
+                physical_deck.clear();
+                physical_deck.take_cards_in_deck_order(&cards);
+
+                session.local_color = Some(color);
+                session.unread(messages.collect());
+                state.set(AppState::InProgress).unwrap();
+                return;
+            }
+            message => log::info!("Ignoring a message in the menu: {:?}", message),
+        }
+    }
+}
+
+fn send_local_moves(
+    session: Option<ResMut<NetworkSession>>,
+    game_state: Res<GameState>,
+    deck: Res<Deck>,
+    mut move_made_rdr: EventReader<MoveMadeEvent>,
+) {
+    let mut session = match session {
+        Some(session) if session.is_connected() => session,
+        _ => return,
+    };
+
+    for event in move_made_rdr.iter() {
+        // remote moves came from the network, they are not sent back
+        if game_state.get_player(&event.color).player_type == PlayerType::Remote {
+            continue;
+        }
+
+        let card = deck.cardboards.get(&event.card_used).unwrap().card.name;
+        if let Err(e) = session.send(&Message::Move {
+            card,
+            mov: event.mov,
+        }) {
+            log::warn!("Could not send the move: {}", e);
+        }
+    }
+}
+
+/// Sends the resignation and the draw offers and answers of the local player
+fn send_local_decisions(
+    session: Option<ResMut<NetworkSession>>,
+    game_state: Res<GameState>,
+    mut draw_offer_rdr: EventReader<DrawOfferEvent>,
+    mut process_win_condition_rdr: EventReader<ProcessWinConditionEvent>,
+) {
+    let mut session = match session {
+        Some(session) if session.is_connected() => session,
+        _ => return,
+    };
+    let is_local =
+        |color: &PlayerColor| game_state.get_player(color).player_type != PlayerType::Remote;
+
+    let mut messages = vec![];
+    for event in draw_offer_rdr.iter() {
+        match *event {
+            DrawOfferEvent::Offer(color) if is_local(&color) => messages.push(Message::DrawOffer),
+            DrawOfferEvent::Accept(color) if is_local(&color) => messages.push(Message::DrawAccept),
+            DrawOfferEvent::Decline(color) if is_local(&color) => {
+                messages.push(Message::DrawDecline)
+            }
+            _ => {}
+        }
+    }
+    for event in process_win_condition_rdr.iter() {
+        match event {
+            ProcessWinConditionEvent::Resign(color) if is_local(color) => {
+                messages.push(Message::Resign)
+            }
+            // in a watched game both sides are local, the spectators still need to know the draw
+            ProcessWinConditionEvent::DrawAgreed
+                if is_local(&PlayerColor::Red) && is_local(&PlayerColor::Blue) =>
+            {
+                messages.push(Message::DrawAccept)
+            }
+            _ => {}
+        }
+    }
+
+    for message in messages {
+        if let Err(e) = session.send(&message) {
+            log::warn!("Could not send {:?}: {}", message, e);
+        }
+    }
+}
+
+fn receive_remote_messages(
+    session: Option<ResMut<NetworkSession>>,
+    board: Res<Board>,
+    game_state: Res<GameState>,
+    deck: Res<Deck>,
+    pending_turn: Option<Res<PendingTurn>>,
+    mut app_state: ResMut<State<AppState>>,
+    mut bot_make_move_ewr: EventWriter<BotMakeMoveEvent>,
+    mut draw_offer_ewr: EventWriter<DrawOfferEvent>,
+    mut process_win_condition_ewr: EventWriter<ProcessWinConditionEvent>,
+    mut change_guide_text_ewr: EventWriter<ChangeGuideTextEvent>,
+) {
+    let mut session = match session {
+        Some(session) if session.is_connected() => session,
+        _ => return,
+    };
+
+    // the previous remote move is applied by the board systems over several frames,
+    // the moves are not read until the last one has been animated and the turn is switched
+    if session.applying_color == Some(game_state.curr_color) || pending_turn.is_some() {
+        return;
+    }
+    session.applying_color = None;
+
+    let messages = match session.poll() {
+        Ok(messages) => messages,
+        Err(e) if session.can_reconnect(&e) => {
+            log::warn!("Connection to the game server is lost: {}", e);
+            session.lose_connection();
+            change_guide_text_ewr.send(ChangeGuideTextEvent {
+                text: "Connection lost. Reconnecting...".to_owned(),
+            });
+            return;
+        }
+        Err(e) => {
+            log::warn!("Network session has ended: {}", e);
+            session.close();
+            let text = match (e, session.role) {
+                (NetworkError::Left, NetworkRole::Spectator) => "Players have left",
+                (NetworkError::Left, _) => "Opponent has left",
+                _ => "Opponent has disconnected",
+            };
+            change_guide_text_ewr.send(ChangeGuideTextEvent {
+                text: text.to_owned(),
+            });
+            end_game(&mut app_state);
+            return;
+        }
+    };
+
+    // spectators receive the moves of both sides
+    let remote_color = match (session.role, session.local_color) {
+        (NetworkRole::Spectator, _) => game_state.curr_color,
+        (_, Some(color)) => color.enemy(),
+        (_, None) => return,
+    };
+
+    let mut messages = messages.into_iter();
+    while let Some(message) = messages.next() {
+        match message {
+            Message::Move { card, mov } => {
+                if game_state.curr_color != remote_color {
+                    log::warn!("Opponent has moved out of turn");
+                    continue;
+                }
+
+                // validating the move with the same rules as the local moves
+                let card_entity = deck
+                    .get_player_cards(&game_state)
+                    .into_iter()
+                    .find(|(_, c)| c.name == card)
+                    .filter(|(_, c)| {
+                        board
+                            .tile_map
+                            .generate_possible_moves_for_card(
+                                &game_state.curr_color,
+                                c,
+                                game_state.rules.as_ref(),
+                            )
+                            .contains(&mov)
+                    })
+                    .map(|(entity, _)| entity);
+
+                match card_entity {
+                    Some(card_used) => {
+                        bot_make_move_ewr.send(BotMakeMoveEvent { mov, card_used });
+                        // one move per turn, the rest waits until this one is applied
+                        session.applying_color = Some(remote_color);
+                        session.unread(messages.collect());
+                        return;
+                    }
+                    None if session.role == NetworkRole::Spectator => {
+                        log::warn!("Watched move {} {:?} is not allowed here", card, mov);
+                        session.close();
+                        change_guide_text_ewr.send(ChangeGuideTextEvent {
+                            text: "Watched game is out of sync".to_owned(),
+                        });
+                        end_game(&mut app_state);
+                        return;
+                    }
+                    None => {
+                        let reason = format!("Illegal move {} {:?}", card, mov);
+                        log::warn!("{}", reason);
+                        if let Err(e) = session.send(&Message::Error { reason }) {
+                            log::warn!("Could not send an error: {}", e);
+                        }
+                        session.close();
+                        change_guide_text_ewr.send(ChangeGuideTextEvent {
+                            text: "Opponent made an illegal move".to_owned(),
+                        });
+                        end_game(&mut app_state);
+                        return;
+                    }
+                }
+            }
+            Message::Resign => {
+                process_win_condition_ewr.send(ProcessWinConditionEvent::Resign(remote_color));
+                return;
+            }
+            // spectators do not see the offers, only the accepted draws
+            Message::DrawAccept if session.role == NetworkRole::Spectator => {
+                process_win_condition_ewr.send(ProcessWinConditionEvent::DrawAgreed);
+                return;
+            }
+            Message::DrawOffer => draw_offer_ewr.send(DrawOfferEvent::Offer(remote_color)),
+            Message::DrawAccept => draw_offer_ewr.send(DrawOfferEvent::Accept(remote_color)),
+            Message::DrawDecline => draw_offer_ewr.send(DrawOfferEvent::Decline(remote_color)),
+            Message::Error { reason } => {
+                log::warn!("Opponent has rejected the message: {}", reason);
+                session.close();
+                change_guide_text_ewr.send(ChangeGuideTextEvent {
+                    text: format!("Network error: {}", reason),
+                });
+                end_game(&mut app_state);
+                return;
+            }
+            message => log::info!("Ignoring a message in the game: {:?}", message),
+        }
+    }
+}
+
+/// Spectators have no bot which evaluates the position, so it is done after every move
+fn evaluate_watched_position(
+    session: Option<Res<NetworkSession>>,
+    board: Res<Board>,
+    game_state: Res<GameState>,
+    mut evaluation_result: ResMut<EvaluationResult>,
+) {
+    match session {
+        Some(session) if session.role == NetworkRole::Spectator => {}
+        _ => return,
+    }
+
+    if board.is_changed() {
+        evaluation_result.score =
+            Evaluation::evaluate(&board.tile_map, &game_state.curr_color, &None);
+    }
+}
+
+/// Tries to get back to the room on the game server after the connection was lost
+fn reconnect_session(
+    time: Res<Time>,
+    session: Option<ResMut<NetworkSession>>,
+    mut app_state: ResMut<State<AppState>>,
+    mut change_guide_text_ewr: EventWriter<ChangeGuideTextEvent>,
+) {
+    let mut session = match session {
+        Some(session) if session.is_reconnecting() => session,
+        _ => return,
+    };
+
+    // the connection is opened on another thread, so the game goes on meanwhile
+    if session.is_connecting() {
+        match session.finish_reconnect() {
+            Ok(false) => {}
+            Ok(true) => {
+                log::info!("Reconnected to the game server");
+                change_guide_text_ewr.send(ChangeGuideTextEvent {
+                    text: "Reconnected".to_owned(),
+                });
+            }
+            Err(e) => log::warn!("Could not reconnect: {}", e),
+        }
+        return;
+    }
+
+    session.reconnect_timer.tick(time.delta());
+    if !session.reconnect_timer.finished() {
+        return;
+    }
+
+    if session.reconnect_attempts >= Some(MAX_RECONNECT_ATTEMPTS) {
+        log::warn!("Could not get back to the room");
+        session.close();
+        change_guide_text_ewr.send(ChangeGuideTextEvent {
+            text: "Connection to the server is lost".to_owned(),
+        });
+        end_game(&mut app_state);
+        return;
+    }
+
+    if let Err(e) = session.reconnect() {
+        log::warn!("Could not reconnect: {}", e);
+    }
+}
+
+/// Network session lasts one game
+fn close_session(
+    mut commands: Commands,
+    session: Option<ResMut<NetworkSession>>,
+    mut selected_players: ResMut<SelectedPlayers>,
+) {
+    if let Some(mut session) = session {
+        session.close();
+        commands.remove_resource::<NetworkSession>();
+    }
+
+    if selected_players.red_player == PlayerType::Remote {
+        selected_players.red_player = PlayerType::Human;
+    }
+    if selected_players.blue_player == PlayerType::Remote {
+        selected_players.blue_player = PlayerType::Human;
+    }
+}
@@ -0,0 +1,405 @@
+//! Line based protocol of the network games.
+//!
+//! Every message is a single line of UTF-8 text terminated with `\n`.
+//! Words are separated with spaces, cards are sent by their names and
+//! coordinates are board coordinates from 0 to 4, where `(0, 0)` is the red side corner.
+//!
+//! | Message                                        | Sender | Meaning                                             |
+//! |------------------------------------------------|--------|-----------------------------------------------------|
+//! | `HELLO <version>`                              | both   | Handshake. Sent right after the connection is made  |
+//! | `START <colour> <rules> <c1> <c2> <c3> <c4> <c5>` | host | Starts the game. `colour` is the side of the guest |
+//! | `MOVE <card> <from x> <from y> <to x> <to y>`  | both   | Move made with the card from the player's hand      |
+//! | `RESIGN`                                       | both   | The sender resigns                                  |
+//! | `DRAW_OFFER`                                   | both   | The sender offers a draw                            |
+//! | `DRAW_ACCEPT`                                  | both   | The sender accepts the offered draw                 |
+//! | `DRAW_DECLINE`                                 | both   | The sender declines the offered draw                |
+//! | `ERROR <reason>`                               | both   | The sender has rejected the last message            |
+//! | `BYE`                                          | both   | The sender closes the connection                    |
+//! | `CREATE`                                       | host   | Asks the game server for a new room                 |
+//! | `JOIN <room>`                                  | guest  | Takes the free seat in the room on the game server  |
+//! | `REJOIN <room> <token> <moves>`                | both   | Returns to the seat after a dropped connection      |
+//! | `ROOM <room> <token>`                          | server | The seat is taken. The token is kept for `REJOIN`   |
+//! | `WATCH <room> <moves>`                         | viewer | Watches the game in the room without a seat         |
+//!
+//! `colour` is `red` or `blue`, `rules` is `standard` or `stream`.
+//! Cards in `START` are five different cards listed in the deck order: two blue cards, the neutral card
+//! and two red cards.
+//! The stamp of the neutral card tells who moves first, Monkey in the session below is blue.
+//!
+//! A typical session:
+//! ```text
+//! host  -> HELLO 1
+//! guest -> HELLO 1
+//! host  -> START blue standard Tiger Crab Monkey Crane Dragon
+//! blue  -> MOVE Crab 1 4 1 3
+//! red   -> MOVE Crane 1 0 1 1
+//! ...
+//! ```
+//! Both sides validate every received move with the rules engine and
+//! reply with `ERROR` to a move that is not allowed.
+//! A draw offer stays open until the opponent answers it or makes a move,
+//! the move declines the offer without `DRAW_DECLINE`.
+//!
+//! Through the game server (`server/` in the workspace) the messages are
+//! carried by WebSocket text frames. The server sends `HELLO` to both seats
+//! once the room is full, checks every `MOVE` itself and relays the valid ones.
+//! `moves` in `REJOIN` is the number of moves the client knows about,
+//! the server answers with `ROOM` and resends the moves played after them.
+//!
+//! Spectators send `WATCH` with the number of moves they know about. The server
+//! answers with `ROOM <room> -`, `HELLO`, `START` and the moves played after them,
+//! and then relays every `MOVE`, `RESIGN`, `DRAW_ACCEPT` and `BYE` of the players.
+//! When the host starts a game in a room without a guest, the host plays both sides
+//! and the game is only watched.
+
+use crate::{
+    components::coordinates::Coordinates,
+    resources::{card::CARDS, game_state::PlayerColor, tile_map::Move},
+    rules::rule_set::RuleSetType,
+};
+
+pub const PROTOCOL_VERSION: u16 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    Hello {
+        version: u16,
+    },
+    Start {
+        /// Side of the guest
+        color: PlayerColor,
+        rules: RuleSetType,
+        /// Card indices in the deck order
+        cards: [u8; 5],
+    },
+    Move {
+        card: &'static str,
+        mov: Move,
+    },
+    Resign,
+    DrawOffer,
+    DrawAccept,
+    DrawDecline,
+    Error {
+        reason: String,
+    },
+    Bye,
+    Create,
+    Join {
+        room: String,
+    },
+    Rejoin {
+        room: String,
+        token: String,
+        moves: u16,
+    },
+    Room {
+        room: String,
+        token: String,
+    },
+    Watch {
+        room: String,
+        moves: u16,
+    },
+}
+
+impl Message {
+    /// Parses a single line without the line ending
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut words = line.split_whitespace();
+        let command = words.next().ok_or_else(|| "Empty message".to_owned())?;
+
+        let message = match command {
+            "HELLO" => Message::Hello {
+                version: parse_number(words.next())?,
+            },
+            "START" => {
+                let color = parse_color(words.next())?;
+                let rules = parse_rules(words.next())?;
+                let mut cards = [0; 5];
+                for idx in 0..cards.len() {
+                    let card = parse_card(words.next())?;
+                    if cards[..idx].contains(&card) {
+                        return Err(format!("Card {} is sent twice", CARDS[card as usize].name));
+                    }
+                    cards[idx] = card;
+                }
+                Message::Start {
+                    color,
+                    rules,
+                    cards,
+                }
+            }
+            "MOVE" => {
+                let card = CARDS[parse_card(words.next())? as usize].name;
+                let from = Coordinates {
+                    x: parse_number(words.next())?,
+                    y: parse_number(words.next())?,
+                };
+                let to = Coordinates {
+                    x: parse_number(words.next())?,
+                    y: parse_number(words.next())?,
+                };
+                Message::Move {
+                    card,
+                    mov: Move { from, to },
+                }
+            }
+            "RESIGN" => Message::Resign,
+            "DRAW_OFFER" => Message::DrawOffer,
+            "DRAW_ACCEPT" => Message::DrawAccept,
+            "DRAW_DECLINE" => Message::DrawDecline,
+            "ERROR" => Message::Error {
+                reason: words.collect::<Vec<_>>().join(" "),
+            },
+            "BYE" => Message::Bye,
+            "CREATE" => Message::Create,
+            "JOIN" => Message::Join {
+                room: parse_word(words.next())?,
+            },
+            "REJOIN" => Message::Rejoin {
+                room: parse_word(words.next())?,
+                token: parse_word(words.next())?,
+                moves: parse_number(words.next())?,
+            },
+            "ROOM" => Message::Room {
+                room: parse_word(words.next())?,
+                token: parse_word(words.next())?,
+            },
+            "WATCH" => Message::Watch {
+                room: parse_word(words.next())?,
+                moves: parse_number(words.next())?,
+            },
+            _ => return Err(format!("Unknown message: {}", line)),
+        };
+
+        Ok(message)
+    }
+}
+
+impl ToString for Message {
+    fn to_string(&self) -> String {
+        match self {
+            Message::Hello { version } => format!("HELLO {}", version),
+            Message::Start {
+                color,
+                rules,
+                cards,
+            } => {
+                let cards = cards
+                    .iter()
+                    .map(|idx| CARDS[*idx as usize].name)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                format!(
+                    "START {} {} {}",
+                    color_token(color),
+                    rules_token(rules),
+                    cards
+                )
+            }
+            Message::Move { card, mov } => format!(
+                "MOVE {} {} {} {} {}",
+                card, mov.from.x, mov.from.y, mov.to.x, mov.to.y
+            ),
+            Message::Resign => "RESIGN".to_owned(),
+            Message::DrawOffer => "DRAW_OFFER".to_owned(),
+            Message::DrawAccept => "DRAW_ACCEPT".to_owned(),
+            Message::DrawDecline => "DRAW_DECLINE".to_owned(),
+            Message::Error { reason } => format!("ERROR {}", reason),
+            Message::Bye => "BYE".to_owned(),
+            Message::Create => "CREATE".to_owned(),
+            Message::Join { room } => format!("JOIN {}", room),
+            Message::Rejoin { room, token, moves } => {
+                format!("REJOIN {} {} {}", room, token, moves)
+            }
+            Message::Room { room, token } => format!("ROOM {} {}", room, token),
+            Message::Watch { room, moves } => format!("WATCH {} {}", room, moves),
+        }
+    }
+}
+
+#[inline]
+fn color_token(color: &PlayerColor) -> &'static str {
+    match color {
+        PlayerColor::Red => "red",
+        PlayerColor::Blue => "blue",
+    }
+}
+
+#[inline]
+fn rules_token(rules: &RuleSetType) -> &'static str {
+    match rules {
+        RuleSetType::Standard => "standard",
+        RuleSetType::WayOfTheStream => "stream",
+    }
+}
+
+fn parse_number<N: std::str::FromStr>(word: Option<&str>) -> Result<N, String> {
+    let word = word.ok_or_else(|| "Missing a number".to_owned())?;
+    word.parse::<N>()
+        .map_err(|_| format!("Not a number: {}", word))
+}
+
+fn parse_word(word: Option<&str>) -> Result<String, String> {
+    word.map(str::to_owned)
+        .ok_or_else(|| "Missing a word".to_owned())
+}
+
+fn parse_color(word: Option<&str>) -> Result<PlayerColor, String> {
+    match word {
+        Some("red") => Ok(PlayerColor::Red),
+        Some("blue") => Ok(PlayerColor::Blue),
+        _ => Err(format!("Unknown colour: {:?}", word)),
+    }
+}
+
+fn parse_rules(word: Option<&str>) -> Result<RuleSetType, String> {
+    match word {
+        Some("standard") => Ok(RuleSetType::Standard),
+        Some("stream") => Ok(RuleSetType::WayOfTheStream),
+        _ => Err(format!("Unknown rules: {:?}", word)),
+    }
+}
+
+/// Returns the index of the card in `CARDS`
+fn parse_card(word: Option<&str>) -> Result<u8, String> {
+    let word = word.ok_or_else(|| "Missing a card".to_owned())?;
+    CARDS
+        .iter()
+        .position(|card| card.name == word)
+        .map(|idx| idx as u8)
+        .ok_or_else(|| format!("Unknown card: {}", word))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(name: &str) -> u8 {
+        parse_card(Some(name)).unwrap()
+    }
+
+    fn mov(from: (u8, u8), to: (u8, u8)) -> Move {
+        Move {
+            from: Coordinates {
+                x: from.0,
+                y: from.1,
+            },
+            to: Coordinates { x: to.0, y: to.1 },
+        }
+    }
+
+    #[test]
+    fn messages_survive_a_round_trip() {
+        let messages = [
+            Message::Hello {
+                version: PROTOCOL_VERSION,
+            },
+            Message::Start {
+                color: PlayerColor::Blue,
+                rules: RuleSetType::WayOfTheStream,
+                cards: [
+                    card("Tiger"),
+                    card("Crab"),
+                    card("Monkey"),
+                    card("Crane"),
+                    card("Dragon"),
+                ],
+            },
+            Message::Move {
+                card: "Crab",
+                mov: mov((1, 4), (1, 3)),
+            },
+            Message::Resign,
+            Message::DrawOffer,
+            Message::DrawAccept,
+            Message::DrawDecline,
+            Message::Error {
+                reason: "Illegal move Crane 1 0 1 1".to_owned(),
+            },
+            Message::Bye,
+            Message::Create,
+            Message::Join {
+                room: "k3x9".to_owned(),
+            },
+            Message::Rejoin {
+                room: "k3x9".to_owned(),
+                token: "f00d".to_owned(),
+                moves: 12,
+            },
+            Message::Room {
+                room: "k3x9".to_owned(),
+                token: "-".to_owned(),
+            },
+            Message::Watch {
+                room: "k3x9".to_owned(),
+                moves: 0,
+            },
+        ];
+
+        for message in messages {
+            let line = message.to_string();
+            assert_eq!(Message::parse(&line), Ok(message), "{}", line);
+        }
+    }
+
+    #[test]
+    fn parses_the_sample_session() {
+        assert_eq!(
+            Message::parse("START blue standard Tiger Crab Monkey Crane Dragon"),
+            Ok(Message::Start {
+                color: PlayerColor::Blue,
+                rules: RuleSetType::Standard,
+                cards: [
+                    card("Tiger"),
+                    card("Crab"),
+                    card("Monkey"),
+                    card("Crane"),
+                    card("Dragon"),
+                ],
+            })
+        );
+        assert_eq!(
+            Message::parse("  MOVE   Crane 1 0 1 1 "),
+            Ok(Message::Move {
+                card: "Crane",
+                mov: mov((1, 0), (1, 1)),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_repeated_cards() {
+        assert!(Message::parse("START blue standard Tiger Crab Monkey Crane Tiger").is_err());
+        assert!(Message::parse("START red stream Crab Crab Crab Crab Crab").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_messages() {
+        let lines = [
+            "",
+            "   ",
+            "HI 1",
+            "hello 1",
+            "HELLO",
+            "HELLO one",
+            "HELLO -1",
+            "START green standard Tiger Crab Monkey Crane Dragon",
+            "START blue chess Tiger Crab Monkey Crane Dragon",
+            "START blue standard Tiger Crab Monkey Crane",
+            "START blue standard Tiger Crab Monkey Crane Unicorn",
+            "MOVE Unicorn 1 4 1 3",
+            "MOVE Crab 1 4 1",
+            "MOVE Crab 1 4 1 x",
+            "JOIN",
+            "REJOIN k3x9 f00d",
+            "REJOIN k3x9 f00d many",
+            "ROOM k3x9",
+            "WATCH k3x9",
+        ];
+        for line in lines {
+            assert!(Message::parse(line).is_err(), "{:?}", line);
+        }
+    }
+}
@@ -0,0 +1,435 @@
+use bevy::{core::Timer, log};
+
+use crate::resources::game_state::PlayerColor;
+
+use super::{
+    connection::{Connection, Host, NetworkError, TcpConnection},
+    protocol::{Message, PROTOCOL_VERSION},
+    websocket::{is_websocket_url, split_room, PendingConnection, WebSocketConnection},
+};
+
+// Seconds between the attempts to get back to the room on the game server
+const RECONNECT_INTERVAL: f32 = 2.;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkRole {
+    /// Waits for the guest, chooses the cards and the rules
+    Host,
+    Guest,
+    /// Watches the game in a room on the game server without playing
+    Spectator,
+}
+
+/// Seat in a room on the game server
+#[derive(Debug, Clone)]
+struct Room {
+    server: String,
+    id: String,
+    token: String,
+}
+
+/// Network game in the making or in progress. Must be used as a resource
+#[derive(Debug)]
+pub struct NetworkSession {
+    pub role: NetworkRole,
+    host: Option<Host>,
+    connection: Option<Connection>,
+    /// Url of the game server, when the game is played through it
+    server: Option<String>,
+    room: Option<Room>,
+    /// The peer has sent a valid handshake
+    pub is_ready: bool,
+    /// Side played on this machine. It is known after the game start
+    pub local_color: Option<PlayerColor>,
+    pub status: String,
+    /// Moves sent and received in this game
+    moves_played: u16,
+    /// Messages sent while the connection to the server was lost
+    outbox: Vec<Message>,
+    /// Received messages which are handled in the next frames
+    inbox: Vec<Message>,
+    /// Side whose remote move is being applied by the board systems
+    pub applying_color: Option<PlayerColor>,
+    /// Failed attempts to get back to the room. `None` when the connection is fine
+    pub reconnect_attempts: Option<u8>,
+    /// Connection to the server being opened by `reconnect`
+    pending_connection: Option<PendingConnection>,
+    pub reconnect_timer: Timer,
+}
+
+impl NetworkSession {
+    fn new(role: NetworkRole, status: String) -> Self {
+        Self {
+            role,
+            host: None,
+            connection: None,
+            server: None,
+            room: None,
+            is_ready: false,
+            local_color: None,
+            status,
+            moves_played: 0,
+            outbox: vec![],
+            inbox: vec![],
+            applying_color: None,
+            reconnect_attempts: None,
+            pending_connection: None,
+            reconnect_timer: Timer::from_seconds(RECONNECT_INTERVAL, true),
+        }
+    }
+
+    /// Waits for the guest on the TCP address or creates a room on the game server
+    /// when the address is a `ws://` url
+    pub fn host(address: &str) -> Result<Self, NetworkError> {
+        if is_websocket_url(address) {
+            let (server, _) = split_room(address);
+            return Self::with_server(NetworkRole::Host, &server, Message::Create);
+        }
+
+        let mut session = Self::new(NetworkRole::Host, format!("Hosting on {}", address));
+        session.host = Some(Host::bind(address)?);
+        Ok(session)
+    }
+
+    /// Connects to the TCP host or takes a seat in the room of the `ws://host:port/room` url
+    pub fn join(address: &str) -> Result<Self, NetworkError> {
+        if is_websocket_url(address) {
+            return match split_room(address) {
+                (server, Some(room)) => {
+                    Self::with_server(NetworkRole::Guest, &server, Message::Join { room })
+                }
+                (_, None) => Err(NetworkError::Protocol(
+                    "Add the room to the address: ws://host:port/room".to_owned(),
+                )),
+            };
+        }
+
+        let mut connection = Connection::Tcp(TcpConnection::connect(address)?);
+        connection.send(&Message::Hello {
+            version: PROTOCOL_VERSION,
+        })?;
+        let mut session = Self::new(NetworkRole::Guest, format!("Connected to {}", address));
+        session.connection = Some(connection);
+        Ok(session)
+    }
+
+    /// Watches the game in the room of the `ws://host:port/room` url
+    pub fn watch(address: &str) -> Result<Self, NetworkError> {
+        match split_room(address) {
+            (server, Some(room)) if is_websocket_url(address) => Self::with_server(
+                NetworkRole::Spectator,
+                &server,
+                Message::Watch { room, moves: 0 },
+            ),
+            _ => Err(NetworkError::Protocol(
+                "Only rooms on the game server can be watched: ws://host:port/room".to_owned(),
+            )),
+        }
+    }
+
+    fn with_server(
+        role: NetworkRole,
+        server: &str,
+        request: Message,
+    ) -> Result<Self, NetworkError> {
+        let mut connection = Connection::WebSocket(WebSocketConnection::connect(server)?);
+        connection.send(&Message::Hello {
+            version: PROTOCOL_VERSION,
+        })?;
+        connection.send(&request)?;
+
+        let mut session = Self::new(role, format!("Connected to {}", server));
+        session.connection = Some(connection);
+        session.server = Some(server.to_owned());
+        Ok(session)
+    }
+
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    /// The game server has given a seat or a view in a room
+    #[inline]
+    pub fn in_room(&self) -> bool {
+        self.room.is_some()
+    }
+
+    #[inline]
+    pub fn is_reconnecting(&self) -> bool {
+        self.connection.is_none() && self.reconnect_attempts.is_some()
+    }
+
+    pub fn send(&mut self, message: &Message) -> Result<(), NetworkError> {
+        log::info!("Sending a network message: {}", message.to_string());
+        if let Message::Move { .. } = message {
+            self.moves_played += 1;
+        }
+
+        if self.is_reconnecting() {
+            self.outbox.push(message.clone());
+            return Ok(());
+        }
+
+        match self.connection.as_mut() {
+            Some(connection) => connection.send(message),
+            None => Err(NetworkError::Closed),
+        }
+    }
+
+    /// Checks if the seat in the room on the game server can be taken back after the error
+    pub fn can_reconnect(&self, error: &NetworkError) -> bool {
+        self.room.is_some() && matches!(error, NetworkError::Io(_) | NetworkError::Closed)
+    }
+
+    /// Drops the broken connection. `reconnect` must be called until it succeeds
+    pub fn lose_connection(&mut self) {
+        self.connection = None;
+        self.reconnect_attempts = Some(self.reconnect_attempts.unwrap_or(0));
+        self.reconnect_timer.reset();
+        self.status = "Reconnecting".to_owned();
+    }
+
+    /// The connection to the server is being opened by `reconnect`
+    #[inline]
+    pub fn is_connecting(&self) -> bool {
+        self.pending_connection.is_some()
+    }
+
+    /// Starts to open a new connection to the server, `finish_reconnect` takes the seat back
+    pub fn reconnect(&mut self) -> Result<(), NetworkError> {
+        let room = self.room.as_ref().ok_or(NetworkError::Closed)?;
+        self.reconnect_attempts = Some(self.reconnect_attempts.unwrap_or(0) + 1);
+        self.pending_connection = Some(PendingConnection::start(&room.server));
+        Ok(())
+    }
+
+    /// Takes the seat in the room back and sends the messages which were not sent meanwhile.
+    /// Returns false while the connection is still being opened
+    pub fn finish_reconnect(&mut self) -> Result<bool, NetworkError> {
+        let result = match self
+            .pending_connection
+            .as_mut()
+            .map(PendingConnection::poll)
+        {
+            Some(Some(result)) => result,
+            _ => return Ok(false),
+        };
+        self.pending_connection = None;
+        let room = self.room.clone().ok_or(NetworkError::Closed)?;
+
+        let unsent_moves = self
+            .outbox
+            .iter()
+            .filter(|message| matches!(message, Message::Move { .. }))
+            .count() as u16;
+
+        let mut connection = Connection::WebSocket(result?);
+        connection.send(&Message::Hello {
+            version: PROTOCOL_VERSION,
+        })?;
+        let known_moves = self.moves_played - unsent_moves;
+        connection.send(&match self.role {
+            NetworkRole::Spectator => Message::Watch {
+                room: room.id,
+                moves: known_moves,
+            },
+            _ => Message::Rejoin {
+                room: room.id,
+                token: room.token,
+                moves: known_moves,
+            },
+        })?;
+        for message in self.outbox.iter() {
+            connection.send(message)?;
+        }
+
+        self.outbox.clear();
+        self.connection = Some(connection);
+        Ok(true)
+    }
+
+    /// Returns the messages to `poll`, so they are handled again in the next frame
+    pub fn unread(&mut self, messages: Vec<Message>) {
+        self.inbox.splice(0..0, messages);
+    }
+
+    /// Accepts the guest, processes the handshake and returns the other received messages
+    pub fn poll(&mut self) -> Result<Vec<Message>, NetworkError> {
+        if self.connection.is_none() {
+            if let Some(host) = self.host.as_ref() {
+                if let Some(mut connection) = host.accept()? {
+                    connection.send(&Message::Hello {
+                        version: PROTOCOL_VERSION,
+                    })?;
+                    self.connection = Some(Connection::Tcp(connection));
+                    // only one guest is accepted
+                    self.host = None;
+                    self.status = "Guest connected".to_owned();
+                }
+            }
+        }
+
+        let connection = match self.connection.as_mut() {
+            Some(connection) => connection,
+            None => return Ok(vec![]),
+        };
+
+        let mut messages = std::mem::take(&mut self.inbox);
+        for message in connection.receive()? {
+            log::info!("Received a network message: {}", message.to_string());
+            match message {
+                Message::Hello { version } if version == PROTOCOL_VERSION => {
+                    self.is_ready = true;
+                    if self.role != NetworkRole::Spectator {
+                        self.status = "Opponent is ready".to_owned();
+                    }
+                }
+                Message::Hello { version } => {
+                    let reason = format!("Unsupported protocol version {}", version);
+                    connection.send(&Message::Error {
+                        reason: reason.clone(),
+                    })?;
+                    return Err(NetworkError::Protocol(reason));
+                }
+                Message::Bye => return Err(NetworkError::Left),
+                Message::Room { room, token } => {
+                    // the server has answered, so the connection is fine again
+                    self.reconnect_attempts = None;
+                    self.status = match self.role {
+                        NetworkRole::Host if !self.is_ready => format!("Room {}. Waiting", room),
+                        NetworkRole::Spectator => format!("Watching room {}", room),
+                        _ => format!("Room {}", room),
+                    };
+                    self.room = self.server.clone().map(|server| Room {
+                        server,
+                        id: room,
+                        token,
+                    });
+                }
+                // the server has refused the room request
+                Message::Error { reason } if !self.is_ready => {
+                    return Err(NetworkError::Protocol(reason))
+                }
+                _ if !self.is_ready => {
+                    return Err(NetworkError::Protocol(
+                        "Message before the handshake".to_owned(),
+                    ))
+                }
+                message => {
+                    if let Message::Move { .. } = message {
+                        self.moves_played += 1;
+                    }
+                    messages.push(message);
+                }
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Says goodbye to the peer. The session must be removed afterwards
+    pub fn close(&mut self) {
+        if let Some(mut connection) = self.connection.take() {
+            if let Err(e) = connection.send(&Message::Bye) {
+                log::info!("Could not say goodbye: {}", e);
+            }
+        }
+        self.host = None;
+        self.room = None;
+        self.outbox.clear();
+        self.inbox.clear();
+        self.reconnect_attempts = None;
+        self.pending_connection = None;
+        self.is_ready = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::*;
+    use crate::{
+        components::coordinates::Coordinates,
+        resources::{card::CARDS, tile_map::Move},
+        rules::rule_set::RuleSetType,
+    };
+
+    /// Polls the session until it receives a message, the handshake is done on the way
+    fn receive(session: &mut NetworkSession) -> Message {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let mut messages = session.poll().unwrap();
+            if !messages.is_empty() {
+                return messages.remove(0);
+            }
+            assert!(Instant::now() < deadline, "{}", session.status);
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn wait_until_ready(host: &mut NetworkSession, guest: &mut NetworkSession) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !host.is_ready || !guest.is_ready {
+            assert!(host.poll().unwrap().is_empty());
+            assert!(guest.poll().unwrap().is_empty());
+            assert!(Instant::now() < deadline, "the handshake is not done");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn plays_over_localhost() {
+        let mut host = NetworkSession::host("127.0.0.1:0").unwrap();
+        let address = host.host.as_ref().unwrap().local_addr().unwrap();
+        let mut guest = NetworkSession::join(&address.to_string()).unwrap();
+        wait_until_ready(&mut host, &mut guest);
+        assert_eq!(host.status, "Opponent is ready");
+        assert_eq!(guest.status, "Opponent is ready");
+
+        let card = |name: &str| CARDS.iter().position(|card| card.name == name).unwrap() as u8;
+        let start = Message::Start {
+            color: PlayerColor::Blue,
+            rules: RuleSetType::Standard,
+            cards: [
+                card("Tiger"),
+                card("Crab"),
+                card("Monkey"),
+                card("Crane"),
+                card("Dragon"),
+            ],
+        };
+        host.send(&start).unwrap();
+        assert_eq!(receive(&mut guest), start);
+
+        // Monkey has the blue stamp, so the guest moves first
+        let mov = Message::Move {
+            card: "Crab",
+            mov: Move {
+                from: Coordinates { x: 1, y: 4 },
+                to: Coordinates { x: 1, y: 3 },
+            },
+        };
+        guest.send(&mov).unwrap();
+        assert_eq!(receive(&mut host), mov);
+        assert_eq!(guest.moves_played, 1);
+        assert_eq!(host.moves_played, 1);
+
+        guest.close();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            match host.poll() {
+                Ok(messages) => assert!(messages.is_empty()),
+                Err(e) => {
+                    assert!(matches!(e, NetworkError::Left), "{}", e);
+                    break;
+                }
+            }
+            assert!(Instant::now() < deadline, "the guest has not said goodbye");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! WebSocket connection to the game server.
+//!
+//! Native builds use `tungstenite` over a non-blocking socket,
+//! the web build uses the WebSocket API of the browser.
+//! Every WebSocket text frame carries one or more protocol lines.
+
+use super::{connection::NetworkError, protocol::Message};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::{PendingConnection, WebSocketConnection};
+#[cfg(target_arch = "wasm32")]
+pub use web::{PendingConnection, WebSocketConnection};
+
+/// Checks if the address points to the game server instead of a TCP host
+#[inline]
+pub fn is_websocket_url(address: &str) -> bool {
+    address.starts_with("ws://") || address.starts_with("wss://")
+}
+
+/// Splits `ws://host:port/room` into the server url and the room id
+pub fn split_room(url: &str) -> (String, Option<String>) {
+    let scheme_end = url.find("://").map(|idx| idx + 3).unwrap_or(0);
+    match url[scheme_end..].find('/') {
+        Some(idx) => {
+            let (server, room) = url.split_at(scheme_end + idx);
+            let room = room.trim_matches('/');
+            let room = if room.is_empty() {
+                None
+            } else {
+                Some(room.to_owned())
+            };
+            (server.to_owned(), room)
+        }
+        None => (url.to_owned(), None),
+    }
+}
+
+fn parse_frame(text: &str, messages: &mut Vec<Message>) -> Result<(), NetworkError> {
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        messages.push(Message::parse(line).map_err(NetworkError::Protocol)?);
+    }
+    Ok(())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{
+        io::ErrorKind,
+        net::{TcpStream, ToSocketAddrs},
+        sync::{
+            mpsc::{self, Receiver, TryRecvError},
+            Mutex,
+        },
+        thread,
+    };
+
+    use tungstenite::{Message as Frame, WebSocket};
+
+    use super::{parse_frame, split_room, Message, NetworkError};
+    use crate::network_plugin::connection::CONNECT_TIMEOUT;
+
+    #[derive(Debug)]
+    pub struct WebSocketConnection {
+        socket: WebSocket<TcpStream>,
+    }
+
+    impl WebSocketConnection {
+        /// Connects to the server and makes the handshake. Blocks for up to `CONNECT_TIMEOUT`
+        pub fn connect(url: &str) -> Result<Self, NetworkError> {
+            let (server, _) = split_room(url);
+            let host = server.split("://").nth(1).unwrap_or(&server);
+            let address = host
+                .to_socket_addrs()?
+                .next()
+                .ok_or_else(|| NetworkError::Protocol(format!("Unknown address: {}", host)))?;
+
+            let stream = TcpStream::connect_timeout(&address, CONNECT_TIMEOUT)?;
+            stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+            stream.set_nodelay(true)?;
+            let (socket, _) = tungstenite::client(url, stream)
+                .map_err(|e| NetworkError::Protocol(e.to_string()))?;
+            // the handshake is done, the game loop must never wait for the socket
+            socket.get_ref().set_nonblocking(true)?;
+
+            Ok(Self { socket })
+        }
+
+        pub fn send(&mut self, message: &Message) -> Result<(), NetworkError> {
+            match self.socket.write_message(Frame::Text(message.to_string())) {
+                Ok(()) => Ok(()),
+                // the frame is queued and will be flushed by `receive`
+                Err(tungstenite::Error::Io(e)) if e.kind() == ErrorKind::WouldBlock => Ok(()),
+                Err(e) => Err(to_network_error(e)),
+            }
+        }
+
+        pub fn receive(&mut self) -> Result<Vec<Message>, NetworkError> {
+            let mut messages = vec![];
+            loop {
+                match self.socket.read_message() {
+                    Ok(Frame::Text(text)) => parse_frame(&text, &mut messages)?,
+                    Ok(Frame::Close(_)) => return Err(NetworkError::Closed),
+                    // pings are answered by tungstenite itself
+                    Ok(_) => {}
+                    Err(tungstenite::Error::Io(e)) if e.kind() == ErrorKind::WouldBlock => break,
+                    Err(e) => return Err(to_network_error(e)),
+                }
+            }
+
+            match self.socket.write_pending() {
+                Err(tungstenite::Error::Io(e)) if e.kind() == ErrorKind::WouldBlock => {}
+                Err(e) => return Err(to_network_error(e)),
+                Ok(()) => {}
+            }
+
+            Ok(messages)
+        }
+    }
+
+    /// Connection opened on another thread, the game loop never waits for it
+    #[derive(Debug)]
+    pub struct PendingConnection {
+        result: Mutex<Receiver<Result<WebSocketConnection, NetworkError>>>,
+    }
+
+    impl PendingConnection {
+        pub fn start(url: &str) -> Self {
+            let url = url.to_owned();
+            let (sender, result) = mpsc::channel();
+            thread::spawn(move || {
+                // nobody waits for the connection when the session is closed meanwhile
+                let _ = sender.send(WebSocketConnection::connect(&url));
+            });
+            Self {
+                result: Mutex::new(result),
+            }
+        }
+
+        /// Returns `None` while the connection is being opened
+        pub fn poll(&mut self) -> Option<Result<WebSocketConnection, NetworkError>> {
+            match self.result.get_mut().unwrap().try_recv() {
+                Ok(result) => Some(result),
+                Err(TryRecvError::Empty) => None,
+                Err(TryRecvError::Disconnected) => Some(Err(NetworkError::Closed)),
+            }
+        }
+    }
+
+    fn to_network_error(e: tungstenite::Error) -> NetworkError {
+        match e {
+            tungstenite::Error::Io(e) => NetworkError::Io(e),
+            tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed => {
+                NetworkError::Closed
+            }
+            e => NetworkError::Protocol(e.to_string()),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use std::{
+        collections::VecDeque,
+        fmt::{self, Debug, Formatter},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    use wasm_bindgen::{prelude::Closure, JsCast};
+    use web_sys::{CloseEvent, MessageEvent, WebSocket};
+
+    use super::{parse_frame, Message, NetworkError};
+
+    pub struct WebSocketConnection {
+        socket: WebSocket,
+        // frames are pushed by the browser callbacks and taken by `receive`
+        received: Arc<Mutex<VecDeque<String>>>,
+        is_closed: Arc<AtomicBool>,
+        // messages sent while the socket is still connecting
+        pending: Vec<String>,
+        // callbacks must live as long as the socket
+        _on_message: Closure<dyn FnMut(MessageEvent)>,
+        _on_close: Closure<dyn FnMut(CloseEvent)>,
+    }
+
+    // The web build runs on a single thread, the browser objects never leave it
+    unsafe impl Send for WebSocketConnection {}
+    unsafe impl Sync for WebSocketConnection {}
+
+    impl Debug for WebSocketConnection {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.debug_struct("WebSocketConnection")
+                .field("url", &self.socket.url())
+                .finish()
+        }
+    }
+
+    impl WebSocketConnection {
+        /// Starts connecting to the server. The browser connects in the background,
+        /// so a failed connection is reported by `receive`
+        pub fn connect(url: &str) -> Result<Self, NetworkError> {
+            let socket =
+                WebSocket::new(url).map_err(|e| NetworkError::Protocol(format!("{:?}", e)))?;
+
+            let received = Arc::new(Mutex::new(VecDeque::new()));
+            let queue = received.clone();
+            let on_message = Closure::wrap(Box::new(move |event: MessageEvent| {
+                if let Some(text) = event.data().as_string() {
+                    queue.lock().unwrap().push_back(text);
+                }
+            }) as Box<dyn FnMut(MessageEvent)>);
+            socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+            let is_closed = Arc::new(AtomicBool::new(false));
+            let closed = is_closed.clone();
+            let on_close = Closure::wrap(Box::new(move |_: CloseEvent| {
+                closed.store(true, Ordering::SeqCst);
+            }) as Box<dyn FnMut(CloseEvent)>);
+            socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+            Ok(Self {
+                socket,
+                received,
+                is_closed,
+                pending: vec![],
+                _on_message: on_message,
+                _on_close: on_close,
+            })
+        }
+
+        pub fn send(&mut self, message: &Message) -> Result<(), NetworkError> {
+            match self.socket.ready_state() {
+                WebSocket::CONNECTING => {
+                    self.pending.push(message.to_string());
+                    Ok(())
+                }
+                WebSocket::OPEN => {
+                    self.flush()?;
+                    self.send_text(&message.to_string())
+                }
+                _ => Err(NetworkError::Closed),
+            }
+        }
+
+        pub fn receive(&mut self) -> Result<Vec<Message>, NetworkError> {
+            if self.socket.ready_state() == WebSocket::OPEN {
+                self.flush()?;
+            }
+
+            let mut messages = vec![];
+            let frames = self.received.lock().unwrap().drain(..).collect::<Vec<_>>();
+            for frame in frames.iter() {
+                parse_frame(frame, &mut messages)?;
+            }
+
+            if messages.is_empty() && self.is_closed.load(Ordering::SeqCst) {
+                return Err(NetworkError::Closed);
+            }
+            Ok(messages)
+        }
+
+        fn flush(&mut self) -> Result<(), NetworkError> {
+            for text in std::mem::take(&mut self.pending) {
+                self.send_text(&text)?;
+            }
+            Ok(())
+        }
+
+        fn send_text(&self, text: &str) -> Result<(), NetworkError> {
+            self.socket
+                .send_with_str(text)
+                .map_err(|e| NetworkError::Protocol(format!("{:?}", e)))
+        }
+    }
+
+    /// The browser opens the socket in the background by itself, so the connection is ready
+    /// right away
+    #[derive(Debug)]
+    pub struct PendingConnection {
+        result: Option<Result<WebSocketConnection, NetworkError>>,
+    }
+
+    impl PendingConnection {
+        pub fn start(url: &str) -> Self {
+            Self {
+                result: Some(WebSocketConnection::connect(url)),
+            }
+        }
+
+        pub fn poll(&mut self) -> Option<Result<WebSocketConnection, NetworkError>> {
+            Some(self.result.take().unwrap_or(Err(NetworkError::Closed)))
+        }
+    }
+
+    impl Drop for WebSocketConnection {
+        fn drop(&mut self) {
+            self.socket.set_onmessage(None);
+            self.socket.set_onclose(None);
+            let _ = self.socket.close();
+        }
+    }
+}
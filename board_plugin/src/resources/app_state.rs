@@ -1,6 +1,8 @@
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum AppState {
     MainMenu,
+    Statistics,
+    Draft,
     InProgress,
     GameEnd,
     Out,
@@ -1,18 +1,31 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 
 use crate::bounds::Bounds2;
 use crate::components::coordinates::Coordinates;
 
-use super::tile_map::TileMap;
+use super::{
+    pointer::Pointer,
+    tile_map::{TileMap, BOARD_SIZE},
+};
 
 /// Base tile map
 #[derive(Debug, Clone)]
 pub struct Board {
+    /// Bounds in the world space, they follow the layout
     pub bounds: Bounds2,
+    /// Size of a tile in the board space
     pub tile_size: f32,
+    /// Scale of the board space set by the layout
+    pub scale: f32,
     pub padding: f32,
+    /// Time in which a moved piece slides to its new tile
+    pub move_duration: Duration,
     pub tile_map: TileMap,
     pub entity: Entity,
+    /// Red is at the top and Blue at the bottom of a flipped board
+    pub is_flipped: bool,
 }
 
 impl Board {
@@ -27,13 +40,57 @@ impl Board {
             return None;
         }
         // World space to board space
-        let coordinates = position - self.bounds.position;
+        let coordinates = (position - self.bounds.position) / self.scale;
         Some(Coordinates {
             x: (coordinates.x / self.tile_size) as u8,
-            y: (coordinates.y / self.tile_size) as u8,
+            y: self.row((coordinates.y / self.tile_size) as u8),
         })
     }
 
+    /// Tile under the pointer. A touch just outside the board hits the nearest tile
+    pub fn pointer_position(&self, window: &Window, pointer: &Pointer) -> Option<Coordinates> {
+        // Window to world space
+        let window_size = Vec2::new(window.width(), window.height());
+        let position = pointer.position? - window_size / 2.;
+
+        let margin = pointer.hit_margin(self.tile_size * self.scale);
+        if !self.bounds.expanded(margin).in_bounds(position) {
+            return None;
+        }
+        // the far edges belong to no tile
+        let position = position.clamp(
+            self.bounds.position,
+            self.bounds.position + self.bounds.size - Vec2::splat(0.5),
+        );
+        self.mouse_position(window, position + window_size / 2.)
+    }
+
+    /// Row on the screen of a board row, the rows are reversed on a flipped board.
+    /// Translates the rows both ways
+    pub fn row(&self, y: u8) -> u8 {
+        match self.is_flipped {
+            true => BOARD_SIZE as u8 - 1 - y,
+            false => y,
+        }
+    }
+
+    /// Center of the tile relative to the board
+    pub fn tile_position(&self, coordinates: &Coordinates) -> Vec2 {
+        Vec2::new(
+            (coordinates.x as f32 + 0.5) * self.tile_size,
+            (self.row(coordinates.y) as f32 + 0.5) * self.tile_size,
+        )
+    }
+
+    /// Offset of a mouse position from the center of the tile in the board space
+    pub fn tile_offset(&self, window: &Window, position: Vec2, coordinates: &Coordinates) -> Vec2 {
+        // Window to world space
+        let window_size = Vec2::new(window.width(), window.height());
+        let position = position - window_size / 2.;
+
+        (position - self.bounds.position) / self.scale - self.tile_position(coordinates)
+    }
+
     pub fn in_bounds(&self, window: &Window, position: Vec2) -> bool {
         // Window to world space
         let window_size = Vec2::new(window.width(), window.height());
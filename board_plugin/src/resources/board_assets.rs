@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
 
+use crate::components::pieces::{Piece, PieceKind};
+
+use super::game_state::PlayerColor;
+
 // Was taken from here: https://dev.to/qongzi/bevy-minesweeper-part-3-1a9a
 
 #[derive(Debug, Clone)]
@@ -47,10 +51,24 @@ pub struct BoardAssets {
     pub selected_red_card_material: SpriteMaterial,
     ///
     pub selected_blue_card_material: SpriteMaterial,
+    /// Material for the neutral card selected in the main menu
+    pub selected_neutral_card_material: SpriteMaterial,
     ///
     pub selected_piece_material: SpriteMaterial,
     ///
     pub allowed_move_tile_material: SpriteMaterial,
+    /// Material for the first card of the player and the tiles it reaches while a piece is dragged
+    pub first_card_move_material: SpriteMaterial,
+    /// Material for the second card of the player and the tiles it reaches while a piece is dragged
+    pub second_card_move_material: SpriteMaterial,
+    /// Material for the tiles reached by both cards while a piece is dragged
+    pub both_cards_move_material: SpriteMaterial,
+    /// Material for the tile chosen with the arrow keys
+    pub keyboard_cursor_material: SpriteMaterial,
+    /// Material drawn above the origin and the destination of the last move
+    pub last_move_tile_material: SpriteMaterial,
+    /// Material for the frame of the card played for the last move
+    pub last_move_card_material: SpriteMaterial,
     ///
     pub guide_text_size: f32,
     ///
@@ -58,3 +76,66 @@ pub struct BoardAssets {
     ///
     pub font: Handle<Font>,
 }
+
+/// Names of the materials in the themes, the fields of `BoardAssets` without `_material`
+pub const MATERIAL_NAMES: [&str; 20] = [
+    "board",
+    "tile",
+    "temple_tile",
+    "blue_pawn",
+    "blue_king",
+    "red_pawn",
+    "red_king",
+    "deck_card_center",
+    "deck_card_allowed_move",
+    "selected_red_card",
+    "selected_blue_card",
+    "selected_neutral_card",
+    "selected_piece",
+    "allowed_move_tile",
+    "first_card_move",
+    "second_card_move",
+    "both_cards_move",
+    "keyboard_cursor",
+    "last_move_tile",
+    "last_move_card",
+];
+
+impl BoardAssets {
+    pub fn piece_material(&self, piece: &Piece) -> &SpriteMaterial {
+        match (piece.color, piece.kind) {
+            (PlayerColor::Red, PieceKind::Pawn) => &self.red_pawn_material,
+            (PlayerColor::Red, PieceKind::King) => &self.red_king_material,
+            (PlayerColor::Blue, PieceKind::Pawn) => &self.blue_pawn_material,
+            (PlayerColor::Blue, PieceKind::King) => &self.blue_king_material,
+        }
+    }
+
+    /// Material by its name in `MATERIAL_NAMES`
+    pub fn material_mut(&mut self, name: &str) -> Option<&mut SpriteMaterial> {
+        let material = match name {
+            "board" => &mut self.board_material,
+            "tile" => &mut self.tile_material,
+            "temple_tile" => &mut self.temple_tile_material,
+            "blue_pawn" => &mut self.blue_pawn_material,
+            "blue_king" => &mut self.blue_king_material,
+            "red_pawn" => &mut self.red_pawn_material,
+            "red_king" => &mut self.red_king_material,
+            "deck_card_center" => &mut self.deck_card_center_material,
+            "deck_card_allowed_move" => &mut self.deck_card_allowed_move_material,
+            "selected_red_card" => &mut self.selected_red_card_material,
+            "selected_blue_card" => &mut self.selected_blue_card_material,
+            "selected_neutral_card" => &mut self.selected_neutral_card_material,
+            "selected_piece" => &mut self.selected_piece_material,
+            "allowed_move_tile" => &mut self.allowed_move_tile_material,
+            "first_card_move" => &mut self.first_card_move_material,
+            "second_card_move" => &mut self.second_card_move_material,
+            "both_cards_move" => &mut self.both_cards_move_material,
+            "keyboard_cursor" => &mut self.keyboard_cursor_material,
+            "last_move_tile" => &mut self.last_move_tile_material,
+            "last_move_card" => &mut self.last_move_card_material,
+            _ => return None,
+        };
+        Some(material)
+    }
+}
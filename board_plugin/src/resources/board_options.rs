@@ -1,4 +1,5 @@
-use bevy::math::Vec3;
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
 // Was taken from here: https://dev.to/qongzi/bevy-minesweeper-part-3-1a9a
@@ -9,12 +10,15 @@ pub enum TileSize {
     Adaptive { min: f32, max: f32 },
 }
 
-/// Board generation options. Must be used as a resource
+/// Board generation options. Must be used as a resource. The board is placed by `Layout`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BoardOptions {
-    pub position: Vec3,
     pub tile_size: TileSize,
     pub tile_padding: f32,
+    /// Time in which a moved piece slides to its new tile, the turn is switched afterwards
+    pub move_duration: Duration,
+    /// Letters of the columns and numbers of the rows are printed along the board
+    pub show_coordinates: bool,
 }
 
 impl Default for TileSize {
@@ -29,9 +33,10 @@ impl Default for TileSize {
 impl Default for BoardOptions {
     fn default() -> Self {
         Self {
-            position: Default::default(),
             tile_size: Default::default(),
             tile_padding: 0.,
+            move_duration: Duration::from_millis(300),
+            show_coordinates: true,
         }
     }
 }
@@ -1,3 +1,5 @@
+use super::game_state::PlayerColor;
+
 #[derive(Debug, Clone, Copy)]
 pub enum Edition {
     Original,
@@ -9,57 +11,125 @@ pub struct Card {
     pub edition: Edition,
     pub directions: &'static [(i8, i8)],
     pub name: &'static str,
+    /// Colour stamp of the card. When the card is neutral at the start, the stamped side moves first
+    pub stamp: PlayerColor,
     pub is_mirrored: bool,
 }
 
 impl Card {
-    const fn new(edition: Edition, directions: &'static [(i8, i8)], name: &'static str) -> Self {
+    const fn new(
+        edition: Edition,
+        directions: &'static [(i8, i8)],
+        name: &'static str,
+        stamp: PlayerColor,
+    ) -> Self {
         Self {
             edition,
             directions,
             name,
+            stamp,
             is_mirrored: false,
         }
     }
 }
 
 pub const CARDS: [Card; 16] = [
-    Card::new(Edition::Original, &[(0, 2), (0, -1)], "Tiger"),
-    Card::new(Edition::Original, &[(0, 1), (-2, 0), (2, 0)], "Crab"),
+    Card::new(
+        Edition::Original,
+        &[(0, 2), (0, -1)],
+        "Tiger",
+        PlayerColor::Blue,
+    ),
+    Card::new(
+        Edition::Original,
+        &[(0, 1), (-2, 0), (2, 0)],
+        "Crab",
+        PlayerColor::Blue,
+    ),
     Card::new(
         Edition::Original,
         &[(-1, 1), (-1, -1), (1, 1), (1, -1)],
         "Monkey",
+        PlayerColor::Blue,
+    ),
+    Card::new(
+        Edition::Original,
+        &[(0, 1), (-1, -1), (1, -1)],
+        "Crane",
+        PlayerColor::Blue,
     ),
-    Card::new(Edition::Original, &[(0, 1), (-1, -1), (1, -1)], "Crane"),
     Card::new(
         Edition::Original,
         &[(-2, 1), (-1, -1), (2, 1), (1, -1)],
         "Dragon",
+        PlayerColor::Red,
     ),
     Card::new(
         Edition::Original,
         &[(-1, 0), (-1, 1), (1, 1), (1, 0)],
         "Elephant",
+        PlayerColor::Red,
+    ),
+    Card::new(
+        Edition::Original,
+        &[(0, -1), (-1, 1), (1, 1)],
+        "Mantis",
+        PlayerColor::Red,
+    ),
+    Card::new(
+        Edition::Original,
+        &[(0, 1), (-1, 0), (1, 0)],
+        "Boar",
+        PlayerColor::Red,
+    ),
+    Card::new(
+        Edition::Original,
+        &[(-2, 0), (-1, 1), (1, -1)],
+        "Frog",
+        PlayerColor::Red,
     ),
-    Card::new(Edition::Original, &[(0, -1), (-1, 1), (1, 1)], "Mantis"),
-    Card::new(Edition::Original, &[(0, 1), (-1, 0), (1, 0)], "Boar"),
-    Card::new(Edition::Original, &[(-2, 0), (-1, 1), (1, -1)], "Frog"),
     Card::new(
         Edition::Original,
         &[(-1, 0), (-1, 1), (1, 0), (1, -1)],
         "Goose",
+        PlayerColor::Blue,
+    ),
+    Card::new(
+        Edition::Original,
+        &[(0, 1), (-1, 0), (0, -1)],
+        "Horse",
+        PlayerColor::Red,
+    ),
+    Card::new(
+        Edition::Original,
+        &[(-1, 1), (-1, -1), (1, 0)],
+        "Eel",
+        PlayerColor::Blue,
+    ),
+    Card::new(
+        Edition::Original,
+        &[(-1, -1), (1, 1), (2, 0)],
+        "Rabbit",
+        PlayerColor::Blue,
     ),
-    Card::new(Edition::Original, &[(0, 1), (-1, 0), (0, -1)], "Horse"),
-    Card::new(Edition::Original, &[(-1, 1), (-1, -1), (1, 0)], "Eel"),
-    Card::new(Edition::Original, &[(-1, -1), (1, 1), (2, 0)], "Rabbit"),
     Card::new(
         Edition::Original,
         &[(-1, 0), (-1, -1), (1, 0), (1, 1)],
         "Rooster",
+        PlayerColor::Red,
+    ),
+    Card::new(
+        Edition::Original,
+        &[(0, 1), (0, -1), (1, 0)],
+        "Ox",
+        PlayerColor::Blue,
+    ),
+    Card::new(
+        Edition::Original,
+        &[(-1, 0), (1, -1), (1, 1)],
+        "Cobra",
+        PlayerColor::Red,
     ),
-    Card::new(Edition::Original, &[(0, 1), (0, -1), (1, 0)], "Ox"),
-    Card::new(Edition::Original, &[(-1, 0), (1, -1), (1, 1)], "Cobra"),
 ];
 
 // const TIGER: Card = Card::new(Edition::Original, &[0, 2, 0, -1], "Tiger");
@@ -0,0 +1,296 @@
+use std::time::Duration;
+
+use super::{game_state::PlayerColor, time_control::TimeControl};
+
+// Bots plan to spend the remaining main time on this many moves
+const MOVES_TO_GO: u32 = 20;
+// Part of the increment or byoyomi period which bots use, the rest covers the frame time
+const EXTRA_TIME_SHARE: f32 = 0.75;
+
+/// Clock of one player
+#[derive(Debug, Clone, Copy)]
+pub struct Clock {
+    pub remaining: Duration,
+    /// The main time is used up and the moves are made within the byoyomi period
+    pub is_byoyomi: bool,
+}
+
+impl Clock {
+    fn new(time_control: &TimeControl) -> Self {
+        match *time_control {
+            TimeControl::None => Self {
+                remaining: Duration::ZERO,
+                is_byoyomi: false,
+            },
+            TimeControl::Fischer { base, .. } => Self {
+                remaining: base,
+                is_byoyomi: false,
+            },
+            TimeControl::Byoyomi { base, period } if base.is_zero() => Self {
+                remaining: period,
+                is_byoyomi: true,
+            },
+            TimeControl::Byoyomi { base, .. } => Self {
+                remaining: base,
+                is_byoyomi: false,
+            },
+        }
+    }
+
+    #[inline]
+    pub fn is_flag_fallen(&self) -> bool {
+        self.remaining.is_zero()
+    }
+}
+
+impl ToString for Clock {
+    fn to_string(&self) -> String {
+        let secs = self.remaining.as_secs();
+        let time = if secs < 10 {
+            // tenths are shown when it gets close
+            format!("0:0{}.{}", secs, self.remaining.subsec_millis() / 100)
+        } else {
+            format!("{}:{:02}", secs / 60, secs % 60)
+        };
+
+        match self.is_byoyomi {
+            true => format!("{} BY", time),
+            false => time,
+        }
+    }
+}
+
+/// Clocks of the game. Must be used as a resource
+#[derive(Debug, Clone)]
+pub struct Clocks {
+    pub time_control: TimeControl,
+    pub red: Clock,
+    pub blue: Clock,
+}
+
+impl Clocks {
+    pub fn new(time_control: TimeControl) -> Self {
+        Self {
+            time_control,
+            red: Clock::new(&time_control),
+            blue: Clock::new(&time_control),
+        }
+    }
+
+    #[inline]
+    pub fn is_timed(&self) -> bool {
+        self.time_control != TimeControl::None
+    }
+
+    #[inline]
+    pub fn get(&self, color: &PlayerColor) -> &Clock {
+        match color {
+            PlayerColor::Red => &self.red,
+            PlayerColor::Blue => &self.blue,
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, color: &PlayerColor) -> &mut Clock {
+        match color {
+            PlayerColor::Red => &mut self.red,
+            PlayerColor::Blue => &mut self.blue,
+        }
+    }
+
+    /// Runs the clock of the player. Returns true when the flag has just fallen
+    pub fn tick(&mut self, color: &PlayerColor, delta: Duration) -> bool {
+        let time_control = self.time_control;
+        let clock = self.get_mut(color);
+        if time_control == TimeControl::None || clock.is_flag_fallen() {
+            return false;
+        }
+
+        if delta < clock.remaining {
+            clock.remaining -= delta;
+            return false;
+        }
+
+        match time_control {
+            TimeControl::Byoyomi { period, .. } if !clock.is_byoyomi => {
+                // the rest of the frame is taken from the first period
+                let overflow = delta - clock.remaining;
+                clock.is_byoyomi = true;
+                clock.remaining = period.saturating_sub(overflow);
+            }
+            _ => clock.remaining = Duration::ZERO,
+        }
+        clock.is_flag_fallen()
+    }
+
+    /// Adds the increment or starts a new byoyomi period after the move of the player
+    pub fn on_move(&mut self, color: &PlayerColor) {
+        let time_control = self.time_control;
+        let clock = self.get_mut(color);
+        if clock.is_flag_fallen() {
+            return;
+        }
+
+        match time_control {
+            TimeControl::Fischer { increment, .. } => clock.remaining += increment,
+            TimeControl::Byoyomi { period, .. } if clock.is_byoyomi => clock.remaining = period,
+            _ => {}
+        }
+    }
+
+    /// Time which a bot may spend on its next move, `None` in an untimed game
+    pub fn move_budget(&self, color: &PlayerColor) -> Option<Duration> {
+        let clock = self.get(color);
+        let budget = match self.time_control {
+            TimeControl::None => return None,
+            TimeControl::Fischer { increment, .. } => {
+                let budget = clock.remaining / MOVES_TO_GO + increment.mul_f32(EXTRA_TIME_SHARE);
+                // the increment comes only after the move
+                budget.min(clock.remaining / 2)
+            }
+            TimeControl::Byoyomi { .. } if clock.is_byoyomi => {
+                clock.remaining.mul_f32(EXTRA_TIME_SHARE)
+            }
+            // the main time may run out during the move, then the period starts
+            TimeControl::Byoyomi { period, .. } => {
+                clock.remaining / MOVES_TO_GO + period.mul_f32(EXTRA_TIME_SHARE)
+            }
+        };
+        Some(budget)
+    }
+}
+
+impl Default for Clocks {
+    fn default() -> Self {
+        Self::new(TimeControl::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: PlayerColor = PlayerColor::Red;
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    fn fischer() -> Clocks {
+        Clocks::new(TimeControl::Fischer {
+            base: secs(60),
+            increment: secs(1),
+        })
+    }
+
+    fn byoyomi(base: u64) -> Clocks {
+        Clocks::new(TimeControl::Byoyomi {
+            base: secs(base),
+            period: secs(10),
+        })
+    }
+
+    #[test]
+    fn untimed_clocks_do_not_run() {
+        let mut clocks = Clocks::default();
+        assert!(!clocks.is_timed());
+        assert!(!clocks.tick(&RED, secs(1000)));
+        clocks.on_move(&RED);
+        assert_eq!(clocks.red.remaining, Duration::ZERO);
+        assert_eq!(clocks.move_budget(&RED), None);
+    }
+
+    #[test]
+    fn adds_the_fischer_increment() {
+        let mut clocks = fischer();
+        assert!(!clocks.tick(&RED, secs(10)));
+        assert_eq!(clocks.red.remaining, secs(50));
+        assert_eq!(clocks.blue.remaining, secs(60));
+
+        clocks.on_move(&RED);
+        assert_eq!(clocks.red.remaining, secs(51));
+        assert!(!clocks.red.is_byoyomi);
+    }
+
+    #[test]
+    fn flag_falls_once() {
+        let mut clocks = fischer();
+        assert!(clocks.tick(&RED, secs(61)));
+        assert!(clocks.red.is_flag_fallen());
+        assert!(!clocks.tick(&RED, secs(1)));
+
+        // a fallen flag gets no increment
+        clocks.on_move(&RED);
+        assert!(clocks.red.is_flag_fallen());
+    }
+
+    #[test]
+    fn enters_and_resets_the_byoyomi_period() {
+        let mut clocks = byoyomi(60);
+        assert!(!clocks.tick(&RED, secs(59)));
+        assert!(!clocks.red.is_byoyomi);
+
+        // the rest of the frame is taken from the first period
+        assert!(!clocks.tick(&RED, secs(3)));
+        assert!(clocks.red.is_byoyomi);
+        assert_eq!(clocks.red.remaining, secs(8));
+
+        clocks.on_move(&RED);
+        assert_eq!(clocks.red.remaining, secs(10));
+
+        // the period is used up without a move
+        assert!(clocks.tick(&RED, secs(10)));
+    }
+
+    #[test]
+    fn main_time_moves_do_not_reset_the_period() {
+        let mut clocks = byoyomi(60);
+        clocks.tick(&RED, secs(20));
+        clocks.on_move(&RED);
+        assert_eq!(clocks.red.remaining, secs(40));
+
+        // a frame longer than the rest of the main time and the period
+        assert!(clocks.tick(&RED, secs(55)));
+    }
+
+    #[test]
+    fn starts_in_the_period_without_main_time() {
+        let clocks = byoyomi(0);
+        assert!(clocks.red.is_byoyomi);
+        assert_eq!(clocks.red.remaining, secs(10));
+        assert_eq!(clocks.red.to_string(), "0:10 BY");
+    }
+
+    #[test]
+    fn shows_tenths_under_ten_seconds() {
+        let mut clocks = fischer();
+        assert_eq!(clocks.red.to_string(), "1:00");
+        clocks.tick(&RED, Duration::from_millis(50_500));
+        assert_eq!(clocks.red.to_string(), "0:09.5");
+    }
+
+    fn assert_budget(clocks: &Clocks, millis: u64) {
+        let budget = clocks.move_budget(&RED).unwrap();
+        // the shares of the periods are computed in f32
+        let diff = budget.as_secs_f64() - Duration::from_millis(millis).as_secs_f64();
+        assert!(diff.abs() < 0.001, "{:?} is not {}ms", budget, millis);
+    }
+
+    #[test]
+    fn budgets_the_bot_time() {
+        let mut clocks = fischer();
+        // a twentieth of the clock and most of the increment
+        assert_budget(&clocks, 3750);
+
+        clocks.tick(&RED, secs(58));
+        assert_budget(&clocks, 850);
+        // with little time left at most half of it is used
+        clocks.tick(&RED, secs(1));
+        assert_budget(&clocks, 500);
+
+        let mut clocks = byoyomi(60);
+        assert_budget(&clocks, 10_500);
+        clocks.tick(&RED, secs(60));
+        assert_budget(&clocks, 7500);
+    }
+}
@@ -1,13 +1,11 @@
 use serde::{Deserialize, Serialize};
-use bevy::prelude::*;
 
 use super::board_options::TileSize;
 
 
-/// Board generation options. Must be used as a resource
+/// Card generation options. Must be used as a resource. The cards are placed by `Layout`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeckOptions {
-    pub position: Vec3,
     pub tile_size: TileSize,
     pub tile_padding: f32,
 }
@@ -15,7 +13,6 @@ pub struct DeckOptions {
 impl Default for DeckOptions {
     fn default() -> Self {
         Self {
-            position: Default::default(),
             tile_size: Default::default(),
             tile_padding: 0.,
         }
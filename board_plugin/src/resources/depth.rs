@@ -1,16 +1,19 @@
+pub const MIN_DEPTH: u8 = 2;
+pub const MAX_DEPTH: u8 = 10;
+
 // Max depth of the Alpha beta search
 #[derive(Debug, Clone)]
 pub struct Depth(pub u8);
 
 impl Depth {
     pub fn add(&mut self) {
-        if self.0 + 1 <= 10 {
+        if self.0 + 1 <= MAX_DEPTH {
             self.0 += 1;
         }
     }
 
     pub fn sub(&mut self) {
-        if self.0 - 1 >= 2 {
+        if self.0 - 1 >= MIN_DEPTH {
             self.0 -= 1;
         }
     }
@@ -0,0 +1,129 @@
+use bevy::core::Timer;
+use rand::{thread_rng, Rng};
+
+use super::card::CARDS;
+use super::game_state::PlayerColor;
+
+/// State of the opening card draft. Players alternately pick cards from the pool
+/// until both of them have two cards, the neutral card is taken from the rest
+#[derive(Debug, Clone)]
+pub struct Draft {
+    /// Indices of the cards from `CARDS` which are still available
+    pub pool: Vec<u8>,
+    pub red_picks: Vec<u8>,
+    pub blue_picks: Vec<u8>,
+    /// Player who picks the next card
+    pub curr_color: PlayerColor,
+    /// Delay before the bot picks a card, so the draft can be followed
+    pub bot_timer: Timer,
+}
+
+impl Draft {
+    /// Creates a pool of `pool_size` cards. Preselected cards are put into the pool first
+    pub fn new(pool_size: u8, preselected: &Vec<u8>, first_picker: PlayerColor) -> Self {
+        let pool_size = (pool_size as usize).clamp(5, CARDS.len());
+        let mut pool: Vec<u8> = Vec::with_capacity(pool_size);
+        for index in preselected.iter() {
+            if pool.len() < pool_size && !pool.contains(index) {
+                pool.push(*index);
+            }
+        }
+
+        let mut rng = thread_rng();
+
+        while pool.len() != pool_size {
+            let index = rng.gen_range(0..CARDS.len()) as u8;
+            if pool.contains(&index) {
+                continue;
+            }
+            pool.push(index);
+        }
+
+        Self {
+            pool,
+            red_picks: Vec::with_capacity(2),
+            blue_picks: Vec::with_capacity(2),
+            curr_color: first_picker,
+            bot_timer: Timer::from_seconds(0.5, false),
+        }
+    }
+
+    pub fn pick(&mut self, card: u8) {
+        if let Some(position) = self.pool.iter().position(|c| *c == card) {
+            self.pool.remove(position);
+        } else {
+            return;
+        }
+
+        match self.curr_color {
+            PlayerColor::Red => self.red_picks.push(card),
+            PlayerColor::Blue => self.blue_picks.push(card),
+        }
+        self.curr_color.switch();
+    }
+
+    pub fn undo_pick(&mut self) {
+        self.curr_color.switch();
+        let card = match self.curr_color {
+            PlayerColor::Red => self.red_picks.pop(),
+            PlayerColor::Blue => self.blue_picks.pop(),
+        };
+        if let Some(card) = card {
+            self.pool.push(card);
+        }
+    }
+
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.red_picks.len() == 2 && self.blue_picks.len() == 2
+    }
+
+    /// Returns the card indices in the order expected by `PhysicalDeck::take_cards_from_indices`:
+    /// red cards, a random neutral card from the rest of the pool and blue cards
+    pub fn deal(&self) -> Vec<u8> {
+        assert!(self.is_finished());
+        let neutral = self.pool[thread_rng().gen_range(0..self.pool.len())];
+        vec![
+            self.red_picks[0],
+            self.red_picks[1],
+            neutral,
+            self.blue_picks[0],
+            self.blue_picks[1],
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::handicap::Handicap;
+
+    #[test]
+    fn weaker_side_picks_first() {
+        let handicap = Handicap::CardChoice {
+            weaker: PlayerColor::Blue,
+        };
+        assert!(handicap.needs_draft());
+
+        let mut draft = Draft::new(6, &vec![], handicap.first_card_choice());
+        let card = draft.pool[0];
+        draft.pick(card);
+        assert_eq!(draft.blue_picks, vec![card]);
+        assert!(draft.red_picks.is_empty());
+        assert_eq!(draft.curr_color, PlayerColor::Red);
+    }
+
+    #[test]
+    fn keeps_the_pool_size_in_range() {
+        assert_eq!(Draft::new(2, &vec![], PlayerColor::Red).pool.len(), 5);
+        assert_eq!(
+            Draft::new(u8::MAX, &vec![], PlayerColor::Red).pool.len(),
+            CARDS.len()
+        );
+
+        // the preselected cards come first and only once
+        let draft = Draft::new(6, &vec![3, 3, 7], PlayerColor::Red);
+        assert_eq!(draft.pool.len(), 6);
+        assert_eq!(&draft.pool[..2], &[3, 7]);
+    }
+}
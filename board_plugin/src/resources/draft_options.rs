@@ -0,0 +1,81 @@
+use super::card::CARDS;
+
+/// Smallest pool: both players pick two cards and the neutral card is left
+pub const MIN_POOL_SIZE: u8 = 5;
+/// Largest pool: every card is revealed
+pub const MAX_POOL_SIZE: u8 = CARDS.len() as u8;
+
+/// Card draft options. Must be used as a resource
+#[derive(Debug, Clone)]
+pub struct DraftOptions {
+    /// If enabled, players pick their cards from the pool at the game start
+    pub enabled: bool,
+    /// Amount of revealed cards to pick from, between `MIN_POOL_SIZE` and `MAX_POOL_SIZE`
+    pool_size: u8,
+}
+
+impl DraftOptions {
+    #[inline]
+    pub fn pool_size(&self) -> u8 {
+        self.pool_size
+    }
+
+    /// Sets the pool size, the sizes out of the range are clamped
+    pub fn set_pool_size(&mut self, pool_size: u8) {
+        self.pool_size = pool_size.clamp(MIN_POOL_SIZE, MAX_POOL_SIZE);
+    }
+
+    /// Cycles the draft in the main menu: off, then every pool size from the smallest one
+    pub fn next(&mut self) {
+        if !self.enabled {
+            self.enabled = true;
+            self.pool_size = MIN_POOL_SIZE;
+        } else if self.pool_size >= MAX_POOL_SIZE {
+            self.enabled = false;
+        } else {
+            self.pool_size += 1;
+        }
+    }
+}
+
+impl Default for DraftOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            pool_size: 6,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_pool_size_in_range() {
+        let mut draft_options = DraftOptions::default();
+        draft_options.set_pool_size(0);
+        assert_eq!(draft_options.pool_size(), MIN_POOL_SIZE);
+        draft_options.set_pool_size(u8::MAX);
+        assert_eq!(draft_options.pool_size(), MAX_POOL_SIZE);
+        draft_options.set_pool_size(8);
+        assert_eq!(draft_options.pool_size(), 8);
+    }
+
+    #[test]
+    fn cycles_through_the_pool_sizes() {
+        let mut draft_options = DraftOptions::default();
+        draft_options.next();
+        assert!(draft_options.enabled);
+        assert_eq!(draft_options.pool_size(), MIN_POOL_SIZE);
+
+        for size in MIN_POOL_SIZE + 1..=MAX_POOL_SIZE {
+            draft_options.next();
+            assert!(draft_options.enabled);
+            assert_eq!(draft_options.pool_size(), size);
+        }
+
+        draft_options.next();
+        assert!(!draft_options.enabled);
+    }
+}
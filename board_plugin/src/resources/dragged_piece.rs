@@ -0,0 +1,41 @@
+use bevy::prelude::Entity;
+
+use crate::components::coordinates::Coordinates;
+
+/// Piece of the current player picked up with the mouse. Must be used as a resource,
+/// it exists while the left button is held after pressing on the piece
+#[derive(Debug, Clone)]
+pub struct DraggedPiece {
+    /// Tile the piece is picked up from
+    pub tile: Entity,
+    pub coordinates: Coordinates,
+    /// Set once the cursor leaves the tile, a press released on the same tile is a click
+    pub is_dragging: bool,
+    pub is_highlighted: bool,
+    /// Cards of the player in the deck order
+    pub cards: [Entity; 2],
+    /// Destinations reachable with each of the `cards`
+    pub moves: [Vec<Coordinates>; 2],
+}
+
+impl DraggedPiece {
+    /// Cards with which the piece can be moved to the coordinates
+    pub fn cards_reaching(&self, coordinates: &Coordinates) -> Vec<Entity> {
+        self.cards
+            .iter()
+            .zip(self.moves.iter())
+            .filter(|(_, moves)| moves.contains(coordinates))
+            .map(|(card, _)| *card)
+            .collect()
+    }
+}
+
+/// Piece dropped on a tile reachable with both cards of the player. Must be used
+/// as a resource, the move is made when the player selects the card to spend
+#[derive(Debug, Clone)]
+pub struct DropChoice {
+    pub tile: Entity,
+    pub from: Coordinates,
+    pub to: Coordinates,
+    pub cards: [Entity; 2],
+}
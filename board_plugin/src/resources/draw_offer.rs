@@ -0,0 +1,16 @@
+use super::game_state::PlayerColor;
+
+/// Draw offer which waits for the answer of the opponent. Must be used as a resource
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DrawOffer {
+    /// The offer is withdrawn when the opponent makes a move instead of answering
+    pub offered_by: Option<PlayerColor>,
+}
+
+impl DrawOffer {
+    /// The opponent of the player has offered a draw which the player can accept
+    #[inline]
+    pub fn is_offered_to(&self, color: &PlayerColor) -> bool {
+        self.offered_by == Some(color.enemy())
+    }
+}
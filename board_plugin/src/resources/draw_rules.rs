@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+// Draw limits in the order they are cycled through in the main menu, `None` turns the rule off
+const REPETITIONS: [Option<u8>; 5] = [None, Some(2), Some(3), Some(4), Some(5)];
+const MOVE_LIMITS: [Option<u16>; 6] = [None, Some(50), Some(100), Some(200), Some(300), Some(500)];
+
+/// Rules which end the game in a draw. Must be used as a resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrawRules {
+    /// The game is drawn when the same position occurs this many times
+    pub repetitions: Option<u8>,
+    /// The game is drawn when more moves than this limit are made
+    pub move_limit: Option<u16>,
+}
+
+impl DrawRules {
+    pub fn next_repetitions(&mut self) {
+        self.repetitions = cycle(&REPETITIONS, self.repetitions, 1);
+    }
+
+    pub fn prev_repetitions(&mut self) {
+        self.repetitions = cycle(&REPETITIONS, self.repetitions, REPETITIONS.len() - 1);
+    }
+
+    pub fn next_move_limit(&mut self) {
+        self.move_limit = cycle(&MOVE_LIMITS, self.move_limit, 1);
+    }
+
+    pub fn prev_move_limit(&mut self) {
+        self.move_limit = cycle(&MOVE_LIMITS, self.move_limit, MOVE_LIMITS.len() - 1);
+    }
+}
+
+impl Default for DrawRules {
+    fn default() -> Self {
+        Self {
+            repetitions: Some(3),
+            move_limit: Some(200),
+        }
+    }
+}
+
+/// Name of a draw limit in the settings and the saved game: the number or `off`
+pub fn limit_name<T: ToString>(limit: Option<T>) -> String {
+    match limit {
+        Some(limit) => limit.to_string(),
+        None => "off".to_owned(),
+    }
+}
+
+/// Reads a draw limit written by `limit_name`. A limit must be above zero
+pub fn parse_limit<T: FromStr + Default + PartialOrd>(name: &str) -> Result<Option<T>, String> {
+    if name == "off" {
+        return Ok(None);
+    }
+    match name.parse::<T>() {
+        Ok(limit) if limit > T::default() => Ok(Some(limit)),
+        _ => Err(format!("Unknown draw limit {}", name)),
+    }
+}
+
+/// Takes the option `step` places after `value`, a value outside of the options starts the cycle
+fn cycle<T: Copy + PartialEq>(options: &[T], value: T, step: usize) -> T {
+    let idx = options.iter().position(|v| *v == value).unwrap_or(0);
+    options[(idx + step) % options.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_through_the_limits() {
+        let mut draw_rules = DrawRules::default();
+        draw_rules.next_repetitions();
+        assert_eq!(draw_rules.repetitions, Some(4));
+        draw_rules.prev_repetitions();
+        draw_rules.prev_repetitions();
+        draw_rules.prev_repetitions();
+        assert_eq!(draw_rules.repetitions, None);
+        draw_rules.prev_repetitions();
+        assert_eq!(draw_rules.repetitions, Some(5));
+
+        draw_rules.next_move_limit();
+        draw_rules.next_move_limit();
+        draw_rules.next_move_limit();
+        assert_eq!(draw_rules.move_limit, None);
+
+        // a limit which is not in the menu starts the cycle again
+        draw_rules.move_limit = Some(42);
+        draw_rules.next_move_limit();
+        assert_eq!(draw_rules.move_limit, Some(50));
+    }
+
+    #[test]
+    fn limits_survive_a_round_trip() {
+        for limit in [None, Some(3u8)] {
+            assert_eq!(parse_limit::<u8>(&limit_name(limit)), Ok(limit));
+        }
+        for limit in [None, Some(200u16)] {
+            assert_eq!(parse_limit::<u16>(&limit_name(limit)), Ok(limit));
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_limits() {
+        assert!(parse_limit::<u8>("0").is_err());
+        assert!(parse_limit::<u8>("-1").is_err());
+        assert!(parse_limit::<u8>("300").is_err());
+        assert!(parse_limit::<u16>("never").is_err());
+        assert!(parse_limit::<u16>("").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+use std::{fs, time::Duration};
+
+use bevy::log;
+
+/// File in the working directory with the external engines, one per line:
+/// `<name> = <command> [arguments...]`. Lines starting with `#` are comments
+pub const ENGINES_FILE: &str = "engines.txt";
+// Thinking time of the engines for one move
+const DEFAULT_MOVE_TIME: Duration = Duration::from_millis(1000);
+
+/// Executable which is played by the `External` player
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineConfig {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+}
+
+impl EngineConfig {
+    /// Reads a `<name> = <command> [arguments...]` line
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let (name, command) = line
+            .split_once('=')
+            .ok_or_else(|| format!("Expected <name> = <command>, got {}", line))?;
+
+        let mut words = command.split_whitespace().map(str::to_owned);
+        let command = words
+            .next()
+            .ok_or_else(|| format!("Engine {} has no command", name.trim()))?;
+
+        Ok(Self {
+            name: name.trim().to_owned(),
+            command,
+            args: words.collect(),
+        })
+    }
+}
+
+/// External engines configured in `ENGINES_FILE`. Must be used as a resource
+#[derive(Debug, Clone)]
+pub struct EngineOptions {
+    pub engines: Vec<EngineConfig>,
+    /// Index of the engine selected in the main menu
+    pub selected: usize,
+    pub move_time: Duration,
+}
+
+impl EngineOptions {
+    /// Loads the engines from `ENGINES_FILE`. A missing file means there are no engines
+    pub fn load() -> Self {
+        let mut options = Self::default();
+
+        let content = match fs::read_to_string(ENGINES_FILE) {
+            Ok(content) => content,
+            Err(e) => {
+                log::info!(
+                    "No external engines are loaded from {}: {}",
+                    ENGINES_FILE,
+                    e
+                );
+                return options;
+            }
+        };
+
+        for line in content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            match EngineConfig::parse(line) {
+                Ok(engine) => options.engines.push(engine),
+                Err(e) => log::warn!("Skipping an engine in {}: {}", ENGINES_FILE, e),
+            }
+        }
+        log::info!("Loaded {} external engines", options.engines.len());
+
+        options
+    }
+
+    #[inline]
+    pub fn selected_engine(&self) -> Option<&EngineConfig> {
+        self.engines.get(self.selected)
+    }
+
+    /// Selects the engine by its name, the selection is kept if there is no such engine
+    pub fn select(&mut self, name: &str) {
+        if let Some(idx) = self.engines.iter().position(|engine| engine.name == name) {
+            self.selected = idx;
+        }
+    }
+
+    pub fn next(&mut self) {
+        if !self.engines.is_empty() {
+            self.selected = (self.selected + 1) % self.engines.len();
+        }
+    }
+
+    pub fn prev(&mut self) {
+        if !self.engines.is_empty() {
+            self.selected = (self.selected + self.engines.len() - 1) % self.engines.len();
+        }
+    }
+}
+
+impl Default for EngineOptions {
+    fn default() -> Self {
+        Self {
+            engines: vec![],
+            selected: 0,
+            move_time: DEFAULT_MOVE_TIME,
+        }
+    }
+}
+
+impl ToString for EngineOptions {
+    fn to_string(&self) -> String {
+        match self.selected_engine() {
+            Some(engine) => engine.name.clone(),
+            None => "None".to_owned(),
+        }
+    }
+}
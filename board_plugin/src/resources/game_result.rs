@@ -0,0 +1,73 @@
+use super::game_state::PlayerColor;
+
+/// How the game has ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEndReason {
+    /// The master is captured or has reached the enemy temple
+    Move,
+    /// The position is a draw by the rules
+    Tie,
+    Timeout,
+    Resignation,
+    /// The player could not continue, e.g. the external engine has crashed
+    Forfeit(String),
+    /// Both players have agreed to a draw
+    Agreement,
+}
+
+/// Result of the finished game. Must be used as a resource
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameResult {
+    /// `None` for a draw
+    pub winner: Option<PlayerColor>,
+    pub reason: GameEndReason,
+}
+
+impl GameResult {
+    #[inline]
+    pub fn win(winner: PlayerColor, reason: GameEndReason) -> Self {
+        Self {
+            winner: Some(winner),
+            reason,
+        }
+    }
+
+    #[inline]
+    pub fn draw(reason: GameEndReason) -> Self {
+        Self {
+            winner: None,
+            reason,
+        }
+    }
+}
+
+impl ToString for GameResult {
+    fn to_string(&self) -> String {
+        let winner = match self.winner {
+            Some(winner) => winner,
+            None if self.reason == GameEndReason::Agreement => {
+                return "Draw by agreement!".to_owned()
+            }
+            None => return "It is a tie!".to_owned(),
+        };
+        let loser = winner.enemy();
+
+        match &self.reason {
+            GameEndReason::Timeout => format!(
+                "{} has run out of time! {} has won!",
+                loser.name(),
+                winner.name()
+            ),
+            GameEndReason::Resignation => {
+                format!("{} has resigned! {} has won!", loser.name(), winner.name())
+            }
+            GameEndReason::Forfeit(reason) => format!(
+                "{} forfeits: {}. {} has won!",
+                loser.name(),
+                reason,
+                winner.name()
+            ),
+            _ => format!("{} has won!", winner.name()),
+        }
+    }
+}
@@ -1,7 +1,13 @@
 use crate::ai::agent::Agent;
+use crate::rules::rule_set::RuleSet;
+
+use super::deck::{Deck, NEUTRAL_CARD_IDX};
+use super::handicap::Handicap;
+use super::position_history::{Position, PositionHistory};
+use super::tile_map::TileMap;
 
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PlayerColor {
     Red,
     Blue,
@@ -23,6 +29,23 @@ impl PlayerColor {
             PlayerColor::Blue => PlayerColor::Red,
         }
     }
+
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlayerColor::Red => "Red",
+            PlayerColor::Blue => "Blue",
+        }
+    }
+
+    /// Index of the player in `GameState::players`
+    #[inline]
+    fn player_idx(&self) -> usize {
+        match self {
+            PlayerColor::Red => 0,
+            PlayerColor::Blue => 1,
+        }
+    }
 }
 
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
@@ -31,6 +54,35 @@ pub enum PlayerType {
     Human,
     Random,
     AlphaBeta,
+    /// Player on the other side of the network connection
+    Remote,
+    /// Engine program selected in the main menu
+    External,
+}
+
+impl PlayerType {
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlayerType::Human => "Human",
+            PlayerType::Random => "Random",
+            PlayerType::AlphaBeta => "AlphaBeta",
+            PlayerType::Remote => "Remote",
+            PlayerType::External => "External",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            PlayerType::Human,
+            PlayerType::Random,
+            PlayerType::AlphaBeta,
+            PlayerType::Remote,
+            PlayerType::External,
+        ]
+        .into_iter()
+        .find(|player_type| player_type.name() == name)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -45,23 +97,50 @@ pub struct GameState {
     pub turn: u16,
     pub current_player_idx: usize,
     pub curr_color: PlayerColor,
+    /// Player who made the first move of the game
+    pub first_color: PlayerColor,
+    pub handicap: Handicap,
+    pub rules: Box<dyn RuleSet>,
+    pub history: PositionHistory,
 }
 
 impl GameState {
-    pub fn new(red_player: Player, blue_player: Player) -> Self {
+    pub fn new(
+        red_player: Player,
+        blue_player: Player,
+        handicap: Handicap,
+        rules: Box<dyn RuleSet>,
+    ) -> Self {
         Self {
             players: [red_player, blue_player],
             turn: 0,
             current_player_idx: 0,
             curr_color: PlayerColor::Red,
+            first_color: PlayerColor::Red,
+            handicap,
+            rules,
+            history: PositionHistory::new(),
         }
     }
 
     #[inline]
     pub fn clear(&mut self) {
         self.turn = 0;
-        self.current_player_idx = 0;
-        self.curr_color = PlayerColor::Red;
+        self.current_player_idx = self.first_color.player_idx();
+        self.curr_color = self.first_color;
+        self.history.clear();
+    }
+
+    /// Picks the first player from the neutral card of the dealt deck according to the rules
+    pub fn set_first_player(&mut self, deck: &Deck) {
+        let neutral_card = &deck
+            .cardboards
+            .get(&deck.cards[NEUTRAL_CARD_IDX])
+            .unwrap()
+            .card;
+        self.first_color = self.rules.first_player(neutral_card);
+        self.curr_color = self.first_color;
+        self.current_player_idx = self.first_color.player_idx();
     }
 
     #[inline]
@@ -69,6 +148,36 @@ impl GameState {
         &self.players[self.current_player_idx]
     }
 
+    #[inline]
+    pub fn get_player(&self, color: &PlayerColor) -> &Player {
+        &self.players[color.player_idx()]
+    }
+
+    /// Nobody plays on this machine, the game is only watched
+    #[inline]
+    pub fn is_spectating(&self) -> bool {
+        self.players
+            .iter()
+            .all(|player| player.player_type == PlayerType::Remote)
+    }
+
+    /// Continues a saved game, where `turn` moves are already made
+    pub fn restore_turn(&mut self, turn: u16, curr_color: PlayerColor, first_color: PlayerColor) {
+        self.turn = turn;
+        self.first_color = first_color;
+        self.curr_color = curr_color;
+        self.current_player_idx = curr_color.player_idx();
+    }
+
+    /// Player on this machine who uses the game buttons: the side to move if it is a human,
+    /// otherwise the human waiting for a bot or a remote move
+    pub fn acting_human(&self) -> Option<PlayerColor> {
+        let curr_color = self.curr_color;
+        [curr_color, curr_color.enemy()]
+            .into_iter()
+            .find(|color| self.get_player(color).player_type == PlayerType::Human)
+    }
+
     #[inline]
     pub fn next_turn(&mut self) {
         self.turn += 1;
@@ -82,4 +191,22 @@ impl GameState {
         self.curr_color.switch();
         self.current_player_idx = (self.current_player_idx + 1) % 2;
     }
+
+    /// Records the position with the current player to move
+    #[inline]
+    pub fn push_position(&mut self, tile_map: &TileMap, deck: &Deck) {
+        self.history
+            .push(Position::new(tile_map, deck, self.curr_color));
+    }
+
+    #[inline]
+    pub fn pop_position(&mut self) {
+        self.history.pop();
+    }
+
+    /// Checks if the last recorded position, reached after `moves` moves, is a draw
+    #[inline]
+    pub fn is_draw(&self, moves: u16) -> bool {
+        self.rules.is_draw(&self.history, moves)
+    }
 }
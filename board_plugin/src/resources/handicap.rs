@@ -0,0 +1,108 @@
+use super::game_state::PlayerColor;
+
+// Handicaps in the order they are cycled through in the main menu
+const HANDICAPS: [Handicap; 7] = [
+    Handicap::None,
+    Handicap::Pawns {
+        stronger: PlayerColor::Red,
+        amount: 1,
+    },
+    Handicap::Pawns {
+        stronger: PlayerColor::Red,
+        amount: 2,
+    },
+    Handicap::Pawns {
+        stronger: PlayerColor::Blue,
+        amount: 1,
+    },
+    Handicap::Pawns {
+        stronger: PlayerColor::Blue,
+        amount: 2,
+    },
+    Handicap::CardChoice {
+        weaker: PlayerColor::Red,
+    },
+    Handicap::CardChoice {
+        weaker: PlayerColor::Blue,
+    },
+];
+
+/// Handicap given to the weaker side. Must be used as a resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handicap {
+    None,
+    /// The stronger side starts without `amount` pawns on its back rank
+    Pawns {
+        stronger: PlayerColor,
+        amount: u8,
+    },
+    /// The weaker side picks the first card in the draft, a game with it always starts with the draft
+    CardChoice {
+        weaker: PlayerColor,
+    },
+}
+
+impl Handicap {
+    pub fn next(&mut self) {
+        let idx = self.index();
+        *self = HANDICAPS[(idx + 1) % HANDICAPS.len()];
+    }
+
+    pub fn prev(&mut self) {
+        let idx = self.index();
+        *self = HANDICAPS[(idx + HANDICAPS.len() - 1) % HANDICAPS.len()];
+    }
+
+    /// Returns the player who picks the first card in the draft
+    #[inline]
+    pub fn first_card_choice(&self) -> PlayerColor {
+        match self {
+            Handicap::CardChoice { weaker } => *weaker,
+            _ => PlayerColor::Red,
+        }
+    }
+
+    /// The card choice is given in the draft, so the game starts with the draft even if it is off
+    #[inline]
+    pub fn needs_draft(&self) -> bool {
+        matches!(self, Handicap::CardChoice { .. })
+    }
+
+    /// Finds the handicap by the name shown in the main menu
+    pub fn from_name(name: &str) -> Option<Self> {
+        HANDICAPS
+            .iter()
+            .copied()
+            .find(|handicap| handicap.to_string() == name)
+    }
+
+    fn index(&self) -> usize {
+        HANDICAPS.iter().position(|h| h == self).unwrap_or(0)
+    }
+}
+
+impl Default for Handicap {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl ToString for Handicap {
+    fn to_string(&self) -> String {
+        let color = |color: &PlayerColor| match color {
+            PlayerColor::Red => "Red",
+            PlayerColor::Blue => "Blue",
+        };
+
+        match self {
+            Handicap::None => "None".to_owned(),
+            Handicap::Pawns { stronger, amount } => format!(
+                "{} -{} pawn{}",
+                color(stronger),
+                amount,
+                if *amount > 1 { "s" } else { "" }
+            ),
+            Handicap::CardChoice { weaker } => format!("{} picks cards first", color(weaker)),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use bevy::prelude::Entity;
+
+use super::tile_map::Move;
+
+/// Move made on the previous turn, it stays highlighted on the board.
+/// Must be used as a resource, it is absent before the first move
+#[derive(Debug, Clone, Copy)]
+pub struct LastMove {
+    pub mov: Move,
+    /// Card played for the move
+    pub card: Entity,
+}
@@ -0,0 +1,207 @@
+use bevy::prelude::*;
+
+/// Height of the button bar at the bottom of the window
+pub const BUTTON_BAR_HEIGHT: f32 = 50.;
+
+/// Placement of the board, the deck and the texts in the window. Must be used as a resource.
+///
+/// The board and the cards are spawned with the tile sizes from the options and scaled
+/// until they fill the window. The layout is computed again when the window is resized
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    pub window_size: Vec2,
+    /// Size of the board when it is spawned
+    pub board_size: Vec2,
+    /// Size of the cards around the neutral card when they are spawned
+    pub deck_size: Vec2,
+    /// Scale of the board, the cards and the texts from their spawned sizes
+    pub scale: f32,
+    /// Bottom left corner of the board
+    pub board_position: Vec2,
+    /// Center of the neutral card, the rest of the cards keep their offsets from it
+    pub deck_position: Vec2,
+    /// Center of the guide text
+    pub guide_text_position: Vec2,
+    /// Center of the turn text, the evaluation and the clocks are under it
+    pub turn_text_position: Vec2,
+    /// The deck is under the board in a portrait window and next to it otherwise
+    pub is_portrait: bool,
+}
+
+impl Layout {
+    pub fn new(window_size: Vec2, board_size: Vec2, deck_size: Vec2) -> Self {
+        let is_portrait = window_size.y > window_size.x;
+
+        // the texts are above the board and the buttons are under it
+        let header = match is_portrait {
+            true => window_size.y / 5.,
+            false => window_size.y / 6.,
+        };
+        let area = Vec2::new(
+            window_size.x * 0.95,
+            window_size.y - header - BUTTON_BAR_HEIGHT,
+        );
+        let area_center = Vec2::new(0., (BUTTON_BAR_HEIGHT - header) / 2.);
+
+        let gap = board_size.x / 4.;
+        let content = match is_portrait {
+            true => Vec2::new(
+                board_size.x.max(deck_size.x),
+                board_size.y + gap + deck_size.y,
+            ),
+            false => Vec2::new(
+                board_size.x + gap + deck_size.x,
+                board_size.y.max(deck_size.y),
+            ),
+        };
+        // large windows are filled too, the tiles are only kept from vanishing in tiny ones
+        let scale = (area / content).min_element().max(0.1);
+
+        let corner = area_center - content * scale / 2.;
+        let (board_position, deck_position) = match is_portrait {
+            true => (
+                Vec2::new(
+                    -board_size.x * scale / 2.,
+                    corner.y + (deck_size.y + gap) * scale,
+                ),
+                Vec2::new(0., corner.y + deck_size.y * scale / 2.),
+            ),
+            false => (
+                Vec2::new(corner.x, area_center.y - board_size.y * scale / 2.),
+                Vec2::new(
+                    corner.x + (board_size.x + gap + deck_size.x / 2.) * scale,
+                    area_center.y,
+                ),
+            ),
+        };
+
+        // the guide text is under the turn text in a narrow window
+        let top = window_size.y / 2.;
+        let turn_text_position = Vec2::new(-window_size.x * 3. / 8., top - window_size.y / 12.);
+        let guide_text_position = match is_portrait {
+            true => Vec2::new(0., top - header * 0.8),
+            false => Vec2::new(0., turn_text_position.y),
+        };
+
+        Self {
+            window_size,
+            board_size,
+            deck_size,
+            scale,
+            board_position,
+            deck_position,
+            guide_text_position,
+            turn_text_position,
+            is_portrait,
+        }
+    }
+
+    /// Center of the evaluation text under the turn text
+    pub fn evaluation_text_position(&self, turn_text_size: f32) -> Vec2 {
+        self.turn_text_position + Vec2::new(10., -turn_text_size) * self.scale
+    }
+
+    /// Center of the clock text under the evaluation text
+    pub fn clock_text_position(&self, turn_text_size: f32) -> Vec2 {
+        self.turn_text_position + Vec2::new(0., -3.5 * turn_text_size) * self.scale
+    }
+
+    /// Same layout for another window size
+    pub fn resized(&self, window_size: Vec2) -> Self {
+        Self::new(window_size, self.board_size, self.deck_size)
+    }
+
+    /// Moves a point placed by `old` layout around the deck to this layout
+    pub fn deck_point(&self, old: &Layout, point: Vec2) -> Vec2 {
+        self.deck_position + (point - old.deck_position) * self.scale / old.scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD_SIZE: [f32; 2] = [500., 500.];
+    const DECK_SIZE: [f32; 2] = [300., 600.];
+    // the content touches the edges of the area it is fitted to
+    const EPSILON: f32 = 1e-3;
+
+    // corners of the board and the deck from the bottom left to the top right
+    fn bounds(layout: &Layout) -> [Vec2; 4] {
+        let deck_half = Vec2::from(DECK_SIZE) * layout.scale / 2.;
+        [
+            layout.board_position,
+            layout.board_position + Vec2::from(BOARD_SIZE) * layout.scale,
+            layout.deck_position - deck_half,
+            layout.deck_position + deck_half,
+        ]
+    }
+
+    fn assert_fits_the_window(layout: &Layout) {
+        let max = layout.window_size / 2. + EPSILON;
+        let min = -max + Vec2::new(0., BUTTON_BAR_HEIGHT);
+        for corner in bounds(layout) {
+            assert!(corner.cmpge(min).all() && corner.cmple(max).all());
+        }
+        assert!(layout.guide_text_position.y < max.y);
+        assert!(layout.turn_text_position.y < max.y);
+    }
+
+    #[test]
+    fn places_the_deck_next_to_the_board_in_a_landscape_window() {
+        let layout = Layout::new(Vec2::new(1280., 720.), BOARD_SIZE.into(), DECK_SIZE.into());
+        assert!(!layout.is_portrait);
+        assert!(layout.scale < 1.);
+        assert_fits_the_window(&layout);
+
+        let [_, board_top_right, deck_bottom_left, _] = bounds(&layout);
+        assert!(deck_bottom_left.x > board_top_right.x);
+        assert_eq!(layout.guide_text_position.y, layout.turn_text_position.y);
+    }
+
+    #[test]
+    fn places_the_deck_under_the_board_in_a_portrait_window() {
+        let layout = Layout::new(Vec2::new(720., 1280.), BOARD_SIZE.into(), DECK_SIZE.into());
+        assert!(layout.is_portrait);
+        assert_fits_the_window(&layout);
+
+        let [board_bottom_left, board_top_right, _, deck_top_right] = bounds(&layout);
+        assert!(deck_top_right.y < board_bottom_left.y);
+        assert_eq!(layout.deck_position.x, 0.);
+        assert!((board_bottom_left.x + board_top_right.x).abs() < EPSILON);
+        assert!(layout.guide_text_position.y < layout.turn_text_position.y);
+    }
+
+    #[test]
+    fn fills_a_window_larger_than_the_default() {
+        let default = Layout::new(Vec2::new(1320., 940.), BOARD_SIZE.into(), DECK_SIZE.into());
+        let layout = default.resized(Vec2::new(2640., 1880.));
+        assert!(layout.scale > 2.);
+        assert!(layout.scale > default.scale * 2.);
+        assert_fits_the_window(&layout);
+
+        let layout = layout.resized(Vec2::new(4000., 3000.));
+        assert!(layout.scale > 4.);
+        assert_fits_the_window(&layout);
+    }
+
+    #[test]
+    fn keeps_a_minimum_scale() {
+        let layout = Layout::new(Vec2::new(40., 30.), BOARD_SIZE.into(), DECK_SIZE.into());
+        assert_eq!(layout.scale, 0.1);
+    }
+
+    #[test]
+    fn moves_the_deck_points_with_the_layout() {
+        let old = Layout::new(Vec2::new(1280., 720.), BOARD_SIZE.into(), DECK_SIZE.into());
+        let new = old.resized(Vec2::new(720., 1280.));
+
+        assert_eq!(new.deck_point(&old, old.deck_position), new.deck_position);
+
+        let point = old.deck_position + Vec2::new(0., DECK_SIZE[1] / 2.) * old.scale;
+        let moved = new.deck_point(&old, point);
+        let expected = new.deck_position + Vec2::new(0., DECK_SIZE[1] / 2.) * new.scale;
+        assert!(moved.abs_diff_eq(expected, EPSILON));
+        assert!(old.deck_point(&new, moved).abs_diff_eq(point, EPSILON));
+    }
+}
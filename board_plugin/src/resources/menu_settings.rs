@@ -0,0 +1,232 @@
+use bevy::log;
+
+use crate::{rules::rule_set::RuleSetType, storage};
+
+use super::{
+    card::CARDS,
+    depth::{Depth, MAX_DEPTH, MIN_DEPTH},
+    draft_options::{DraftOptions, MAX_POOL_SIZE, MIN_POOL_SIZE},
+    draw_rules::{limit_name, parse_limit, DrawRules},
+    game_state::PlayerType,
+    handicap::Handicap,
+    palette::Palette,
+    time_control::TimeControl,
+};
+
+/// Name of the settings in the storage. Every line is `<key> <value>`, see `MenuSettings::save`
+pub const SETTINGS_FILE: &str = "settings.txt";
+
+/// Options of the main menu kept between the launches. Must be used as a resource,
+/// the menu restores them when it is opened and saves every change
+#[derive(Debug, Clone, PartialEq)]
+pub struct MenuSettings {
+    pub red_player: PlayerType,
+    pub blue_player: PlayerType,
+    pub depth: u8,
+    pub handicap: Handicap,
+    pub time_control: TimeControl,
+    pub rule_set_type: RuleSetType,
+    pub draw_rules: DrawRules,
+    pub draft: bool,
+    /// Amount of the cards in the draft pool
+    pub draft_pool: u8,
+    /// Letters and numbers are printed along the board
+    pub coordinates: bool,
+    /// Name of the engine played by the External player
+    pub engine: Option<String>,
+    /// File name of the theme without the extension
+    pub theme: Option<String>,
+    pub palette: Palette,
+    /// Indices in `CARDS` in the order of the selection
+    pub selected_cards: Vec<u8>,
+}
+
+impl MenuSettings {
+    /// Loads the settings from `SETTINGS_FILE`. Broken lines are skipped and keep the defaults
+    pub fn load() -> Self {
+        let mut settings = Self::default();
+
+        let content = match storage::load(SETTINGS_FILE) {
+            Ok(Some(content)) => content,
+            Ok(None) => return settings,
+            Err(e) => {
+                log::warn!("Settings are not loaded: {}", e);
+                return settings;
+            }
+        };
+
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            if let Err(e) = settings.parse_line(line) {
+                log::warn!("Skipping a line in {}: {}", SETTINGS_FILE, e);
+            }
+        }
+        log::info!("Loaded settings: {:?}", settings);
+
+        settings
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), String> {
+        let (key, value) = line
+            .split_once(' ')
+            .ok_or_else(|| format!("Expected a value in {}", line))?;
+        let value = value.trim();
+        let unknown = || format!("Unknown {} {}", key, value);
+
+        match key {
+            "red" => self.red_player = PlayerType::from_name(value).ok_or_else(unknown)?,
+            "blue" => self.blue_player = PlayerType::from_name(value).ok_or_else(unknown)?,
+            "depth" => {
+                self.depth = value
+                    .parse()
+                    .ok()
+                    .filter(|depth| (MIN_DEPTH..=MAX_DEPTH).contains(depth))
+                    .ok_or_else(unknown)?
+            }
+            "handicap" => self.handicap = Handicap::from_name(value).ok_or_else(unknown)?,
+            "clock" => self.time_control = TimeControl::from_name(value).ok_or_else(unknown)?,
+            "rules" => self.rule_set_type = RuleSetType::from_name(value).ok_or_else(unknown)?,
+            "repetitions" => self.draw_rules.repetitions = parse_limit(value)?,
+            "move_limit" => self.draw_rules.move_limit = parse_limit(value)?,
+            "draft" => {
+                self.draft = match value {
+                    "on" => true,
+                    "off" => false,
+                    _ => return Err(unknown()),
+                }
+            }
+            "draft_pool" => {
+                self.draft_pool = value
+                    .parse()
+                    .ok()
+                    .filter(|size| (MIN_POOL_SIZE..=MAX_POOL_SIZE).contains(size))
+                    .ok_or_else(unknown)?
+            }
+            "coordinates" => {
+                self.coordinates = match value {
+                    "on" => true,
+                    "off" => false,
+                    _ => return Err(unknown()),
+                }
+            }
+            "engine" => self.engine = Some(value.to_owned()),
+            "theme" => self.theme = Some(value.to_owned()),
+            "palette" => self.palette = Palette::from_name(value).ok_or_else(unknown)?,
+            "cards" => {
+                let mut cards = Vec::with_capacity(5);
+                for name in value.split_whitespace().take(5) {
+                    let idx = CARDS
+                        .iter()
+                        .position(|card| card.name == name)
+                        .ok_or_else(|| format!("Unknown card {}", name))?
+                        as u8;
+                    if !cards.contains(&idx) {
+                        cards.push(idx);
+                    }
+                }
+                self.selected_cards = cards;
+            }
+            _ => return Err(format!("Unknown entry {}", line)),
+        }
+        Ok(())
+    }
+
+    /// Writes the settings to `SETTINGS_FILE`
+    pub fn save(&self) {
+        let mut lines = vec![
+            format!("red {}", self.red_player.name()),
+            format!("blue {}", self.blue_player.name()),
+            format!("depth {}", self.depth),
+            format!("handicap {}", self.handicap.to_string()),
+            format!("clock {}", self.time_control.to_string()),
+            format!("rules {}", self.rule_set_type.to_string()),
+            format!("repetitions {}", limit_name(self.draw_rules.repetitions)),
+            format!("move_limit {}", limit_name(self.draw_rules.move_limit)),
+            format!("draft {}", if self.draft { "on" } else { "off" }),
+            format!("draft_pool {}", self.draft_pool),
+            format!(
+                "coordinates {}",
+                if self.coordinates { "on" } else { "off" }
+            ),
+            format!("palette {}", self.palette.to_string()),
+        ];
+        if let Some(engine) = &self.engine {
+            lines.push(format!("engine {}", engine));
+        }
+        if let Some(theme) = &self.theme {
+            lines.push(format!("theme {}", theme));
+        }
+        if !self.selected_cards.is_empty() {
+            let cards = self
+                .selected_cards
+                .iter()
+                .map(|idx| CARDS[*idx as usize].name)
+                .collect::<Vec<_>>()
+                .join(" ");
+            lines.push(format!("cards {}", cards));
+        }
+
+        if let Err(e) = storage::save(SETTINGS_FILE, &(lines.join("\n") + "\n")) {
+            log::warn!("Settings are not saved: {}", e);
+        }
+    }
+}
+
+impl Default for MenuSettings {
+    fn default() -> Self {
+        Self {
+            red_player: PlayerType::Human,
+            blue_player: PlayerType::Human,
+            depth: Depth::default().0,
+            handicap: Handicap::default(),
+            time_control: TimeControl::default(),
+            rule_set_type: RuleSetType::default(),
+            draw_rules: DrawRules::default(),
+            draft: false,
+            draft_pool: DraftOptions::default().pool_size(),
+            coordinates: true,
+            engine: None,
+            theme: None,
+            palette: Palette::default(),
+            selected_cards: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_draft_and_the_draw_rules() {
+        let mut settings = MenuSettings::default();
+        for line in [
+            "draft on",
+            "draft_pool 9",
+            "repetitions off",
+            "move_limit 100",
+        ] {
+            settings.parse_line(line).unwrap();
+        }
+
+        assert!(settings.draft);
+        assert_eq!(settings.draft_pool, 9);
+        assert_eq!(settings.draw_rules.repetitions, None);
+        assert_eq!(settings.draw_rules.move_limit, Some(100));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        let mut settings = MenuSettings::default();
+        for line in [
+            "draft_pool 4",
+            "draft_pool 17",
+            "draft_pool many",
+            "repetitions 0",
+            "move_limit",
+            "unknown 1",
+        ] {
+            assert!(settings.parse_line(line).is_err(), "{}", line);
+        }
+        assert_eq!(settings, MenuSettings::default());
+    }
+}
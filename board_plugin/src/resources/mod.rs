@@ -12,3 +12,26 @@ pub mod app_state;
 pub mod text_handler;
 pub mod physical_deck;
 pub mod depth;
+pub mod handicap;
+pub mod position_history;
+pub mod draw_rules;
+pub mod draft;
+pub mod draft_options;
+pub mod network_options;
+pub mod engine_options;
+pub mod time_control;
+pub mod clocks;
+pub mod draw_offer;
+pub mod game_result;
+pub mod statistics;
+pub mod saved_game;
+pub mod menu_settings;
+pub mod pending_turn;
+pub mod dragged_piece;
+pub mod undo_history;
+pub mod last_move;
+pub mod theme;
+pub mod theme_options;
+pub mod palette;
+pub mod layout;
+pub mod pointer;
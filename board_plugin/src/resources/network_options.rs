@@ -0,0 +1,21 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:7777";
+// browsers can not open TCP sockets, so the web build plays through the game server
+#[cfg(target_arch = "wasm32")]
+pub const DEFAULT_ADDRESS: &str = "ws://127.0.0.1:9001";
+
+/// Network game options. Must be used as a resource
+#[derive(Debug, Clone)]
+pub struct NetworkOptions {
+    /// Address to host a game on or to join a game at.
+    /// `ws://host:port` creates a room on the game server, `ws://host:port/room` joins it
+    pub address: String,
+}
+
+impl Default for NetworkOptions {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_ADDRESS.to_owned(),
+        }
+    }
+}
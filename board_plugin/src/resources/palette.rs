@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+
+use super::board_assets::{BoardAssets, SpriteMaterial};
+
+// Palettes in the order they are cycled through in the main menu
+const PALETTES: [Palette; 4] = [
+    Palette::Standard,
+    Palette::Deuteranopia,
+    Palette::Protanopia,
+    Palette::Tritanopia,
+];
+
+/// Colors of the sides and the highlights told apart with color blindness.
+/// The palettes besides `Standard` draw the pawns of the sides with different shapes
+/// and the highlighted tiles with patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Palette {
+    /// Colors of the selected theme
+    Standard,
+    /// Red and green look alike
+    Deuteranopia,
+    /// Red and green look alike, red looks dark
+    Protanopia,
+    /// Blue and green, yellow and violet look alike
+    Tritanopia,
+}
+
+/// Colors of a palette, hex as in the themes
+struct PaletteColors {
+    blue: &'static str,
+    red: &'static str,
+    selected_blue_card: &'static str,
+    selected_red_card: &'static str,
+    selected_neutral_card: &'static str,
+    selected_piece: &'static str,
+    allowed_move: &'static str,
+    first_card_move: &'static str,
+    second_card_move: &'static str,
+    both_cards_move: &'static str,
+}
+
+impl Palette {
+    pub fn next(&mut self) {
+        let idx = PALETTES.iter().position(|p| p == self).unwrap_or(0);
+        *self = PALETTES[(idx + 1) % PALETTES.len()];
+    }
+
+    /// Finds the palette by the name shown in the main menu
+    pub fn from_name(name: &str) -> Option<Self> {
+        PALETTES
+            .iter()
+            .copied()
+            .find(|palette| palette.to_string() == name)
+    }
+
+    /// Recolors the assets skinned by the theme. The textures of the pawns and the highlights
+    /// are replaced, so the palette is applied after the theme
+    pub fn apply(&self, board_assets: &mut BoardAssets, asset_server: &AssetServer) {
+        // the colors are taken from the palette of Okabe and Ito
+        let colors = match self {
+            Palette::Standard => return,
+            Palette::Deuteranopia => PaletteColors {
+                blue: "0072b2",
+                red: "d55e00",
+                selected_blue_card: "56b4e9",
+                selected_red_card: "d55e00",
+                selected_neutral_card: "f0e442",
+                selected_piece: "f0e442",
+                allowed_move: "56b4e9",
+                first_card_move: "e69f00",
+                second_card_move: "56b4e9",
+                both_cards_move: "f0e442",
+            },
+            Palette::Protanopia => PaletteColors {
+                blue: "0072b2",
+                red: "e69f00",
+                selected_blue_card: "56b4e9",
+                selected_red_card: "e69f00",
+                selected_neutral_card: "f0e442",
+                selected_piece: "f0e442",
+                allowed_move: "56b4e9",
+                first_card_move: "e69f00",
+                second_card_move: "56b4e9",
+                both_cards_move: "f0e442",
+            },
+            Palette::Tritanopia => PaletteColors {
+                blue: "0072b2",
+                red: "d55e00",
+                selected_blue_card: "56b4e9",
+                selected_red_card: "cc79a7",
+                selected_neutral_card: "f0f0f0",
+                selected_piece: "f0f0f0",
+                allowed_move: "009e73",
+                first_card_move: "cc79a7",
+                second_card_move: "009e73",
+                both_cards_move: "f0f0f0",
+            },
+        };
+
+        let color = |hex: &str| Color::hex(hex).unwrap();
+        let material = |hex: &str, sprite: &str| SpriteMaterial {
+            color: color(hex),
+            texture: asset_server.load(sprite),
+        };
+
+        board_assets.blue_pawn_material = material(colors.blue, "sprites/pawn_circle.png");
+        board_assets.red_pawn_material = material(colors.red, "sprites/pawn_triangle.png");
+        board_assets.blue_king_material = material(colors.blue, "sprites/star.png");
+        board_assets.red_king_material = material(colors.red, "sprites/star.png");
+
+        board_assets.selected_blue_card_material.color = color(colors.selected_blue_card);
+        board_assets.selected_red_card_material.color = color(colors.selected_red_card);
+        board_assets.selected_neutral_card_material.color = color(colors.selected_neutral_card);
+        board_assets.selected_piece_material.color = color(colors.selected_piece);
+
+        board_assets.allowed_move_tile_material =
+            material(colors.allowed_move, "sprites/pattern_dots.png");
+        board_assets.first_card_move_material =
+            material(colors.first_card_move, "sprites/pattern_stripes_up.png");
+        board_assets.second_card_move_material =
+            material(colors.second_card_move, "sprites/pattern_stripes_down.png");
+        board_assets.both_cards_move_material =
+            material(colors.both_cards_move, "sprites/pattern_crosshatch.png");
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::Standard
+    }
+}
+
+impl ToString for Palette {
+    fn to_string(&self) -> String {
+        match self {
+            Palette::Standard => "Standard",
+            Palette::Deuteranopia => "Deuteranopia",
+            Palette::Protanopia => "Protanopia",
+            Palette::Tritanopia => "Tritanopia",
+        }
+        .to_owned()
+    }
+}
@@ -0,0 +1,4 @@
+/// The move is made, but the turn is switched only after the pieces have finished moving.
+/// Must be used as a resource, the board does not take input while it is present
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PendingTurn;
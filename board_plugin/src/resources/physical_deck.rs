@@ -66,4 +66,20 @@ impl PhysicalDeck {
         // Reversing because red should get the cards that are in the end of the array
         self.cards.reverse();
     }
+
+    /// Takes the cards as they are laid out in the deck: two blue cards, neutral and two red cards
+    pub fn take_cards_in_deck_order(&mut self, indices: &[u8]) {
+        assert!(indices.len() == 5);
+        for index in indices.iter() {
+            self.cards.push(CARDS[*index as usize].clone());
+        }
+    }
+
+    /// Indices in `CARDS` of the taken cards in the deck order
+    pub fn indices(&self) -> Vec<u8> {
+        self.cards
+            .iter()
+            .map(|card| CARDS.iter().position(|c| c.name == card.name).unwrap() as u8)
+            .collect()
+    }
 }
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+/// The pointer is held when it stays pressed for this long
+pub const HOLD_DURATION: Duration = Duration::from_millis(500);
+/// Part of the size of a card or a tile a touch may miss it by
+pub const TOUCH_MARGIN: f32 = 0.15;
+
+/// The left mouse button or the first finger on a touch screen. Must be used as a resource,
+/// the input systems read the pointer instead of the mouse, so the game is played by touch alike
+#[derive(Debug, Clone, Default)]
+pub struct Pointer {
+    /// Position in the window with the origin in the bottom left corner like the cursor
+    pub position: Option<Vec2>,
+    pub is_pressed: bool,
+    pub just_pressed: bool,
+    pub just_released: bool,
+    /// The last press was a touch, the hit areas are larger then
+    pub is_touch: bool,
+    /// For how long the pointer is pressed
+    pub held_for: Duration,
+}
+
+impl Pointer {
+    #[inline]
+    pub fn is_held(&self) -> bool {
+        self.is_pressed && self.held_for >= HOLD_DURATION
+    }
+
+    /// Distance a press may miss a target of `size` by
+    #[inline]
+    pub fn hit_margin(&self, size: f32) -> f32 {
+        match self.is_touch {
+            true => size * TOUCH_MARGIN,
+            false => 0.,
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use bevy::utils::{AHashExt, HashMap};
+
+use super::deck::Deck;
+use super::game_state::PlayerColor;
+use super::tile::Tile;
+use super::tile_map::{TileMap, BOARD_SIZE};
+
+/// A snapshot of the game used to detect repetitions:
+/// pieces on the board, card arrangement and the side to move
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Position {
+    map: [[Tile; BOARD_SIZE]; BOARD_SIZE],
+    cards: [&'static str; 5],
+    curr_color: PlayerColor,
+}
+
+impl Position {
+    pub fn new(tile_map: &TileMap, deck: &Deck, curr_color: PlayerColor) -> Self {
+        let mut cards = [""; 5];
+        for (i, entity) in deck.cards.iter().enumerate() {
+            cards[i] = deck.cardboards.get(entity).unwrap().card.name;
+        }
+
+        Self {
+            map: tile_map.map,
+            cards,
+            curr_color,
+        }
+    }
+}
+
+/// All positions that occurred in the game
+#[derive(Debug, Clone)]
+pub struct PositionHistory {
+    counts: HashMap<Position, u8>,
+    // positions in the order they were played, so the search can undo them
+    positions: Vec<Position>,
+}
+
+impl PositionHistory {
+    pub fn new() -> Self {
+        Self {
+            counts: HashMap::with_capacity(256),
+            positions: Vec::with_capacity(256),
+        }
+    }
+
+    #[inline]
+    pub fn push(&mut self, position: Position) {
+        *self.counts.entry(position.clone()).or_insert(0) += 1;
+        self.positions.push(position);
+    }
+
+    #[inline]
+    pub fn pop(&mut self) {
+        if let Some(position) = self.positions.pop() {
+            if let Some(count) = self.counts.get_mut(&position) {
+                *count -= 1;
+                if *count == 0 {
+                    self.counts.remove(&position);
+                }
+            }
+        }
+    }
+
+    /// How many times the last played position has occurred
+    #[inline]
+    pub fn last_count(&self) -> u8 {
+        match self.positions.last() {
+            Some(position) => self.counts.get(position).copied().unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.clear();
+        self.positions.clear();
+    }
+}
+
+impl Default for PositionHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
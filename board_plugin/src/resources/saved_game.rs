@@ -0,0 +1,255 @@
+use bevy::log;
+
+use crate::{rules::rule_set::RuleSetType, storage};
+
+use super::{
+    board::Board,
+    card::CARDS,
+    deck::Deck,
+    depth::Depth,
+    draw_rules::{limit_name, parse_limit, DrawRules},
+    game_state::{GameState, PlayerColor, PlayerType},
+    handicap::Handicap,
+    tile_map::TileMap,
+};
+
+/// Name of the saved game in the storage. Every line is `<key> <value>`, see `SavedGame::to_text`
+pub const SAVED_GAME_FILE: &str = "saved_game.txt";
+
+/// Unfinished game which is continued from the main menu. `create_board` restores the game
+/// from it when it is present as a resource. The position history and the clocks are not kept,
+/// so the repetitions are counted and the clocks start again from the restored position
+#[derive(Debug, Clone)]
+pub struct SavedGame {
+    pub rule_set_type: RuleSetType,
+    pub draw_rules: DrawRules,
+    pub handicap: Handicap,
+    pub red_player: PlayerType,
+    pub blue_player: PlayerType,
+    pub depth: u8,
+    /// Amount of the moves made before the game was saved
+    pub turn: u16,
+    pub curr_color: PlayerColor,
+    pub first_color: PlayerColor,
+    pub tile_map: TileMap,
+    /// Indices in `CARDS` in the deck order: two blue cards, the neutral card and two red cards
+    pub cards: [u8; 5],
+    pub mirrored: [bool; 5],
+}
+
+/// Marks that the saved game belongs to the game on the board,
+/// so the save is removed when this game is finished
+#[derive(Debug, Clone, Copy)]
+pub struct SavedGameInUse;
+
+impl SavedGame {
+    pub fn new(
+        board: &Board,
+        deck: &Deck,
+        game_state: &GameState,
+        depth: &Depth,
+        rule_set_type: RuleSetType,
+    ) -> Self {
+        let mut cards = [0; 5];
+        let mut mirrored = [false; 5];
+        for (idx, entity) in deck.cards.iter().enumerate() {
+            let card = &deck.cardboards.get(entity).unwrap().card;
+            cards[idx] = CARDS.iter().position(|c| c.name == card.name).unwrap() as u8;
+            mirrored[idx] = card.is_mirrored;
+        }
+
+        Self {
+            rule_set_type,
+            draw_rules: *game_state.rules.draw_rules(),
+            handicap: game_state.handicap,
+            red_player: game_state.get_player(&PlayerColor::Red).player_type,
+            blue_player: game_state.get_player(&PlayerColor::Blue).player_type,
+            depth: depth.0,
+            turn: game_state.turn,
+            curr_color: game_state.curr_color,
+            first_color: game_state.first_color,
+            tile_map: board.tile_map,
+            cards,
+            mirrored,
+        }
+    }
+
+    /// Loads the game from `SAVED_GAME_FILE`, `None` if there is no saved game
+    pub fn load() -> Option<Self> {
+        let content = match storage::load(SAVED_GAME_FILE) {
+            Ok(content) => content?,
+            Err(e) => {
+                log::warn!("Saved game is not loaded: {}", e);
+                return None;
+            }
+        };
+
+        match Self::parse(&content) {
+            Ok(saved_game) => Some(saved_game),
+            Err(e) => {
+                log::warn!("Saved game is broken: {}", e);
+                None
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        storage::save(SAVED_GAME_FILE, &self.to_text())
+    }
+
+    /// Removes the saved game, e.g. when it has been played to the end
+    pub fn delete() {
+        if let Err(e) = storage::remove(SAVED_GAME_FILE) {
+            log::warn!("Saved game is not removed: {}", e);
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let cards = self
+            .cards
+            .iter()
+            .map(|idx| CARDS[*idx as usize].name)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mirrored = self
+            .mirrored
+            .iter()
+            .map(|is_mirrored| if *is_mirrored { "1" } else { "0" })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        [
+            format!("rules {}", self.rule_set_type.to_string()),
+            format!("repetitions {}", limit_name(self.draw_rules.repetitions)),
+            format!("move_limit {}", limit_name(self.draw_rules.move_limit)),
+            format!("handicap {}", self.handicap.to_string()),
+            format!("red {}", self.red_player.name()),
+            format!("blue {}", self.blue_player.name()),
+            format!("depth {}", self.depth),
+            format!("turn {}", self.turn),
+            format!("color {}", self.curr_color.name()),
+            format!("first {}", self.first_color.name()),
+            format!("board {}", self.tile_map.to_rows()),
+            format!("cards {}", cards),
+            format!("mirrored {}", mirrored),
+        ]
+        .join("\n")
+            + "\n"
+    }
+
+    fn parse(content: &str) -> Result<Self, String> {
+        let value = |key: &str| -> Result<&str, String> {
+            content
+                .lines()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix(' '))
+                .map(str::trim)
+                .ok_or_else(|| format!("Missing {}", key))
+        };
+        let color = |key: &str| -> Result<PlayerColor, String> {
+            match value(key)? {
+                "Red" => Ok(PlayerColor::Red),
+                "Blue" => Ok(PlayerColor::Blue),
+                color => Err(format!("Unknown colour {}", color)),
+            }
+        };
+        let player_type = |key: &str| -> Result<PlayerType, String> {
+            let name = value(key)?;
+            PlayerType::from_name(name).ok_or_else(|| format!("Unknown player {}", name))
+        };
+
+        let mut cards = [0; 5];
+        let names = value("cards")?.split_whitespace().collect::<Vec<_>>();
+        if names.len() != cards.len() {
+            return Err(format!("Expected 5 cards, got {}", names.len()));
+        }
+        for (idx, name) in names.iter().enumerate() {
+            cards[idx] = CARDS
+                .iter()
+                .position(|card| card.name == *name)
+                .ok_or_else(|| format!("Unknown card {}", name))? as u8;
+        }
+
+        let mut mirrored = [false; 5];
+        for (idx, flag) in value("mirrored")?.split_whitespace().take(5).enumerate() {
+            mirrored[idx] = flag == "1";
+        }
+
+        Ok(Self {
+            rule_set_type: RuleSetType::from_name(value("rules")?)
+                .ok_or_else(|| format!("Unknown rules {}", value("rules").unwrap()))?,
+            draw_rules: DrawRules {
+                repetitions: parse_limit(value("repetitions")?)?,
+                move_limit: parse_limit(value("move_limit")?)?,
+            },
+            handicap: Handicap::from_name(value("handicap")?)
+                .ok_or_else(|| format!("Unknown handicap {}", value("handicap").unwrap()))?,
+            red_player: player_type("red")?,
+            blue_player: player_type("blue")?,
+            depth: value("depth")?
+                .parse()
+                .map_err(|_| "Depth is not a number".to_owned())?,
+            turn: value("turn")?
+                .parse()
+                .map_err(|_| "Turn is not a number".to_owned())?,
+            curr_color: color("color")?,
+            first_color: color("first")?,
+            tile_map: TileMap::from_rows(value("board")?)?,
+            cards,
+            mirrored,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn saved_game() -> SavedGame {
+        SavedGame {
+            rule_set_type: RuleSetType::WayOfTheStream,
+            draw_rules: DrawRules {
+                repetitions: None,
+                move_limit: Some(100),
+            },
+            handicap: Handicap::default(),
+            red_player: PlayerType::AlphaBeta,
+            blue_player: PlayerType::Human,
+            depth: 4,
+            turn: 7,
+            curr_color: PlayerColor::Red,
+            first_color: PlayerColor::Blue,
+            tile_map: TileMap::new(),
+            cards: [0, 1, 2, 3, 4],
+            mirrored: [true, true, false, false, false],
+        }
+    }
+
+    #[test]
+    fn survives_a_round_trip() {
+        let saved_game = saved_game();
+        let parsed = SavedGame::parse(&saved_game.to_text()).unwrap();
+
+        assert_eq!(parsed.rule_set_type, saved_game.rule_set_type);
+        assert_eq!(parsed.draw_rules, saved_game.draw_rules);
+        assert_eq!(parsed.handicap, saved_game.handicap);
+        assert_eq!(parsed.red_player, saved_game.red_player);
+        assert_eq!(parsed.blue_player, saved_game.blue_player);
+        assert_eq!(parsed.depth, saved_game.depth);
+        assert_eq!(parsed.turn, saved_game.turn);
+        assert_eq!(parsed.curr_color, saved_game.curr_color);
+        assert_eq!(parsed.first_color, saved_game.first_color);
+        assert_eq!(parsed.tile_map.to_rows(), saved_game.tile_map.to_rows());
+        assert_eq!(parsed.cards, saved_game.cards);
+        assert_eq!(parsed.mirrored, saved_game.mirrored);
+    }
+
+    #[test]
+    fn rejects_a_broken_save() {
+        let text = saved_game().to_text();
+        assert!(SavedGame::parse(&text.replace("move_limit 100", "move_limit 0")).is_err());
+        assert!(SavedGame::parse(&text.replace("repetitions off\n", "")).is_err());
+        assert!(SavedGame::parse(&text.replace("turn 7", "turn seven")).is_err());
+        assert!(SavedGame::parse(&text.replace("Crab", "Unicorn")).is_err());
+        assert!(SavedGame::parse("").is_err());
+    }
+}
@@ -2,7 +2,7 @@ use bevy::prelude::Entity;
 
 use crate::components::coordinates::Coordinates;
 
-use super::game_state::PlayerType;
+use super::game_state::{PlayerColor, PlayerType};
 
 #[derive(Debug, Clone)]
 pub struct SelectedCard {
@@ -43,6 +43,36 @@ pub struct SelectedPlayers {
     pub blue_player: PlayerType,
 }
 
+impl SelectedPlayers {
+    #[inline]
+    pub fn get(&self, color: &PlayerColor) -> PlayerType {
+        match color {
+            PlayerColor::Red => self.red_player,
+            PlayerColor::Blue => self.blue_player,
+        }
+    }
+
+    #[inline]
+    pub fn set(&mut self, color: PlayerColor, player_type: PlayerType) {
+        match color {
+            PlayerColor::Red => self.red_player = player_type,
+            PlayerColor::Blue => self.blue_player = player_type,
+        }
+    }
+
+    /// Side played by the remote player, if there is one
+    #[inline]
+    pub fn remote_color(&self) -> Option<PlayerColor> {
+        if self.red_player == PlayerType::Remote {
+            Some(PlayerColor::Red)
+        } else if self.blue_player == PlayerType::Remote {
+            Some(PlayerColor::Blue)
+        } else {
+            None
+        }
+    }
+}
+
 impl Default for SelectedPlayers {
     fn default() -> Self {
         Self {
@@ -0,0 +1,480 @@
+use std::collections::BTreeMap;
+
+use bevy::log;
+
+use crate::storage;
+
+use super::game_state::PlayerColor;
+
+/// Name of the statistics in the storage. Every line is a tab separated entry:
+/// `active <profile>`, `profile <name> <elo> <games>`, `bot <name> <elo> <games>`
+/// or `record <profile> <opponent> <wins> <losses> <draws>`
+pub const STATISTICS_FILE: &str = "statistics.txt";
+pub const DEFAULT_PROFILE: &str = "Player";
+pub const MAX_PROFILE_NAME_LENGTH: usize = 16;
+// Rating of a new profile or bot
+const INITIAL_RATING: f32 = 1200.;
+// The biggest change of the rating after one game
+const K_FACTOR: f32 = 32.;
+
+/// Side of a finished game as it is seen by the statistics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Participant {
+    /// Human who plays with the profile
+    Profile(String),
+    /// Bot configuration, e.g. `AlphaBeta depth 6`
+    Bot(String),
+    /// Player on another machine. The games are counted, but not rated
+    Remote,
+}
+
+impl Participant {
+    pub fn name(&self) -> &str {
+        match self {
+            Participant::Profile(name) => name,
+            Participant::Bot(name) => name,
+            Participant::Remote => "Remote",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl Outcome {
+    #[inline]
+    fn score(&self) -> f32 {
+        match self {
+            Outcome::Win => 1.,
+            Outcome::Loss => 0.,
+            Outcome::Draw => 0.5,
+        }
+    }
+
+    #[inline]
+    fn reverse(&self) -> Self {
+        match self {
+            Outcome::Win => Outcome::Loss,
+            Outcome::Loss => Outcome::Win,
+            Outcome::Draw => Outcome::Draw,
+        }
+    }
+}
+
+/// Wins, losses and draws of a profile against one opponent
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Record {
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+impl Record {
+    fn add(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Win => self.wins += 1,
+            Outcome::Loss => self.losses += 1,
+            Outcome::Draw => self.draws += 1,
+        }
+    }
+}
+
+impl ToString for Record {
+    fn to_string(&self) -> String {
+        format!("{}W {}L {}D", self.wins, self.losses, self.draws)
+    }
+}
+
+/// Elo rating with the amount of rated games
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rating {
+    pub elo: f32,
+    pub games: u32,
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self {
+            elo: INITIAL_RATING,
+            games: 0,
+        }
+    }
+}
+
+impl ToString for Rating {
+    fn to_string(&self) -> String {
+        format!("{:.0} ({} games)", self.elo, self.games)
+    }
+}
+
+/// Results of the finished games kept between the sessions. Must be used as a resource
+#[derive(Debug, Clone)]
+pub struct Statistics {
+    /// Profile of the humans playing on this machine
+    pub active_profile: String,
+    pub profiles: BTreeMap<String, Rating>,
+    pub bots: BTreeMap<String, Rating>,
+    /// Results of the profiles keyed by the profile and the opponent name
+    pub records: BTreeMap<(String, String), Record>,
+}
+
+impl Statistics {
+    /// Loads the statistics from `STATISTICS_FILE`. Broken lines are skipped
+    pub fn load() -> Self {
+        let statistics = match storage::load(STATISTICS_FILE) {
+            Ok(Some(content)) => Self::parse(&content),
+            Ok(None) => Self::default(),
+            Err(e) => {
+                log::warn!("Statistics are not loaded: {}", e);
+                Self::default()
+            }
+        };
+        log::info!(
+            "Loaded statistics of {} profiles and {} bots",
+            statistics.profiles.len(),
+            statistics.bots.len()
+        );
+
+        statistics
+    }
+
+    fn parse(content: &str) -> Self {
+        let mut statistics = Self::default();
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            if let Err(e) = statistics.parse_line(line) {
+                log::warn!("Skipping a line in {}: {}", STATISTICS_FILE, e);
+            }
+        }
+        statistics
+            .profiles
+            .entry(statistics.active_profile.clone())
+            .or_default();
+
+        statistics
+    }
+
+    fn parse_line(&mut self, line: &str) -> Result<(), String> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let number = |idx: usize| -> Result<u32, String> {
+            fields
+                .get(idx)
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(|| format!("Expected a number in {}", line))
+        };
+        let rating = || -> Result<Rating, String> {
+            let elo = fields
+                .get(2)
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(|| format!("Expected a rating in {}", line))?;
+            Ok(Rating {
+                elo,
+                games: number(3)?,
+            })
+        };
+
+        match fields.as_slice() {
+            ["active", name] => self.active_profile = name.to_string(),
+            ["profile", name, ..] => {
+                self.profiles.insert(name.to_string(), rating()?);
+            }
+            ["bot", name, ..] => {
+                self.bots.insert(name.to_string(), rating()?);
+            }
+            ["record", profile, opponent, ..] => {
+                let record = Record {
+                    wins: number(3)?,
+                    losses: number(4)?,
+                    draws: number(5)?,
+                };
+                self.records
+                    .insert((profile.to_string(), opponent.to_string()), record);
+            }
+            _ => return Err(format!("Unknown entry {}", line)),
+        }
+        Ok(())
+    }
+
+    /// Writes the statistics to `STATISTICS_FILE`
+    pub fn save(&self) {
+        if let Err(e) = storage::save(STATISTICS_FILE, &self.to_text()) {
+            log::warn!("Statistics are not saved: {}", e);
+        }
+    }
+
+    fn to_text(&self) -> String {
+        let mut lines = vec![format!("active\t{}", self.active_profile)];
+        for (name, rating) in self.profiles.iter() {
+            lines.push(format!(
+                "profile\t{}\t{:.1}\t{}",
+                name, rating.elo, rating.games
+            ));
+        }
+        for (name, rating) in self.bots.iter() {
+            lines.push(format!(
+                "bot\t{}\t{:.1}\t{}",
+                name, rating.elo, rating.games
+            ));
+        }
+        for ((profile, opponent), record) in self.records.iter() {
+            lines.push(format!(
+                "record\t{}\t{}\t{}\t{}\t{}",
+                profile, opponent, record.wins, record.losses, record.draws
+            ));
+        }
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Creates the profile and makes it active
+    pub fn add_profile(&mut self, name: &str) -> Result<(), String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("Profile needs a name".to_owned());
+        }
+        if self.profiles.contains_key(name) {
+            return Err(format!("Profile {} already exists", name));
+        }
+
+        self.profiles.insert(name.to_owned(), Rating::default());
+        self.active_profile = name.to_owned();
+        Ok(())
+    }
+
+    pub fn next_profile(&mut self) {
+        let names = self.profiles.keys().cloned().collect::<Vec<_>>();
+        let idx = names.iter().position(|name| *name == self.active_profile);
+        let idx = idx.map_or(0, |idx| (idx + 1) % names.len());
+        self.active_profile = names[idx].clone();
+    }
+
+    pub fn prev_profile(&mut self) {
+        let names = self.profiles.keys().cloned().collect::<Vec<_>>();
+        let idx = names.iter().position(|name| *name == self.active_profile);
+        let idx = idx.map_or(0, |idx| (idx + names.len() - 1) % names.len());
+        self.active_profile = names[idx].clone();
+    }
+
+    pub fn rating(&self, participant: &Participant) -> Option<Rating> {
+        match participant {
+            Participant::Profile(name) => {
+                Some(self.profiles.get(name).copied().unwrap_or_default())
+            }
+            Participant::Bot(name) => Some(self.bots.get(name).copied().unwrap_or_default()),
+            Participant::Remote => None,
+        }
+    }
+
+    fn rating_mut(&mut self, participant: &Participant) -> Option<&mut Rating> {
+        match participant {
+            Participant::Profile(name) => Some(self.profiles.entry(name.clone()).or_default()),
+            Participant::Bot(name) => Some(self.bots.entry(name.clone()).or_default()),
+            Participant::Remote => None,
+        }
+    }
+
+    /// Results of the profile against every opponent it has played
+    pub fn records_of<'a>(
+        &'a self,
+        profile: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a Record)> {
+        self.records
+            .iter()
+            .filter(move |((name, _), _)| name == profile)
+            .map(|((_, opponent), record)| (opponent.as_str(), record))
+    }
+
+    /// Counts the finished game for the profiles and rates it if nobody played remotely.
+    /// `winner` is `None` for a draw
+    pub fn record_game(
+        &mut self,
+        red: &Participant,
+        blue: &Participant,
+        winner: Option<PlayerColor>,
+    ) {
+        // a profile playing itself or a bot playing its copy changes nothing
+        if red == blue {
+            log::info!("{} has played itself, the game is not counted", red.name());
+            return;
+        }
+
+        let red_outcome = match winner {
+            Some(PlayerColor::Red) => Outcome::Win,
+            Some(PlayerColor::Blue) => Outcome::Loss,
+            None => Outcome::Draw,
+        };
+
+        for (participant, opponent, outcome) in
+            [(red, blue, red_outcome), (blue, red, red_outcome.reverse())]
+        {
+            if let Participant::Profile(profile) = participant {
+                self.records
+                    .entry((profile.clone(), opponent.name().to_owned()))
+                    .or_default()
+                    .add(outcome);
+            }
+        }
+
+        let (red_rating, blue_rating) = match (self.rating(red), self.rating(blue)) {
+            (Some(red_rating), Some(blue_rating)) => (red_rating, blue_rating),
+            _ => return,
+        };
+        let red_expected = 1. / (1. + 10f32.powf((blue_rating.elo - red_rating.elo) / 400.));
+        let red_change = K_FACTOR * (red_outcome.score() - red_expected);
+
+        for (participant, change) in [(red, red_change), (blue, -red_change)] {
+            let rating = self.rating_mut(participant).unwrap();
+            rating.elo += change;
+            rating.games += 1;
+        }
+        log::info!(
+            "Rating of {} has changed by {:.1}, rating of {} by {:.1}",
+            red.name(),
+            red_change,
+            blue.name(),
+            -red_change
+        );
+    }
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        let mut profiles = BTreeMap::new();
+        profiles.insert(DEFAULT_PROFILE.to_owned(), Rating::default());
+        Self {
+            active_profile: DEFAULT_PROFILE.to_owned(),
+            profiles,
+            bots: BTreeMap::new(),
+            records: BTreeMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bot() -> Participant {
+        Participant::Bot("AlphaBeta depth 6".to_owned())
+    }
+
+    fn profile() -> Participant {
+        Participant::Profile(DEFAULT_PROFILE.to_owned())
+    }
+
+    #[test]
+    fn rates_the_game_of_equal_players() {
+        let mut statistics = Statistics::default();
+        statistics.record_game(&profile(), &bot(), Some(PlayerColor::Red));
+
+        let profile_rating = statistics.rating(&profile()).unwrap();
+        let bot_rating = statistics.rating(&bot()).unwrap();
+        assert_eq!(profile_rating.elo, INITIAL_RATING + K_FACTOR / 2.);
+        assert_eq!(bot_rating.elo, INITIAL_RATING - K_FACTOR / 2.);
+        assert_eq!((profile_rating.games, bot_rating.games), (1, 1));
+
+        let record = statistics.records_of(DEFAULT_PROFILE).collect::<Vec<_>>();
+        assert_eq!(
+            record,
+            vec![(
+                "AlphaBeta depth 6",
+                &Record {
+                    wins: 1,
+                    losses: 0,
+                    draws: 0
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn rates_an_upset_higher_than_an_expected_win() {
+        let mut statistics = Statistics::default();
+        statistics.bots.insert(
+            bot().name().to_owned(),
+            Rating {
+                elo: 1600.,
+                games: 10,
+            },
+        );
+
+        statistics.record_game(&bot(), &profile(), None);
+        // a draw against a weaker player costs rating
+        let bot_elo = statistics.rating(&bot()).unwrap().elo;
+        assert!(bot_elo < 1600.);
+        assert!((bot_elo + statistics.rating(&profile()).unwrap().elo - 2800.).abs() < 0.01);
+
+        let profile_elo = statistics.rating(&profile()).unwrap().elo;
+        statistics.record_game(&profile(), &bot(), Some(PlayerColor::Red));
+        let upset_change = statistics.rating(&profile()).unwrap().elo - profile_elo;
+        assert!(upset_change > K_FACTOR / 2. && upset_change < K_FACTOR);
+    }
+
+    #[test]
+    fn does_not_rate_remote_games_and_self_play() {
+        let mut statistics = Statistics::default();
+        statistics.record_game(&profile(), &Participant::Remote, Some(PlayerColor::Blue));
+        assert_eq!(statistics.rating(&profile()), Some(Rating::default()));
+        assert_eq!(statistics.rating(&Participant::Remote), None);
+        assert_eq!(
+            statistics.records_of(DEFAULT_PROFILE).next(),
+            Some((
+                "Remote",
+                &Record {
+                    wins: 0,
+                    losses: 1,
+                    draws: 0
+                }
+            ))
+        );
+
+        statistics.record_game(&bot(), &bot(), Some(PlayerColor::Red));
+        assert!(statistics.bots.is_empty());
+    }
+
+    #[test]
+    fn survives_a_round_trip() {
+        let mut statistics = Statistics::default();
+        statistics.add_profile("Alice").unwrap();
+        statistics.record_game(&bot(), &Participant::Profile("Alice".to_owned()), None);
+        statistics.record_game(&profile(), &bot(), Some(PlayerColor::Blue));
+
+        let parsed = Statistics::parse(&statistics.to_text());
+        assert_eq!(parsed.active_profile, "Alice");
+        assert_eq!(parsed.profiles, statistics.profiles);
+        assert_eq!(parsed.bots, statistics.bots);
+        assert_eq!(parsed.records, statistics.records);
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let content = "active\tAlice\n\
+                       profile\tAlice\t1250.5\t3\n\
+                       profile\tBob\tstrong\t3\n\
+                       bot\tRandom\t1100\n\
+                       record\tAlice\tRandom\t1\t-1\t0\n\
+                       record\tAlice\tBob\t2\t1\t0\n\
+                       unknown entry\n";
+
+        let parsed = Statistics::parse(content);
+        assert_eq!(parsed.active_profile, "Alice");
+        assert_eq!(
+            parsed.profiles.keys().collect::<Vec<_>>(),
+            vec!["Alice", DEFAULT_PROFILE]
+        );
+        assert_eq!(parsed.profiles["Alice"].elo, 1250.5);
+        assert!(parsed.bots.is_empty());
+        assert_eq!(parsed.records.len(), 1);
+    }
+
+    #[test]
+    fn creates_the_missing_active_profile() {
+        let parsed = Statistics::parse("active\tAlice\n");
+        assert!(parsed.profiles.contains_key("Alice"));
+        assert!(Statistics::default().add_profile(DEFAULT_PROFILE).is_err());
+        assert!(Statistics::default().add_profile("  ").is_err());
+    }
+}
@@ -4,7 +4,8 @@ use bevy::prelude::Entity;
 pub struct TextHandler {
     pub turn_text: Entity,
     pub guide_text: Entity,
-    pub evaluation_text: Entity
+    pub evaluation_text: Entity,
+    pub clock_text: Entity,
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,233 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    render::texture::DEFAULT_IMAGE_HANDLE,
+    utils::BoxedFuture,
+};
+
+use super::board_assets::{BoardAssets, MATERIAL_NAMES};
+
+/// Material of a theme, the color tints the sprite
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeMaterial {
+    pub color: Color,
+    /// Path of the sprite in the assets, a plain square is drawn without it
+    pub sprite: Option<String>,
+}
+
+/// Skin of the board loaded from a `.theme` file. Every line is `<key> = <value>`,
+/// lines starting with `#` are comments:
+///
+/// ```text
+/// name = Default
+/// font = fonts/pixeled.ttf
+/// tile = #404040
+/// blue_king = #ffffff sprites/star_blue.png
+/// ```
+///
+/// Materials are named as in `MATERIAL_NAMES` and take a hex color with an optional sprite.
+/// The materials missing in the theme are taken from the built-in assets
+#[derive(Debug, Clone, PartialEq, TypeUuid)]
+#[uuid = "1e68d8f0-9e0b-406a-91c4-ece0a1e73e8b"]
+pub struct Theme {
+    pub name: String,
+    /// Path of the font in the assets
+    pub font: Option<String>,
+    pub materials: Vec<(String, ThemeMaterial)>,
+}
+
+impl Theme {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut theme = Self {
+            name: String::new(),
+            font: None,
+            materials: vec![],
+        };
+
+        for line in content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("Expected <key> = <value>, got {}", line))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            match key {
+                "name" => theme.name = value.to_owned(),
+                "font" => theme.font = Some(value.to_owned()),
+                _ if MATERIAL_NAMES.contains(&key) => {
+                    let mut words = value.split_whitespace();
+                    let color = words.next().unwrap_or_default();
+                    let color = Color::hex(color.trim_start_matches('#'))
+                        .map_err(|e| format!("Wrong color of {}: {:?}", key, e))?;
+                    let material = ThemeMaterial {
+                        color,
+                        sprite: words.next().map(str::to_owned),
+                    };
+                    theme.materials.push((key.to_owned(), material));
+                }
+                _ => return Err(format!("Unknown key {}", key)),
+            }
+        }
+
+        if theme.name.is_empty() {
+            return Err("The theme has no name".to_owned());
+        }
+        Ok(theme)
+    }
+
+    /// Built-in assets skinned with the theme
+    pub fn board_assets(&self, base: &BoardAssets, asset_server: &AssetServer) -> BoardAssets {
+        let mut board_assets = base.clone();
+        board_assets.label = self.name.clone();
+
+        for (name, theme_material) in self.materials.iter() {
+            if let Some(material) = board_assets.material_mut(name) {
+                material.color = theme_material.color;
+                material.texture = match &theme_material.sprite {
+                    Some(path) => asset_server.load(path.as_str()),
+                    None => DEFAULT_IMAGE_HANDLE.typed(),
+                };
+            }
+        }
+        if let Some(path) = &self.font {
+            board_assets.font = asset_server.load(path.as_str());
+        }
+
+        board_assets
+    }
+}
+
+/// Loads the `.theme` files with the asset server, so the themes are loaded on the web as well
+#[derive(Debug, Default)]
+pub struct ThemeLoader;
+
+impl AssetLoader for ThemeLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let content = std::str::from_utf8(bytes)?;
+            let theme = Theme::parse(content).map_err(|e| {
+                anyhow::anyhow!("Theme {} is broken: {}", load_context.path().display(), e)
+            })?;
+            load_context.set_default_asset(LoadedAsset::new(theme));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // writes the theme back in the format of the `.theme` files
+    fn to_text(theme: &Theme) -> String {
+        let mut lines = vec![format!("name = {}", theme.name)];
+        if let Some(font) = &theme.font {
+            lines.push(format!("font = {}", font));
+        }
+        for (name, material) in theme.materials.iter() {
+            let color = material
+                .color
+                .as_rgba_f32()
+                .iter()
+                .map(|channel| format!("{:02x}", (channel * 255.).round() as u8))
+                .collect::<String>();
+            let sprite = material.sprite.as_deref().unwrap_or_default();
+            lines.push(format!("{} = #{} {}", name, color, sprite));
+        }
+        lines.join("\n")
+    }
+
+    fn night_theme() -> Theme {
+        Theme {
+            name: "Night".to_owned(),
+            font: Some("fonts/orange_kid.ttf".to_owned()),
+            materials: vec![
+                (
+                    "tile".to_owned(),
+                    ThemeMaterial {
+                        color: Color::rgba_u8(16, 32, 48, 255),
+                        sprite: None,
+                    },
+                ),
+                (
+                    "blue_king".to_owned(),
+                    ThemeMaterial {
+                        color: Color::rgba_u8(255, 255, 255, 128),
+                        sprite: Some("sprites/star_blue.png".to_owned()),
+                    },
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn survives_a_round_trip() {
+        let theme = night_theme();
+        assert_eq!(Theme::parse(&to_text(&theme)), Ok(theme));
+
+        let no_font = Theme {
+            font: None,
+            materials: vec![],
+            ..night_theme()
+        };
+        assert_eq!(Theme::parse(&to_text(&no_font)), Ok(no_font));
+    }
+
+    #[test]
+    fn parses_the_bundled_themes() {
+        for content in [
+            include_str!("../../../assets/themes/default.theme"),
+            include_str!("../../../assets/themes/contrast.theme"),
+            include_str!("../../../assets/themes/wood.theme"),
+        ] {
+            let theme = Theme::parse(content).unwrap();
+            assert!(!theme.name.is_empty());
+            assert!(theme.font.is_some());
+        }
+
+        let default = Theme::parse(include_str!("../../../assets/themes/default.theme")).unwrap();
+        for name in MATERIAL_NAMES {
+            assert!(default.materials.iter().any(|(key, _)| key == name));
+        }
+    }
+
+    #[test]
+    fn skips_the_comments_and_the_blank_lines() {
+        let content = "# comment\n\n  name = Spaced  \n   # indented comment\ntile=#102030";
+        let theme = Theme::parse(content).unwrap();
+        assert_eq!(theme.name, "Spaced");
+        assert_eq!(
+            theme.materials,
+            vec![(
+                "tile".to_owned(),
+                ThemeMaterial {
+                    color: Color::rgb_u8(16, 32, 48),
+                    sprite: None,
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_themes() {
+        let text = to_text(&night_theme());
+        assert!(Theme::parse(&text.replace("tile =", "tile")).is_err());
+        assert!(Theme::parse(&text.replace("tile =", "floor =")).is_err());
+        assert!(Theme::parse(&text.replace("#102030ff", "#1020zz")).is_err());
+        assert!(Theme::parse(&text.replace("#102030ff", "")).is_err());
+        assert!(Theme::parse(&text.replace("name = Night", "")).is_err());
+        assert!(Theme::parse("").is_err());
+    }
+}
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+
+use super::{board_assets::BoardAssets, palette::Palette, theme::Theme};
+
+/// Theme files in the assets. The web build can not list the folder, so the files are named here
+pub const THEME_FILES: [&str; 3] = [
+    "themes/default.theme",
+    "themes/wood.theme",
+    "themes/contrast.theme",
+];
+
+/// Themes which can be selected in the main menu. Must be used as a resource
+#[derive(Debug, Clone)]
+pub struct ThemeOptions {
+    /// Themes by the names of their files
+    pub themes: Vec<(String, Handle<Theme>)>,
+    /// Index of the selected theme
+    pub selected: usize,
+    /// Assets skinned by the themes, they are used until the selected theme is loaded
+    pub base: BoardAssets,
+    /// Palette applied above the selected theme
+    pub palette: Palette,
+}
+
+impl ThemeOptions {
+    /// Starts loading the themes from `THEME_FILES`
+    pub fn load(asset_server: &AssetServer, base: BoardAssets) -> Self {
+        let themes = THEME_FILES
+            .iter()
+            .map(|path| {
+                let name = path
+                    .trim_start_matches("themes/")
+                    .trim_end_matches(".theme")
+                    .to_owned();
+                (name, asset_server.load(*path))
+            })
+            .collect();
+
+        Self {
+            themes,
+            selected: 0,
+            base,
+            palette: Palette::default(),
+        }
+    }
+
+    #[inline]
+    pub fn selected_theme(&self) -> &(String, Handle<Theme>) {
+        &self.themes[self.selected]
+    }
+
+    /// Selects the theme by the name of its file, the selection is kept if there is no such theme
+    pub fn select(&mut self, name: &str) {
+        if let Some(idx) = self.themes.iter().position(|(file, _)| file == name) {
+            self.selected = idx;
+        }
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % self.themes.len();
+    }
+}
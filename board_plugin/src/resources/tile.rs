@@ -7,7 +7,7 @@ use crate::components::pieces::Piece;
 #[derive(Debug, Component)]
 pub struct TempleTile;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct Tile {
     pub piece: Option<Piece>,
 }
@@ -3,6 +3,7 @@ use bevy::prelude::Entity;
 use crate::components::coordinates::Coordinates;
 use crate::components::pieces::{Piece, PieceKind::*};
 use crate::resources::tile::Tile;
+use crate::rules::rule_set::RuleSet;
 use std::ops::{Deref, DerefMut};
 
 use super::card::Card;
@@ -11,12 +12,25 @@ use super::game_state::{
     GameState,
     PlayerColor::{self, *},
 };
+use super::handicap::Handicap;
 
-const BOARD_SIZE: usize = 5;
+pub const BOARD_SIZE: usize = 5;
 
 pub const RED_TEMPLE: Coordinates = Coordinates { x: 2, y: 0 };
 pub const BLUE_TEMPLE: Coordinates = Coordinates { x: 2, y: 4 };
 
+/// Temple of the player, where the enemy master must arrive for the Way of the Stream win
+#[inline]
+pub fn temple_of(color: PlayerColor) -> Coordinates {
+    match color {
+        Red => RED_TEMPLE,
+        Blue => BLUE_TEMPLE,
+    }
+}
+
+// Back rank files from which pawns are removed by the handicap
+const HANDICAP_PAWN_FILES: [usize; 2] = [0, 4];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MoveResult {
     Win,
@@ -107,6 +121,84 @@ impl TileMap {
         Self { height, width, map }
     }
 
+    /// Generates a map with the pawns removed from the stronger side's back rank
+    pub fn with_handicap(handicap: &Handicap) -> Self {
+        let mut tile_map = Self::new();
+
+        if let Handicap::Pawns { stronger, amount } = *handicap {
+            let back_rank = match stronger {
+                Red => 0,
+                Blue => BOARD_SIZE - 1,
+            };
+
+            for x in HANDICAP_PAWN_FILES.iter().take(amount as usize) {
+                tile_map.map[back_rank][*x].piece = None;
+            }
+        }
+
+        tile_map
+    }
+
+    /// Rows from `y = 0` to `y = 4` separated with `/`, e.g. `rrRrr/...../...../...../bbBbb`.
+    /// Red pieces are `r` and `R` for the master, blue pieces are `b` and `B`, empty tiles are `.`
+    pub fn to_rows(&self) -> String {
+        self.map
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|tile| match tile.piece {
+                        Some(Piece {
+                            color: Red,
+                            kind: Pawn,
+                        }) => 'r',
+                        Some(Piece {
+                            color: Red,
+                            kind: King,
+                        }) => 'R',
+                        Some(Piece {
+                            color: Blue,
+                            kind: Pawn,
+                        }) => 'b',
+                        Some(Piece {
+                            color: Blue,
+                            kind: King,
+                        }) => 'B',
+                        None => '.',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Reads the rows written by `to_rows`
+    pub fn from_rows(rows: &str) -> Result<Self, String> {
+        let mut tile_map = Self::new();
+
+        let rows = rows.split('/').collect::<Vec<_>>();
+        if rows.len() != BOARD_SIZE {
+            return Err(format!("Expected {} rows, got {}", BOARD_SIZE, rows.len()));
+        }
+
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != BOARD_SIZE {
+                return Err(format!("Row {} must have {} tiles", row, BOARD_SIZE));
+            }
+            for (x, c) in row.chars().enumerate() {
+                tile_map.map[y][x].piece = match c {
+                    'r' => Some(Piece::new(Pawn, Red)),
+                    'R' => Some(Piece::new(King, Red)),
+                    'b' => Some(Piece::new(Pawn, Blue)),
+                    'B' => Some(Piece::new(King, Blue)),
+                    '.' => None,
+                    _ => return Err(format!("Unknown piece {}", c)),
+                };
+            }
+        }
+
+        Ok(tile_map)
+    }
+
     // This function was completely fully copied from here: https://dev.to/qongzi/bevy-minesweeper-part-2-1hi5
     // because it saved the time and was convenient to use
     #[cfg(feature = "debug")]
@@ -129,71 +221,57 @@ impl TileMap {
         coordinates: &Coordinates,
         card: &Card,
         game_state: &GameState,
+    ) -> Vec<Coordinates> {
+        self.generate_moves_of(
+            &game_state.curr_color,
+            coordinates,
+            card,
+            game_state.rules.as_ref(),
+        )
+    }
+
+    /// Destinations of a piece of the player with the card, whoever has the turn
+    pub fn generate_moves_of(
+        &self,
+        color: &PlayerColor,
+        coordinates: &Coordinates,
+        card: &Card,
+        rules: &dyn RuleSet,
     ) -> Vec<Coordinates> {
         card.directions
             .iter()
-            .map(|tuple| {
-                if card.is_mirrored {
-                    *coordinates + (tuple.0, -tuple.1)
-                } else {
-                    *coordinates + *tuple
-                }
-            })
+            .map(|dir| rules.apply_direction(*coordinates, *dir, card))
             .filter(|coords| {
                 coords.x < 5
                     && coords.y < 5
-                    && match self.map[coords.y as usize][coords.x as usize].piece {
-                        Some(piece) => piece.color != game_state.curr_color,
-                        // no piece - it is good to go
-                        None => true,
-                    }
+                    && rules
+                        .can_move_to(color, &self.map[coords.y as usize][coords.x as usize].piece)
             })
             .collect::<Vec<_>>()
     }
 
-    pub fn make_a_move(&mut self, start: Coordinates, end: Coordinates) -> MoveResult {
-        let start_tile = self.map[start.y as usize][start.x as usize];
-        let end_tile = self.map[end.y as usize][end.x as usize];
-
+    pub fn make_a_move(
+        &mut self,
+        start: Coordinates,
+        end: Coordinates,
+        rules: &dyn RuleSet,
+    ) -> MoveResult {
         // we can be sure that the start tile must have a piece
-        let start_piece = start_tile.piece.unwrap();
-
-        // win after capture
-        if let Some(end_piece) = end_tile.piece {
-            let mut result = MoveResult::Move;
-            if start_piece.color != end_piece.color && end_piece.kind == King {
-                result = MoveResult::Win;
-            } else if start_piece.color != end_piece.color {
-                result = MoveResult::Capture;
-                // if capturing with the win
-                if start_piece.kind == King && start_piece.color == Red && end == BLUE_TEMPLE {
-                    result = MoveResult::Win;
-                } else if start_piece.kind == King && start_piece.color == Blue && end == RED_TEMPLE
-                {
-                    result = MoveResult::Win;
-                }
-            }
-
-            self.map[end.y as usize][end.x as usize] = self.map[start.y as usize][start.x as usize];
-            self.map[start.y as usize][start.x as usize].piece = None;
-            return result;
-        }
-
+        let start_piece = self.map[start.y as usize][start.x as usize].piece.unwrap();
+        let end_piece = self.map[end.y as usize][end.x as usize].piece;
+
+        let result = if rules.is_win(&start_piece, &end_piece, &end) {
+            MoveResult::Win
+        } else if end_piece.is_some() {
+            MoveResult::Capture
+        } else {
+            MoveResult::Move
+        };
+
+        self.map[end.y as usize][end.x as usize] = self.map[start.y as usize][start.x as usize];
         self.map[start.y as usize][start.x as usize].piece = None;
-        self.map[end.y as usize][end.x as usize] = start_tile;
-
-        // if the end piece is the empty tile and it is a temple
-        let end_tile = self.map[end.y as usize][end.x as usize];
-        if let Some(piece) = end_tile.piece {
-            if piece.kind == King && piece.color == Red && end == BLUE_TEMPLE {
-                return MoveResult::Win;
-            }
-            if piece.kind == King && piece.color == Blue && end == RED_TEMPLE {
-                return MoveResult::Win;
-            }
-        }
 
-        MoveResult::Move
+        result
     }
 
     pub fn undo_move(
@@ -232,7 +310,11 @@ impl TileMap {
         let cards = deck.get_player_cards(game_state);
         let mut possible_moves = Vec::with_capacity(2);
         for (e, card) in cards.iter() {
-            let moves = self.generate_possible_moves_for_card(&game_state.curr_color, card);
+            let moves = self.generate_possible_moves_for_card(
+                &game_state.curr_color,
+                card,
+                game_state.rules.as_ref(),
+            );
             possible_moves.push(PossibleMoves { card: *e, moves });
         }
         possible_moves
@@ -242,6 +324,7 @@ impl TileMap {
         &self,
         curr_player_color: &PlayerColor,
         card: &Card,
+        rules: &dyn RuleSet,
     ) -> Vec<Move> {
         let mut moves = vec![];
         for (y, line) in self.map.iter().enumerate() {
@@ -256,25 +339,17 @@ impl TileMap {
                         y: y as u8,
                     };
                     for dir in card.directions {
-                        let mov = if card.is_mirrored {
-                            Move {
-                                from: coordinates,
-                                to: coordinates + (dir.0, -dir.1),
-                            }
-                        } else {
-                            Move {
-                                from: coordinates,
-                                to: coordinates + *dir,
-                            }
+                        let mov = Move {
+                            from: coordinates,
+                            to: rules.apply_direction(coordinates, *dir, card),
                         };
 
                         if mov.to.x < 5
                             && mov.to.y < 5
-                            && match self.map[mov.to.y as usize][mov.to.x as usize].piece {
-                                Some(piece) => piece.color != *curr_player_color,
-                                // no piece - it is good to go
-                                None => true,
-                            }
+                            && rules.can_move_to(
+                                curr_player_color,
+                                &self.map[mov.to.y as usize][mov.to.x as usize].piece,
+                            )
                         {
                             moves.push(mov);
                         }
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+// Time controls in the order they are cycled through in the main menu
+const TIME_CONTROLS: [TimeControl; 7] = [
+    TimeControl::None,
+    TimeControl::Fischer {
+        base: Duration::from_secs(60),
+        increment: Duration::from_secs(1),
+    },
+    TimeControl::Fischer {
+        base: Duration::from_secs(3 * 60),
+        increment: Duration::from_secs(2),
+    },
+    TimeControl::Fischer {
+        base: Duration::from_secs(10 * 60),
+        increment: Duration::from_secs(5),
+    },
+    TimeControl::Byoyomi {
+        base: Duration::from_secs(60),
+        period: Duration::from_secs(10),
+    },
+    TimeControl::Byoyomi {
+        base: Duration::from_secs(5 * 60),
+        period: Duration::from_secs(30),
+    },
+    TimeControl::Byoyomi {
+        base: Duration::ZERO,
+        period: Duration::from_secs(15),
+    },
+];
+
+/// Time control of the game selected in the main menu. Must be used as a resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeControl {
+    None,
+    /// `increment` is added to the clock after every move
+    Fischer {
+        base: Duration,
+        increment: Duration,
+    },
+    /// After the `base` time is used, every move must be made within the `period`
+    Byoyomi {
+        base: Duration,
+        period: Duration,
+    },
+}
+
+impl TimeControl {
+    pub fn next(&mut self) {
+        let idx = self.index();
+        *self = TIME_CONTROLS[(idx + 1) % TIME_CONTROLS.len()];
+    }
+
+    pub fn prev(&mut self) {
+        let idx = self.index();
+        *self = TIME_CONTROLS[(idx + TIME_CONTROLS.len() - 1) % TIME_CONTROLS.len()];
+    }
+
+    /// Finds the time control by the name shown in the main menu
+    pub fn from_name(name: &str) -> Option<Self> {
+        TIME_CONTROLS
+            .iter()
+            .copied()
+            .find(|time_control| time_control.to_string() == name)
+    }
+
+    fn index(&self) -> usize {
+        TIME_CONTROLS.iter().position(|t| t == self).unwrap_or(0)
+    }
+}
+
+impl Default for TimeControl {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+impl ToString for TimeControl {
+    fn to_string(&self) -> String {
+        match self {
+            TimeControl::None => "None".to_owned(),
+            TimeControl::Fischer { base, increment } => {
+                format!("{}m + {}s", base.as_secs() / 60, increment.as_secs())
+            }
+            TimeControl::Byoyomi { base, period } if base.is_zero() => {
+                format!("{}s per move", period.as_secs())
+            }
+            TimeControl::Byoyomi { base, period } => {
+                format!("{}m, {}s byoyomi", base.as_secs() / 60, period.as_secs())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycles_through_the_time_controls() {
+        let mut time_control = TimeControl::default();
+        for expected in TIME_CONTROLS.iter().skip(1) {
+            time_control.next();
+            assert_eq!(time_control, *expected);
+        }
+        time_control.next();
+        assert_eq!(time_control, TimeControl::None);
+
+        time_control.prev();
+        assert_eq!(time_control, TIME_CONTROLS[TIME_CONTROLS.len() - 1]);
+    }
+
+    #[test]
+    fn names_survive_a_round_trip() {
+        for time_control in TIME_CONTROLS {
+            assert_eq!(
+                TimeControl::from_name(&time_control.to_string()),
+                Some(time_control)
+            );
+        }
+        assert_eq!(TIME_CONTROLS[1].to_string(), "1m + 1s");
+        assert_eq!(TIME_CONTROLS[4].to_string(), "1m, 10s byoyomi");
+        assert_eq!(TIME_CONTROLS[6].to_string(), "15s per move");
+    }
+
+    #[test]
+    fn rejects_unknown_names() {
+        assert_eq!(TimeControl::from_name("2m + 3s"), None);
+        assert_eq!(TimeControl::from_name(""), None);
+    }
+}
@@ -0,0 +1,73 @@
+use bevy::prelude::Entity;
+
+use super::{
+    deck::Deck,
+    game_state::{GameState, PlayerColor, PlayerType},
+    last_move::LastMove,
+    position_history::PositionHistory,
+    tile_map::TileMap,
+};
+
+/// Game at the beginning of a turn
+#[derive(Debug, Clone)]
+pub struct TurnSnapshot {
+    pub tile_map: TileMap,
+    /// Card entities in the deck order
+    pub cards: Vec<Entity>,
+    pub mirrored: [bool; 5],
+    pub turn: u16,
+    pub curr_color: PlayerColor,
+    pub history: PositionHistory,
+    /// Move which has led to the turn
+    pub last_move: Option<LastMove>,
+}
+
+impl TurnSnapshot {
+    pub fn new(
+        tile_map: &TileMap,
+        deck: &Deck,
+        game_state: &GameState,
+        last_move: Option<&LastMove>,
+    ) -> Self {
+        let mut mirrored = [false; 5];
+        for (idx, entity) in deck.cards.iter().enumerate() {
+            mirrored[idx] = deck.cardboards.get(entity).unwrap().card.is_mirrored;
+        }
+
+        Self {
+            tile_map: *tile_map,
+            cards: deck.cards.clone(),
+            mirrored,
+            turn: game_state.turn,
+            curr_color: game_state.curr_color,
+            history: game_state.history.clone(),
+            last_move: last_move.copied(),
+        }
+    }
+}
+
+/// Turns of the current game which can be taken back. Must be used as a resource
+#[derive(Debug, Clone, Default)]
+pub struct UndoHistory {
+    /// The last snapshot is the current turn
+    snapshots: Vec<TurnSnapshot>,
+}
+
+impl UndoHistory {
+    #[inline]
+    pub fn push(&mut self, snapshot: TurnSnapshot) {
+        self.snapshots.push(snapshot);
+    }
+
+    /// Forgets the turns after the previous turn of a human and returns it.
+    /// Nothing is changed when the humans have not moved yet
+    pub fn take_back(&mut self, game_state: &GameState) -> Option<TurnSnapshot> {
+        let current = self.snapshots.len().checked_sub(1)?;
+        let idx = self.snapshots[..current].iter().rposition(|snapshot| {
+            game_state.get_player(&snapshot.curr_color).player_type == PlayerType::Human
+        })?;
+
+        self.snapshots.truncate(idx + 1);
+        self.snapshots.last().cloned()
+    }
+}
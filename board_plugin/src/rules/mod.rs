@@ -0,0 +1,3 @@
+pub mod rule_set;
+pub mod standard;
+pub mod way_of_the_stream;
@@ -0,0 +1,161 @@
+use core::fmt::Debug;
+
+use crate::{
+    components::{
+        coordinates::Coordinates,
+        pieces::{Piece, PieceKind},
+    },
+    resources::{
+        card::Card,
+        draw_rules::DrawRules,
+        game_state::PlayerColor,
+        position_history::PositionHistory,
+        tile_map::temple_of,
+    },
+};
+
+use super::{standard::StandardRules, way_of_the_stream::WayOfTheStreamRules};
+
+/// Rules of the game consulted by the move generation, the agents and the game systems.
+///
+/// Default methods implement the standard rules
+pub trait RuleSet: Debug + Sync + Send {
+    /// Way of the Stone: capturing the enemy master wins the game
+    fn win_by_capture(&self) -> bool {
+        true
+    }
+
+    /// Way of the Stream: moving the master into the enemy temple wins the game
+    fn win_by_temple(&self) -> bool {
+        true
+    }
+
+    /// Checks if moving the `piece` to the `to` coordinates, where `captured` piece stands, wins the game
+    fn is_win(&self, piece: &Piece, captured: &Option<Piece>, to: &Coordinates) -> bool {
+        let by_capture = match captured {
+            Some(captured) => captured.color != piece.color && captured.kind == PieceKind::King,
+            None => false,
+        };
+        let by_temple = piece.kind == PieceKind::King && *to == temple_of(piece.enemy());
+
+        (self.win_by_capture() && by_capture) || (self.win_by_temple() && by_temple)
+    }
+
+    /// Checks if the player may move a piece to the tile with the `target` piece
+    fn can_move_to(&self, player_color: &PlayerColor, target: &Option<Piece>) -> bool {
+        match target {
+            // the master can not be captured if it does not win the game
+            Some(piece) if piece.kind == PieceKind::King && !self.win_by_capture() => false,
+            Some(piece) => piece.color != *player_color,
+            // no piece - it is good to go
+            None => true,
+        }
+    }
+
+    /// Applies the card direction to the coordinates. Mirrored cards belong to the blue player
+    /// and their directions are flipped vertically
+    #[inline]
+    fn apply_direction(
+        &self,
+        coordinates: Coordinates,
+        direction: (i8, i8),
+        card: &Card,
+    ) -> Coordinates {
+        if card.is_mirrored {
+            coordinates + (direction.0, -direction.1)
+        } else {
+            coordinates + direction
+        }
+    }
+
+    fn draw_rules(&self) -> &DrawRules;
+
+    /// Checks if the last recorded position, reached after `moves` moves of the game, is a draw
+    fn is_draw(&self, history: &PositionHistory, moves: u16) -> bool {
+        let draw_rules = self.draw_rules();
+        let by_move_limit = match draw_rules.move_limit {
+            Some(limit) => moves > limit,
+            None => false,
+        };
+        let by_repetition = match draw_rules.repetitions {
+            Some(limit) => history.last_count() >= limit,
+            None => false,
+        };
+        by_move_limit || by_repetition
+    }
+
+    /// Player who makes the first move: the colour stamped on the neutral card
+    fn first_player(&self, neutral_card: &Card) -> PlayerColor {
+        neutral_card.stamp
+    }
+
+    // Same construction as for the `Agent` to be able to clone the rules
+    fn clone_dyn(&self) -> Box<dyn RuleSet>;
+}
+
+impl Clone for Box<dyn RuleSet> {
+    fn clone(&self) -> Self {
+        self.clone_dyn()
+    }
+}
+
+/// Rule set selected in the main menu. Must be used as a resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleSetType {
+    Standard,
+    WayOfTheStream,
+}
+
+impl RuleSetType {
+    /// Finds the rules by the name shown in the main menu
+    pub fn from_name(name: &str) -> Option<Self> {
+        [RuleSetType::Standard, RuleSetType::WayOfTheStream]
+            .into_iter()
+            .find(|rules| rules.to_string() == name)
+    }
+
+    pub fn build(&self, draw_rules: DrawRules) -> Box<dyn RuleSet> {
+        match self {
+            RuleSetType::Standard => Box::new(StandardRules::new(draw_rules)),
+            RuleSetType::WayOfTheStream => Box::new(WayOfTheStreamRules::new(draw_rules)),
+        }
+    }
+}
+
+impl Default for RuleSetType {
+    fn default() -> Self {
+        Self::Standard
+    }
+}
+
+impl ToString for RuleSetType {
+    fn to_string(&self) -> String {
+        match self {
+            RuleSetType::Standard => "Standard".to_owned(),
+            RuleSetType::WayOfTheStream => "Way of the Stream".to_owned(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standard_rules(move_limit: Option<u16>) -> StandardRules {
+        StandardRules::new(DrawRules {
+            repetitions: None,
+            move_limit,
+        })
+    }
+
+    #[test]
+    fn draws_right_after_the_move_limit() {
+        let history = PositionHistory::new();
+        let rules = standard_rules(Some(4));
+        assert!(!rules.is_draw(&history, 0));
+        assert!(!rules.is_draw(&history, 4));
+        assert!(rules.is_draw(&history, 5));
+
+        assert!(!standard_rules(None).is_draw(&history, u16::MAX));
+    }
+}
@@ -0,0 +1,32 @@
+use crate::resources::draw_rules::DrawRules;
+
+use super::rule_set::RuleSet;
+
+/// Standard rules: the game is won by capturing the enemy master or
+/// by moving the own master into the enemy temple
+#[derive(Debug, Clone)]
+pub struct StandardRules {
+    pub draw_rules: DrawRules,
+}
+
+impl StandardRules {
+    pub fn new(draw_rules: DrawRules) -> Self {
+        Self { draw_rules }
+    }
+}
+
+impl Default for StandardRules {
+    fn default() -> Self {
+        Self::new(DrawRules::default())
+    }
+}
+
+impl RuleSet for StandardRules {
+    fn draw_rules(&self) -> &DrawRules {
+        &self.draw_rules
+    }
+
+    fn clone_dyn(&self) -> Box<dyn RuleSet> {
+        Box::new(self.clone())
+    }
+}
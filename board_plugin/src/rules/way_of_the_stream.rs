@@ -0,0 +1,30 @@
+use crate::resources::draw_rules::DrawRules;
+
+use super::rule_set::RuleSet;
+
+/// The game is won only by moving the own master into the enemy temple.
+/// Masters can not be captured
+#[derive(Debug, Clone)]
+pub struct WayOfTheStreamRules {
+    pub draw_rules: DrawRules,
+}
+
+impl WayOfTheStreamRules {
+    pub fn new(draw_rules: DrawRules) -> Self {
+        Self { draw_rules }
+    }
+}
+
+impl RuleSet for WayOfTheStreamRules {
+    fn win_by_capture(&self) -> bool {
+        false
+    }
+
+    fn draw_rules(&self) -> &DrawRules {
+        &self.draw_rules
+    }
+
+    fn clone_dyn(&self) -> Box<dyn RuleSet> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,375 @@
+use std::cmp::Ordering;
+
+use bevy::{ecs::system::EntityCommands, log, prelude::*};
+
+use crate::resources::{
+    app_state::AppState,
+    depth::Depth,
+    engine_options::EngineOptions,
+    game_result::GameResult,
+    game_state::{GameState, PlayerColor, PlayerType},
+    statistics::{Participant, Statistics, MAX_PROFILE_NAME_LENGTH},
+};
+
+const BUTTON_NORMAL: Color = Color::rgb(0.25, 0.25, 0.25);
+const BUTTON_HOVERED: Color = Color::rgb(0.35, 0.35, 0.35);
+const BUTTON_PRESSED: Color = Color::rgb(0.35, 0.75, 0.35);
+
+struct StatsData {
+    camera_entity: Entity,
+    root: Entity,
+}
+
+/// Name of the profile which is typed on the statistics screen
+#[derive(Debug, Clone, Default)]
+struct NewProfileName(String);
+
+/// Records the finished games and shows the statistics screen
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        log::info!("Loaded statistics plugin!");
+        app.add_system_set(SystemSet::on_enter(AppState::Statistics).with_system(setup_ui))
+            .add_system_set(
+                SystemSet::on_update(AppState::Statistics)
+                    .with_system(button_system)
+                    .with_system(profile_name_input_system.label("profile_name_input"))
+                    .with_system(update_stats_texts.after("profile_name_input")),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::Statistics).with_system(cleanup))
+            .add_system_set(
+                SystemSet::on_update(AppState::GameEnd).with_system(record_game_result),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+enum StatsButtonAction {
+    PreviousProfile,
+    NextProfile,
+    AddProfile,
+    Back,
+}
+
+#[derive(Component)]
+struct ProfileLabel;
+
+#[derive(Component)]
+struct NewProfileLabel;
+
+#[derive(Component)]
+struct StatsText;
+
+/// Side of the game as it is counted in the statistics
+fn participant(
+    player_type: PlayerType,
+    statistics: &Statistics,
+    depth: &Depth,
+    engine_options: &EngineOptions,
+) -> Participant {
+    match player_type {
+        PlayerType::Human => Participant::Profile(statistics.active_profile.clone()),
+        PlayerType::Random => Participant::Bot("Random".to_owned()),
+        PlayerType::AlphaBeta => Participant::Bot(format!("AlphaBeta depth {}", depth.0)),
+        PlayerType::External => {
+            Participant::Bot(format!("External {}", engine_options.to_string()))
+        }
+        PlayerType::Remote => Participant::Remote,
+    }
+}
+
+/// Counts the game once its result is known. Games left with the buttons have no result
+fn record_game_result(
+    game_result: Option<Res<GameResult>>,
+    game_state: Res<GameState>,
+    depth: Res<Depth>,
+    engine_options: Res<EngineOptions>,
+    mut statistics: ResMut<Statistics>,
+) {
+    let game_result = match game_result {
+        Some(game_result) if game_result.is_added() => game_result,
+        _ => return,
+    };
+
+    let [red, blue] = [PlayerColor::Red, PlayerColor::Blue].map(|color| {
+        participant(
+            game_state.get_player(&color).player_type,
+            &statistics,
+            &depth,
+            &engine_options,
+        )
+    });
+    statistics.record_game(&red, &blue, game_result.winner);
+    statistics.save();
+}
+
+fn statistics_text(statistics: &Statistics) -> String {
+    let profile = &statistics.active_profile;
+    let rating = statistics
+        .rating(&Participant::Profile(profile.clone()))
+        .unwrap_or_default();
+
+    let mut lines = vec![format!("Rating of {}: {}", profile, rating.to_string())];
+    lines.push(String::new());
+    lines.push("Results against the opponents:".to_owned());
+    let records = statistics.records_of(profile).collect::<Vec<_>>();
+    if records.is_empty() {
+        lines.push("No finished games yet".to_owned());
+    }
+    for (opponent, record) in records {
+        lines.push(format!("{} - {}", opponent, record.to_string()));
+    }
+
+    lines.push(String::new());
+    lines.push("Ratings:".to_owned());
+    let mut ratings = statistics
+        .profiles
+        .iter()
+        .chain(statistics.bots.iter())
+        .collect::<Vec<_>>();
+    ratings.sort_by(|a, b| b.1.elo.partial_cmp(&a.1.elo).unwrap_or(Ordering::Equal));
+    for (name, rating) in ratings {
+        lines.push(format!("{} - {}", name, rating.to_string()));
+    }
+
+    lines.join("\n")
+}
+
+fn button_system(
+    mut buttons: Query<
+        (&Interaction, &StatsButtonAction, &mut UiColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut statistics: ResMut<Statistics>,
+    mut new_profile_name: ResMut<NewProfileName>,
+    mut state: ResMut<State<AppState>>,
+) {
+    for (interaction, action, mut color) in buttons.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => *color = BUTTON_PRESSED.into(),
+            Interaction::Hovered => {
+                *color = BUTTON_HOVERED.into();
+                continue;
+            }
+            Interaction::None => {
+                *color = BUTTON_NORMAL.into();
+                continue;
+            }
+        }
+
+        match action {
+            StatsButtonAction::PreviousProfile => statistics.prev_profile(),
+            StatsButtonAction::NextProfile => statistics.next_profile(),
+            StatsButtonAction::AddProfile => match statistics.add_profile(&new_profile_name.0) {
+                Ok(()) => new_profile_name.0.clear(),
+                Err(e) => log::info!("{}", e),
+            },
+            StatsButtonAction::Back => {
+                state.set(AppState::MainMenu).unwrap();
+                continue;
+            }
+        }
+        log::info!("Active profile is {}", statistics.active_profile);
+        statistics.save();
+    }
+}
+
+fn profile_name_input_system(
+    keys: Res<Input<KeyCode>>,
+    mut new_profile_name: ResMut<NewProfileName>,
+    mut statistics: ResMut<Statistics>,
+    mut received_character_rdr: EventReader<ReceivedCharacter>,
+) {
+    if keys.just_pressed(KeyCode::Back) {
+        new_profile_name.0.pop();
+    }
+
+    if keys.just_pressed(KeyCode::Return) {
+        match statistics.add_profile(&new_profile_name.0) {
+            Ok(()) => {
+                new_profile_name.0.clear();
+                statistics.save();
+            }
+            Err(e) => log::info!("{}", e),
+        }
+    }
+
+    for event in received_character_rdr.iter() {
+        let is_allowed = event.char.is_ascii_alphanumeric() || event.char == ' ';
+        if is_allowed && new_profile_name.0.len() < MAX_PROFILE_NAME_LENGTH {
+            new_profile_name.0.push(event.char);
+        }
+    }
+}
+
+fn update_stats_texts(
+    statistics: Res<Statistics>,
+    new_profile_name: Res<NewProfileName>,
+    mut profile_labels: Query<
+        &mut Text,
+        (
+            With<ProfileLabel>,
+            Without<NewProfileLabel>,
+            Without<StatsText>,
+        ),
+    >,
+    mut new_profile_labels: Query<
+        &mut Text,
+        (
+            With<NewProfileLabel>,
+            Without<ProfileLabel>,
+            Without<StatsText>,
+        ),
+    >,
+    mut stats_texts: Query<
+        &mut Text,
+        (
+            With<StatsText>,
+            Without<ProfileLabel>,
+            Without<NewProfileLabel>,
+        ),
+    >,
+) {
+    if statistics.is_changed() {
+        for mut text in profile_labels.iter_mut() {
+            text.sections[0].value = statistics.active_profile.clone();
+        }
+        for mut text in stats_texts.iter_mut() {
+            text.sections[0].value = statistics_text(&statistics);
+        }
+    }
+
+    if new_profile_name.is_changed() {
+        for mut text in new_profile_labels.iter_mut() {
+            text.sections[0].value = format!("{}_", new_profile_name.0);
+        }
+    }
+}
+
+fn setup_ui(mut commands: Commands, asset_server: Res<AssetServer>, statistics: Res<Statistics>) {
+    let camera_entity = commands.spawn_bundle(UiCameraBundle::default()).id();
+    let font = asset_server.load("fonts/pixeled.ttf");
+    let text_font = asset_server.load("fonts/orange_kid.ttf");
+
+    let root = commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                // the children are placed from the top
+                flex_direction: FlexDirection::ColumnReverse,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::FlexStart,
+                ..Default::default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .insert(Name::new("Statistics"))
+        .with_children(|parent| {
+            spawn_text(parent, "Statistics", font.clone(), 80.);
+
+            spawn_row(parent, |row| {
+                spawn_text(row, "Profile: ", font.clone(), 40.);
+                spawn_button(row, "<", font.clone(), StatsButtonAction::PreviousProfile);
+                spawn_text(row, &statistics.active_profile, font.clone(), 30.).insert(ProfileLabel);
+                spawn_button(row, ">", font.clone(), StatsButtonAction::NextProfile);
+            });
+
+            spawn_row(parent, |row| {
+                spawn_text(row, "New profile: ", font.clone(), 40.);
+                spawn_text(row, "_", font.clone(), 30.).insert(NewProfileLabel);
+                spawn_button(row, "ADD", font.clone(), StatsButtonAction::AddProfile);
+            });
+
+            spawn_text(parent, &statistics_text(&statistics), text_font, 30.).insert(StatsText);
+
+            spawn_row(parent, |row| {
+                spawn_button(row, "BACK", font.clone(), StatsButtonAction::Back);
+            });
+        })
+        .id();
+
+    commands.insert_resource(NewProfileName::default());
+    commands.insert_resource(StatsData {
+        camera_entity,
+        root,
+    });
+}
+
+fn spawn_row(parent: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(80.), Val::Auto),
+                margin: Rect::all(Val::Px(10.)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .with_children(children);
+}
+
+fn spawn_text<'w, 's, 'a>(
+    parent: &'a mut ChildBuilder<'w, 's, '_>,
+    value: &str,
+    font: Handle<Font>,
+    font_size: f32,
+) -> EntityCommands<'w, 's, 'a> {
+    parent.spawn_bundle(TextBundle {
+        style: Style {
+            margin: Rect::all(Val::Px(10.)),
+            ..Default::default()
+        },
+        text: Text::with_section(
+            value,
+            TextStyle {
+                font,
+                font_size,
+                color: Color::WHITE,
+            },
+            TextAlignment {
+                vertical: VerticalAlign::Center,
+                horizontal: HorizontalAlign::Center,
+            },
+        ),
+        ..Default::default()
+    })
+}
+
+fn spawn_button(
+    parent: &mut ChildBuilder,
+    text: &str,
+    font: Handle<Font>,
+    action: StatsButtonAction,
+) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Percent(15.), Val::Auto),
+                margin: Rect::all(Val::Px(10.)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: BUTTON_NORMAL.into(),
+            ..Default::default()
+        })
+        .insert(action)
+        .insert(Name::new(text.to_string()))
+        .with_children(|builder| {
+            spawn_text(builder, text, font, 30.);
+        });
+}
+
+fn cleanup(mut commands: Commands, stats_data: Res<StatsData>) {
+    commands.entity(stats_data.root).despawn_recursive();
+    commands
+        .entity(stats_data.camera_entity)
+        .despawn_recursive();
+    commands.remove_resource::<StatsData>();
+    commands.remove_resource::<NewProfileName>();
+}
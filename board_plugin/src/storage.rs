@@ -0,0 +1,67 @@
+//! Text data kept between the sessions of the game.
+//!
+//! Native builds keep every entry in a file of the working directory,
+//! the web build keeps it in the localStorage of the browser under the same name.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use native::{load, remove, save};
+#[cfg(target_arch = "wasm32")]
+pub use web::{load, remove, save};
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{fs, io::ErrorKind};
+
+    /// Returns `Ok(None)` if nothing is saved under the name yet
+    pub fn load(name: &str) -> Result<Option<String>, String> {
+        match fs::read_to_string(name) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("Could not read {}: {}", name, e)),
+        }
+    }
+
+    pub fn save(name: &str, content: &str) -> Result<(), String> {
+        fs::write(name, content).map_err(|e| format!("Could not write {}: {}", name, e))
+    }
+
+    pub fn remove(name: &str) -> Result<(), String> {
+        match fs::remove_file(name) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Could not remove {}: {}", name, e)),
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use web_sys::Storage;
+
+    fn local_storage() -> Result<Storage, String> {
+        web_sys::window()
+            .ok_or_else(|| "There is no browser window".to_owned())?
+            .local_storage()
+            .map_err(|e| format!("{:?}", e))?
+            .ok_or_else(|| "localStorage is not available".to_owned())
+    }
+
+    /// Returns `Ok(None)` if nothing is saved under the name yet
+    pub fn load(name: &str) -> Result<Option<String>, String> {
+        local_storage()?
+            .get_item(name)
+            .map_err(|e| format!("Could not read {}: {:?}", name, e))
+    }
+
+    pub fn save(name: &str, content: &str) -> Result<(), String> {
+        local_storage()?
+            .set_item(name, content)
+            .map_err(|e| format!("Could not write {}: {:?}", name, e))
+    }
+
+    pub fn remove(name: &str) -> Result<(), String> {
+        local_storage()?
+            .remove_item(name)
+            .map_err(|e| format!("Could not remove {}: {:?}", name, e))
+    }
+}
@@ -1,69 +1,149 @@
 use crate::{
     components::{board_tile::BoardTile, coordinates::Coordinates, pieces::Piece},
     events::{
-        BotMakeMoveEvent, CardSwapEvent, GenerateBotMoveEvent, NextTurnEvent,
+        BotMakeMoveEvent, CardSwapEvent, GenerateBotMoveEvent, MoveMadeEvent,
         ProcessWinConditionEvent,
     },
     resources::{
-        board::Board, board_assets::BoardAssets, deck::Deck, game_state::GameState,
-        tile_map::MoveResult, text_handler::EvaluationResult,
+        board::Board, clocks::Clocks, deck::Deck, game_state::GameState,
+        text_handler::EvaluationResult, tile_map::Move,
     },
-    BoardPlugin,
+    systems::piece_animation::start_move_animation,
 };
-use bevy::{log, prelude::*};
+use bevy::{log, prelude::*, utils::Instant};
 
 pub fn generate_bot_move(
     board: Res<Board>,
     game_state: Res<GameState>,
     deck: Res<Deck>,
+    mut clocks: ResMut<Clocks>,
     mut evaluation_result: ResMut<EvaluationResult>,
     mut random_bot_move_rdr: EventReader<GenerateBotMoveEvent>,
     mut bot_make_move_ewr: EventWriter<BotMakeMoveEvent>,
+    mut process_win_condition_ewr: EventWriter<ProcessWinConditionEvent>,
 ) {
     for _ in random_bot_move_rdr.iter() {
         let current_player = game_state.get_current_player();
-        let (card, mov, score) = current_player
-            .agent
-            .generate_move(&board, &game_state, &deck);
+        let started = Instant::now();
+        // in a timed game the bots think as long as their clocks allow
+        let (card, mov, score) = match clocks.move_budget(&game_state.curr_color) {
+            Some(time_budget) => current_player
+                .agent
+                .generate_move_in_time(&board, &game_state, &deck, time_budget),
+            None => current_player
+                .agent
+                .generate_move(&board, &game_state, &deck),
+        };
 
-        evaluation_result.score = score;
+        // the search blocks the frame, the next frame passes while the piece slides and
+        // `update_clocks` does not charge it, so the thinking time is charged here
+        let color = game_state.curr_color;
+        if clocks.tick(&color, started.elapsed()) {
+            log::info!("{:?} has run out of time while thinking", color);
+            process_win_condition_ewr.send(ProcessWinConditionEvent::FlagFall(color));
+            return;
+        }
 
-        if card.is_none() || mov.is_none() {
-            log::info!("Card or move is None!");
+        // an external engine thinks on another thread, its move is read by `poll_bot_move`
+        if current_player.agent.is_thinking() {
+            log::info!("Waiting for the {:?} engine to move", color);
             return;
         }
 
-        let card = card.unwrap();
-        let mov = mov.unwrap();
+        send_bot_move(
+            (card, mov, score),
+            &game_state,
+            &deck,
+            &mut evaluation_result,
+            &mut bot_make_move_ewr,
+            &mut process_win_condition_ewr,
+        );
+    }
+}
+
+/// Reads the move of a bot searching on another thread. The frames go on while it thinks,
+/// so its clock is run by `update_clocks`
+pub fn poll_bot_move(
+    board: Res<Board>,
+    game_state: Res<GameState>,
+    deck: Res<Deck>,
+    mut evaluation_result: ResMut<EvaluationResult>,
+    mut bot_make_move_ewr: EventWriter<BotMakeMoveEvent>,
+    mut process_win_condition_ewr: EventWriter<ProcessWinConditionEvent>,
+) {
+    let agent = &game_state.get_current_player().agent;
+    if !agent.is_thinking() {
+        return;
+    }
 
-        log::info!(
-            "Bot move is {:?} and used card is {:?} and score is {:?}",
-            mov,
-            deck.cardboards.get(&card).unwrap().card.name,
-            score
+    if let Some(result) = agent.poll_move(&board, &game_state, &deck) {
+        send_bot_move(
+            result,
+            &game_state,
+            &deck,
+            &mut evaluation_result,
+            &mut bot_make_move_ewr,
+            &mut process_win_condition_ewr,
         );
+    }
+}
 
-        bot_make_move_ewr.send(BotMakeMoveEvent {
-            mov: mov,
-            card_used: card,
-        });
+fn send_bot_move(
+    (card, mov, score): (Option<Entity>, Option<Move>, i32),
+    game_state: &GameState,
+    deck: &Deck,
+    evaluation_result: &mut EvaluationResult,
+    bot_make_move_ewr: &mut EventWriter<BotMakeMoveEvent>,
+    process_win_condition_ewr: &mut EventWriter<ProcessWinConditionEvent>,
+) {
+    evaluation_result.score = score;
+
+    if card.is_none() || mov.is_none() {
+        log::info!("Card or move is None!");
+        // an external engine has crashed or has not answered in time
+        if let Some(reason) = game_state.get_current_player().agent.failure() {
+            process_win_condition_ewr.send(ProcessWinConditionEvent::Forfeit {
+                color: game_state.curr_color,
+                reason,
+            });
+        }
+        return;
     }
+
+    let card = card.unwrap();
+    let mov = mov.unwrap();
+
+    log::info!(
+        "Bot move is {:?} and used card is {:?} and score is {:?}",
+        mov,
+        deck.cardboards.get(&card).unwrap().card.name,
+        score
+    );
+
+    bot_make_move_ewr.send(BotMakeMoveEvent {
+        mov: mov,
+        card_used: card,
+    });
 }
 
-pub fn bot_make_move<T>(
+pub fn bot_make_move(
     mut commands: Commands,
     mut board: ResMut<Board>,
-    board_assets: Res<BoardAssets>,
     tiles_q: Query<(Entity, &Coordinates), With<BoardTile>>,
     children_q: Query<&Children, With<BoardTile>>,
     pieces_q: Query<&Piece>,
+    transforms_q: Query<&Transform, Without<BoardTile>>,
     mut bot_make_move_rdr: EventReader<BotMakeMoveEvent>,
     mut card_swap_ewr: EventWriter<CardSwapEvent>,
-    mut next_turn_ewr: EventWriter<NextTurnEvent>,
     mut process_win_condition_ewr: EventWriter<ProcessWinConditionEvent>,
+    mut move_made_ewr: EventWriter<MoveMadeEvent>,
+    game_state: Res<GameState>,
 ) {
     for event in bot_make_move_rdr.iter() {
-        let move_result = board.tile_map.make_a_move(event.mov.from, event.mov.to);
+        let move_result =
+            board
+                .tile_map
+                .make_a_move(event.mov.from, event.mov.to, game_state.rules.as_ref());
 
         #[cfg(feature = "debug")]
         {
@@ -71,65 +151,23 @@ pub fn bot_make_move<T>(
             log::info!("Move result is: {:?}", move_result);
         }
 
-        let mut from_tile_entity = None;
-        for (parent, coords) in tiles_q.iter() {
-            if *coords == event.mov.from {
-                from_tile_entity = Some(parent);
-                break;
-            }
-        }
-
-        if from_tile_entity == None {
-            log::warn!("Could not find a tile with coords: {:?}", event.mov);
-            return;
-        }
-
-        // clear the sprite of old selected piece
-        // we should have a selected piece for sure
-        let mut piece = None;
-        if let Ok((parent, _)) = tiles_q.get(from_tile_entity.unwrap()) {
-            if let Ok(children) = children_q.get(parent) {
-                for child_entity in children.iter() {
-                    commands.entity(*child_entity).despawn_recursive();
-                    // save a piece type to know what to spawn on a new place
-                    if let Ok(p) = pieces_q.get(parent) {
-                        piece = Some(*p);
-                    }
-                    commands.entity(parent).remove::<Piece>();
-                    break;
-                }
-            }
-        }
-
-        // set a piece on a new location
-        for (parent, coords) in tiles_q.iter() {
-            // despawn a captured figure
-            if (move_result == MoveResult::Capture || move_result == MoveResult::Win)
-                && *coords == event.mov.to
-            {
-                if let Ok(children) = children_q.get(parent) {
-                    for child_entity in children.iter() {
-                        commands.entity(*child_entity).despawn_recursive();
-                        commands.entity(parent).remove::<Piece>();
-                    }
-                }
-            }
-
-            if *coords == event.mov.to {
-                let mut cmd = commands.entity(parent);
-                BoardPlugin::<T>::spawn_a_piece(
-                    piece,
-                    &mut cmd,
-                    &board_assets,
-                    board.tile_size,
-                    board.padding,
-                );
-                break;
-            }
-        }
+        // the turn is switched when the piece arrives, so the move can be followed
+        start_move_animation(
+            &mut commands,
+            &board,
+            &event.mov,
+            &tiles_q,
+            &children_q,
+            &pieces_q,
+            &transforms_q,
+        );
 
+        move_made_ewr.send(MoveMadeEvent {
+            mov: event.mov,
+            card_used: event.card_used,
+            color: game_state.curr_color,
+        });
         card_swap_ewr.send(CardSwapEvent(event.card_used));
-        process_win_condition_ewr.send(ProcessWinConditionEvent(move_result));
-        next_turn_ewr.send(NextTurnEvent);
+        process_win_condition_ewr.send(ProcessWinConditionEvent::Move(move_result));
     } // event loop
 }
@@ -0,0 +1,98 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    components::{
+        board_tile::BoardTile, card_index::CardIndex, coordinates::Coordinates,
+        keyboard_cursor::KeyboardCursor, last_move::LastMoveTile, texts::RankLabel,
+    },
+    events::{FlipBoardEvent, MirrorCardEvent},
+    resources::{
+        board::Board,
+        deck::{Deck, NEUTRAL_CARD_IDX},
+        dragged_piece::DraggedPiece,
+        pending_turn::PendingTurn,
+    },
+};
+
+/// Turns the board upside down. The rows of the tiles and their numbers are reversed,
+/// the cards of the players change their sides around the neutral card
+pub fn flip_board(
+    mut board: ResMut<Board>,
+    mut deck: ResMut<Deck>,
+    pending_turn: Option<Res<PendingTurn>>,
+    dragged_piece: Option<Res<DraggedPiece>>,
+    mut tiles_q: Query<(&Coordinates, &mut Transform), With<BoardTile>>,
+    mut labels_q: Query<(&RankLabel, &mut Transform), Without<BoardTile>>,
+    mut cursor_q: Query<
+        (&KeyboardCursor, &mut Transform),
+        (Without<BoardTile>, Without<RankLabel>),
+    >,
+    mut last_move_q: Query<
+        (&LastMoveTile, &mut Transform),
+        (
+            Without<BoardTile>,
+            Without<RankLabel>,
+            Without<KeyboardCursor>,
+        ),
+    >,
+    mut cards_q: Query<
+        &mut Transform,
+        (
+            With<CardIndex>,
+            Without<BoardTile>,
+            Without<RankLabel>,
+            Without<KeyboardCursor>,
+            Without<LastMoveTile>,
+        ),
+    >,
+    mut flip_board_rdr: EventReader<FlipBoardEvent>,
+    mut mirror_card_ewr: EventWriter<MirrorCardEvent>,
+) {
+    if flip_board_rdr.iter().last().is_none() {
+        return;
+    }
+    // the moving pieces and cards are not followed
+    if pending_turn.is_some() || dragged_piece.is_some() {
+        log::info!("The board is flipped when nothing moves on it");
+        return;
+    }
+
+    board.is_flipped = !board.is_flipped;
+    log::info!("Flipping the board, flipped: {}", board.is_flipped);
+
+    for (coordinates, mut transform) in tiles_q.iter_mut() {
+        let position = board.tile_position(coordinates);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+    for (label, mut transform) in labels_q.iter_mut() {
+        transform.translation.y =
+            board.row(label.0) as f32 * board.tile_size + board.tile_size / 8.;
+    }
+    if let Ok((cursor, mut transform)) = cursor_q.get_single_mut() {
+        let position = board.tile_position(&cursor.coordinates);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+    for (marker, mut transform) in last_move_q.iter_mut() {
+        let position = board.tile_position(&marker.coordinates);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+    }
+
+    let neutral_card = deck.cards[NEUTRAL_CARD_IDX];
+    let center = match cards_q.get(neutral_card) {
+        Ok(transform) => transform.translation.y,
+        Err(_) => return,
+    };
+    for entity in deck.cards.clone() {
+        if let Ok(mut transform) = cards_q.get_mut(entity) {
+            transform.translation.y = 2. * center - transform.translation.y;
+        }
+        let cardboard = deck.cardboards.get_mut(&entity).unwrap();
+        let bounds = &mut cardboard.bounds;
+        bounds.position.y = 2. * center - bounds.position.y - bounds.size.y;
+
+        mirror_card_ewr.send(MirrorCardEvent(entity));
+    }
+}
@@ -7,18 +7,20 @@ use crate::components::guide_text_timer::GuideTextTimer;
 use crate::components::pieces::{Piece, PieceKind};
 use crate::events::{
     CardSwapEvent, ChangeGuideTextEvent, ColorSelectedPieceEvent, GenerateAllowedMovesEvent,
-    MovePieceEvent, NextTurnEvent, NoCardSelectedEvent, PieceSelectEvent, ProcessWinConditionEvent,
-    ResetAllowedMovesEvent, ResetSelectedCardColorEvent, ResetSelectedPieceColorEvent,
+    MoveMadeEvent, MovePieceEvent, NoCardSelectedEvent, PieceSelectEvent,
+    ProcessWinConditionEvent, ResetAllowedMovesEvent, ResetSelectedCardColorEvent,
+    ResetSelectedPieceColorEvent,
 };
 use crate::resources::board::Board;
 use crate::resources::board_assets::BoardAssets;
 use crate::resources::deck::Deck;
+use crate::resources::pending_turn::PendingTurn;
+use crate::resources::pointer::Pointer;
 use crate::resources::game_state::{GameState, PlayerColor, PlayerType};
 use crate::resources::selected::{SelectedCard, SelectedPiece};
 use crate::resources::tile::TempleTile;
-use crate::resources::tile_map::MoveResult;
-use crate::BoardPlugin;
-use bevy::input::{mouse::MouseButtonInput, ElementState};
+use crate::resources::tile_map::Move;
+use crate::systems::piece_animation::start_move_animation;
 use bevy::log;
 use bevy::prelude::*;
 
@@ -26,36 +28,29 @@ pub fn input_handling(
     game_state: Res<GameState>,
     windows: Res<Windows>,
     board: Res<Board>,
-    mut button_evr: EventReader<MouseButtonInput>,
+    pointer: Res<Pointer>,
+    pending_turn: Option<Res<PendingTurn>>,
     mut tile_trigger_ewr: EventWriter<PieceSelectEvent>,
 ) {
-    // do not handle input when it is not a player turn
-    if game_state.get_current_player().player_type != PlayerType::Human {
+    // do not handle input when it is not a player turn, spectators never have one.
+    // The turn is not over until the moved piece arrives
+    if game_state.is_spectating()
+        || game_state.get_current_player().player_type != PlayerType::Human
+        || pending_turn.is_some()
+    {
         return;
     }
 
-    let window = windows.get_primary().unwrap();
+    // If the mouse button is pressed or the board is touched
+    if !pointer.just_pressed {
+        return;
+    }
 
-    for event in button_evr.iter() {
-        // If mouse button is pressed
-        if let ElementState::Pressed = event.state {
-            // get the current mouse position in the window
-            let position = window.cursor_position();
-            if let Some(pos) = position {
-                log::trace!("Mouse button pressed: {:?} at {}", event.button, pos);
-                let tile_coordinates = board.mouse_position(window, pos);
-                // if mouse was pressed on the tile map
-                if let Some(coordinates) = tile_coordinates {
-                    match event.button {
-                        MouseButton::Left => {
-                            log::info!("Pressed to tile on {}", coordinates);
-                            tile_trigger_ewr.send(PieceSelectEvent(coordinates));
-                        }
-                        _ => (),
-                    }
-                }
-            }
-        }
+    let window = windows.get_primary().unwrap();
+    // if the pointer was pressed on the tile map
+    if let Some(coordinates) = board.pointer_position(window, &pointer) {
+        log::info!("Pressed to tile on {}", coordinates);
+        tile_trigger_ewr.send(PieceSelectEvent(coordinates));
     }
 }
 
@@ -80,11 +75,22 @@ pub fn process_selected_tile(
         log::info!("Selected piece: {:?}", selected_piece);
         // if no cards selected, do not allow to choose a piece
         if selected_card.entity == None {
+            // the own pieces are dragged without a card
+            let is_own_piece = pieces_parents_q.iter().any(|(parent, coords)| {
+                *coords == event.0
+                    && pieces_q
+                        .get(parent)
+                        .map_or(false, |piece| piece.color == game_state.curr_color)
+            });
+            if is_own_piece {
+                continue;
+            }
+
             change_guide_text_ewr.send(ChangeGuideTextEvent {
                 text: "Please, select a card first!".to_owned(),
             });
             commands.spawn().insert(GuideTextTimer {
-                old_text: "Red to move. Select a card!".to_owned(),
+                old_text: format!("{} to move. Select a card!", game_state.curr_color.name()),
                 timer: Timer::new(Duration::from_secs(1), false),
             });
             no_card_selected_ewr.send(NoCardSelectedEvent);
@@ -205,7 +211,8 @@ pub fn generate_allowed_moves(
     deck: Res<Deck>,
     game_state: Res<GameState>,
     selected_card: Res<SelectedCard>,
-    mut tiles_q: Query<(Entity, &Coordinates, &mut Sprite), With<BoardTile>>,
+    board_assets: Res<BoardAssets>,
+    mut tiles_q: Query<(Entity, &Coordinates, &mut Sprite, &mut Handle<Image>), With<BoardTile>>,
     children_q: Query<&Children, With<BoardTile>>,
     mut visibility_q: Query<&mut Visibility>,
     mut generate_allowed_moves_rdr: EventReader<GenerateAllowedMovesEvent>,
@@ -227,7 +234,7 @@ pub fn generate_allowed_moves(
 
         log::info!("Allowed moves: {:?}", allowed_moves);
 
-        for (entity, coords, mut sprite) in tiles_q.iter_mut() {
+        for (entity, coords, mut sprite, mut texture) in tiles_q.iter_mut() {
             if allowed_moves.contains(coords) {
                 if let Ok(children) = children_q.get(entity) {
                     for child in children.iter() {
@@ -236,7 +243,8 @@ pub fn generate_allowed_moves(
                         }
                     }
                 }
-                sprite.color = Color::TOMATO;
+                sprite.color = board_assets.allowed_move_tile_material.color;
+                *texture = board_assets.allowed_move_tile_material.texture.clone();
                 commands.entity(entity).insert(AllowedMove);
             }
         }
@@ -246,13 +254,16 @@ pub fn generate_allowed_moves(
 pub fn reset_allowed_moves(
     mut commands: Commands,
     board_assets: Res<BoardAssets>,
-    mut tiles_q: Query<(Entity, &mut Sprite, Option<&TempleTile>), With<AllowedMove>>,
+    mut tiles_q: Query<
+        (Entity, &mut Sprite, &mut Handle<Image>, Option<&TempleTile>),
+        With<AllowedMove>,
+    >,
     children_q: Query<&Children, With<BoardTile>>,
     mut visibility_q: Query<&mut Visibility>,
     mut reset_allowed_moves_event: EventReader<ResetAllowedMovesEvent>,
 ) {
     for _ in reset_allowed_moves_event.iter() {
-        for (entity, mut sprite, temple) in tiles_q.iter_mut() {
+        for (entity, mut sprite, mut texture, temple) in tiles_q.iter_mut() {
             if let Ok(children) = children_q.get(entity) {
                 for child in children.iter() {
                     if let Ok(mut visibility) = visibility_q.get_mut(*child) {
@@ -260,38 +271,43 @@ pub fn reset_allowed_moves(
                     }
                 }
             }
-            if temple.is_none() {
-                sprite.color = board_assets.tile_material.color;
+            let material = if temple.is_none() {
+                &board_assets.tile_material
             } else {
-                sprite.color = board_assets.temple_tile_material.color;
-            }
-            
+                &board_assets.temple_tile_material
+            };
+            sprite.color = material.color;
+            *texture = material.texture.clone();
+
             commands.entity(entity).remove::<AllowedMove>();
         }
     }
 }
 
-pub fn move_piece<T>(
+pub fn move_piece(
     mut commands: Commands,
     mut board: ResMut<Board>,
     mut selected_piece: ResMut<SelectedPiece>,
     mut selected_card: ResMut<SelectedCard>,
-    board_assets: Res<BoardAssets>,
     tiles_q: Query<(Entity, &Coordinates), With<BoardTile>>,
     children_q: Query<&Children, With<BoardTile>>,
     pieces_q: Query<&Piece>,
+    transforms_q: Query<&Transform, Without<BoardTile>>,
     mut move_piece_rdr: EventReader<MovePieceEvent>,
     mut reset_selected_card_ewr: EventWriter<ResetSelectedCardColorEvent>,
     mut card_swap_ewr: EventWriter<CardSwapEvent>,
-    mut next_turn_ewr: EventWriter<NextTurnEvent>,
     mut process_win_condition_ewr: EventWriter<ProcessWinConditionEvent>,
+    mut move_made_ewr: EventWriter<MoveMadeEvent>,
+    game_state: Res<GameState>,
 ) {
-    // TODO: for a better handling of a piece movement, it could be better to use a bundle
-    // with a piece and a sprite
     for event in move_piece_rdr.iter() {
+        let mov = Move {
+            from: selected_piece.coordinates.unwrap(),
+            to: event.0,
+        };
         let move_result = board
             .tile_map
-            .make_a_move(selected_piece.coordinates.unwrap(), event.0);
+            .make_a_move(mov.from, mov.to, game_state.rules.as_ref());
 
         #[cfg(feature = "debug")]
         {
@@ -299,55 +315,26 @@ pub fn move_piece<T>(
             log::info!("Move result is: {:?}", move_result);
         }
 
-        // clear the sprite of old selected piece
-        // we should have a selected piece for sure
-        let mut piece = None;
-        if let Ok((parent, _)) = tiles_q.get(selected_piece.entity.unwrap()) {
-            if let Ok(children) = children_q.get(parent) {
-                for child_entity in children.iter() {
-                    commands.entity(*child_entity).despawn_recursive();
-                    // save a piece type to know what to spawn on a new place
-                    if let Ok(p) = pieces_q.get(parent) {
-                        piece = Some(*p);
-                    }
-                    commands.entity(parent).remove::<Piece>();
-                    break;
-                }
-            }
-        }
-
-        // set a piece on a new location
-        for (parent, coords) in tiles_q.iter() {
-            // despawn a captured figure
-            if (move_result == MoveResult::Capture || move_result == MoveResult::Win)
-                && *coords == event.0
-            {
-                if let Ok(children) = children_q.get(parent) {
-                    for child_entity in children.iter() {
-                        commands.entity(*child_entity).despawn_recursive();
-                        commands.entity(parent).remove::<Piece>();
-                    }
-                }
-            }
-
-            if *coords == event.0 {
-                let mut cmd = commands.entity(parent);
-                BoardPlugin::<T>::spawn_a_piece(
-                    piece,
-                    &mut cmd,
-                    &board_assets,
-                    board.tile_size,
-                    board.padding,
-                );
-                break;
-            }
-        }
+        // the turn is switched when the piece arrives
+        start_move_animation(
+            &mut commands,
+            &board,
+            &mov,
+            &tiles_q,
+            &children_q,
+            &pieces_q,
+            &transforms_q,
+        );
 
         selected_piece.clear();
+        move_made_ewr.send(MoveMadeEvent {
+            mov,
+            card_used: selected_card.entity.unwrap(),
+            color: game_state.curr_color,
+        });
         card_swap_ewr.send(CardSwapEvent(selected_card.entity.unwrap()));
         reset_selected_card_ewr.send(ResetSelectedCardColorEvent(selected_card.entity.unwrap()));
         selected_card.entity = None;
-        process_win_condition_ewr.send(ProcessWinConditionEvent(move_result));
-        next_turn_ewr.send(NextTurnEvent);
+        process_win_condition_ewr.send(ProcessWinConditionEvent::Move(move_result));
     }
 }
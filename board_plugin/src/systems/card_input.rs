@@ -1,5 +1,6 @@
 use crate::components::background::Background;
 use crate::components::board_tile::BoardTile;
+use crate::components::card_animation::CardTween;
 use crate::components::card_board::CardOwner;
 use crate::components::card_index::CardIndex;
 use crate::components::coordinates::Coordinates;
@@ -11,9 +12,13 @@ use crate::resources::board::Board;
 use crate::resources::board_assets::BoardAssets;
 use crate::resources::deck::{Deck, NEUTRAL_CARD_IDX};
 use crate::resources::game_state::{GameState, PlayerColor};
+use crate::resources::layout::Layout;
+use crate::resources::pending_turn::PendingTurn;
+use crate::resources::pointer::Pointer;
 use crate::resources::selected::{SelectedCard, SelectedPiece};
 use bevy::log;
 use bevy::prelude::*;
+use std::f32::consts::PI;
 
 pub fn card_selection_handling(
     board: Res<Board>,
@@ -22,64 +27,66 @@ pub fn card_selection_handling(
     mut selected_piece: ResMut<SelectedPiece>,
     deck: Res<Deck>,
     windows: Res<Windows>,
-    mouse_button_inputs: Res<Input<MouseButton>>,
+    pointer: Res<Pointer>,
+    pending_turn: Option<Res<PendingTurn>>,
     colors_q: Query<&CardOwner>,
     mut color_selected_card_ewr: EventWriter<ColorSelectedCardEvent>,
     mut reset_selected_card_color_ewr: EventWriter<ResetSelectedCardColorEvent>,
     mut reset_selected_piece_color_ewr: EventWriter<ResetSelectedPieceColorEvent>,
     mut reset_allowed_moves_ewr: EventWriter<ResetAllowedMovesEvent>,
 ) {
+    // spectators only watch, the cards are not selected while the moved piece is sliding
+    if game_state.is_spectating() || pending_turn.is_some() {
+        return;
+    }
+
     let window = windows.get_primary().unwrap();
 
     let mut was_card_selected = false;
 
-    if mouse_button_inputs.just_pressed(MouseButton::Left) {
-        let position = window.cursor_position();
-
-        // Do not reset the selected card if the mouse position is within the board
-        if let Some(pos) = position {
-            if board.in_bounds(&window, pos) {
-                return;
-            }
+    if pointer.just_pressed {
+        // Do not reset the selected card if the pointer position is within the board
+        if board.pointer_position(window, &pointer).is_some() {
+            return;
         }
 
         let curr_color = game_state.curr_color;
 
         for (card_board_entity, card_board) in deck.cardboards.iter() {
-            if let Some(pos) = position {
-                if card_board.in_bounds(&window, pos) {
-                    if let Ok(card_owner) = colors_q.get(*card_board_entity) {
-                        if !card_owner.does_belong_to_player(&curr_color) {
-                            log::info!("Cannot select a card with a different color!");
-                            return;
-                        }
+            if card_board.in_pointer_bounds(window, &pointer) {
+                if let Ok(card_owner) = colors_q.get(*card_board_entity) {
+                    if !card_owner.does_belong_to_player(&curr_color) {
+                        log::info!("Cannot select a card with a different color!");
+                        return;
                     }
+                }
 
-                    // Check if there is an already selected card. Clear its color
-                    if let Some(entity) = selected_card.entity {
-                        // skip rerendering the same selected card
-                        if entity == *card_board_entity {
-                            was_card_selected = true;
-                            continue;
-                        }
+                // Check if there is an already selected card. Clear its color
+                if let Some(entity) = selected_card.entity {
+                    // skip rerendering the same selected card
+                    if entity == *card_board_entity {
+                        was_card_selected = true;
+                        break;
+                    }
 
-                        reset_selected_card_color_ewr
-                            .send(ResetSelectedCardColorEvent(selected_card.entity.unwrap()));
-                        selected_card.entity = None;
+                    reset_selected_card_color_ewr
+                        .send(ResetSelectedCardColorEvent(selected_card.entity.unwrap()));
+                    selected_card.entity = None;
 
-                        // Reset the selected piece if the card was changed
-                        if selected_piece.entity != None {
-                            reset_selected_piece_color_ewr
-                                .send(ResetSelectedPieceColorEvent(selected_piece.entity.unwrap()));
-                            reset_allowed_moves_ewr.send(ResetAllowedMovesEvent);
-                            selected_piece.clear();
-                        }
+                    // Reset the selected piece if the card was changed
+                    if selected_piece.entity != None {
+                        reset_selected_piece_color_ewr
+                            .send(ResetSelectedPieceColorEvent(selected_piece.entity.unwrap()));
+                        reset_allowed_moves_ewr.send(ResetAllowedMovesEvent);
+                        selected_piece.clear();
                     }
-                    // Set a new selected card
-                    selected_card.entity = Some(*card_board_entity);
-                    color_selected_card_ewr.send(ColorSelectedCardEvent(*card_board_entity));
-                    was_card_selected = true;
                 }
+                // Set a new selected card
+                selected_card.entity = Some(*card_board_entity);
+                color_selected_card_ewr.send(ColorSelectedCardEvent(*card_board_entity));
+                was_card_selected = true;
+                // the enlarged areas of the touched cards may overlap
+                break;
             }
         }
 
@@ -163,11 +170,14 @@ pub fn blink_non_selected_card(
     }
 }
 
+/// Puts the used card to the middle and gives the neutral card to the player.
+/// The cards are moved on the screen by `animate_cards`
 pub fn card_swap(
+    mut commands: Commands,
+    board: Res<Board>,
     mut deck: ResMut<Deck>,
-    mut transform_q: Query<(&mut Transform, &mut CardIndex, &mut CardOwner)>,
+    mut transform_q: Query<(&Transform, &mut CardIndex, &mut CardOwner)>,
     mut card_swap_rdr: EventReader<CardSwapEvent>,
-    mut mirror_card_ewr: EventWriter<MirrorCardEvent>,
 ) {
     for event in card_swap_rdr.iter() {
         // saving the entity ids for swapping
@@ -203,7 +213,9 @@ pub fn card_swap(
         // reading the center card properties
         let (cen_transform, cen_card_index, cen_card_owner) = match transform_q.get(neutral_entity)
         {
-            Ok(query) => query,
+            Ok((transform, card_index, card_owner)) => {
+                (*transform, card_index.clone(), card_owner.clone())
+            }
             Err(e) => {
                 log::warn!("Error raised when swaping cards: {:?}", e);
                 return;
@@ -211,7 +223,7 @@ pub fn card_swap(
         };
 
         // preparing swapping card properties for the change
-        let (mut sw_transform, mut sw_card_index, mut sw_card_owner) =
+        let (sw_transform, mut sw_card_index, mut sw_card_owner) =
             match transform_q.get_mut(swapping_entity) {
                 Ok(query) => query,
                 Err(e) => {
@@ -225,35 +237,80 @@ pub fn card_swap(
             .swap(cen_card_index.0 as usize, sw_card_index.0 as usize);
 
         // saving temporary values for the swap
-        let temp_transform = sw_transform.clone();
+        let temp_transform = *sw_transform;
         let temp_card_index = sw_card_index.clone();
         let temp_cardowner = sw_card_owner.clone();
 
-        *sw_transform = cen_transform.clone();
-        *sw_card_index = cen_card_index.clone();
-        *sw_card_owner = cen_card_owner.clone();
+        *sw_card_index = cen_card_index;
+        *sw_card_owner = cen_card_owner;
 
         // changing the center card
-        let (mut cen_transform, mut cen_card_index, mut cen_card_owner) =
-            match transform_q.get_mut(neutral_entity) {
-                Ok(query) => query,
-                Err(e) => {
-                    log::warn!("Error raised when swaping cards: {:?}", e);
-                    return;
-                }
-            };
+        let (_, mut cen_card_index, mut cen_card_owner) = match transform_q.get_mut(neutral_entity)
+        {
+            Ok(query) => query,
+            Err(e) => {
+                log::warn!("Error raised when swaping cards: {:?}", e);
+                return;
+            }
+        };
 
-        *cen_transform = temp_transform;
         *cen_card_index = temp_card_index;
         *cen_card_owner = temp_cardowner;
-        if is_mirrored {
-            mirror_card_ewr.send(MirrorCardEvent(neutral_entity));
-            mirror_card_ewr.send(MirrorCardEvent(swapping_entity));
+
+        // the cards slide to their new slots, the ones which change their orientation
+        // are turned over on the way, so the grid always matches `Card::is_mirrored`
+        let timer = Timer::new(board.move_duration, false);
+        commands.entity(swapping_entity).insert(CardTween {
+            start: temp_transform.translation,
+            end: cen_transform.translation,
+            is_turned: is_mirrored,
+            timer: timer.clone(),
+        });
+        commands.entity(neutral_entity).insert(CardTween {
+            start: cen_transform.translation,
+            end: temp_transform.translation,
+            is_turned: is_mirrored,
+            timer,
+        });
+    }
+}
+
+/// Slides the swapped cards to their new slots. A turned card is squeezed vertically until
+/// it is seen edge-on, its grid is redrawn mirrored then and the card is stretched back
+pub fn animate_cards(
+    mut commands: Commands,
+    time: Res<Time>,
+    layout: Res<Layout>,
+    mut tweens_q: Query<(Entity, &mut CardTween, &mut Transform)>,
+    mut mirror_card_ewr: EventWriter<MirrorCardEvent>,
+) {
+    // the card slows down when it arrives
+    let ease = |percent: f32| 1. - (1. - percent).powi(2);
+
+    for (entity, mut tween, mut transform) in tweens_q.iter_mut() {
+        let before = ease(tween.timer.percent());
+        tween.timer.tick(time.delta());
+        let t = ease(tween.timer.percent());
+        transform.translation = tween.start.lerp(tween.end, t);
+
+        if tween.is_turned {
+            transform.scale.y = layout.scale * (PI * t).cos().abs();
+            // redrawing the same grid again when the card arrives changes nothing
+            if (before < 0.5 && t >= 0.5) || tween.timer.finished() {
+                mirror_card_ewr.send(MirrorCardEvent(entity));
+            }
+        }
+
+        if tween.timer.finished() {
+            transform.translation = tween.end;
+            transform.scale.y = layout.scale;
+            commands.entity(entity).remove::<CardTween>();
         }
     }
 }
 
 pub fn mirror_card(
+    board: Res<Board>,
     deck: Res<Deck>,
     board_assets: Res<BoardAssets>,
     tiles_q: Query<&Children, With<CardIndex>>,
@@ -271,7 +328,7 @@ pub fn mirror_card(
             .directions
             .iter()
             .map(|tuple| {
-                if card.is_mirrored {
+                if card.is_mirrored != board.is_flipped {
                     center + (tuple.0, -tuple.1)
                 } else {
                     center + *tuple
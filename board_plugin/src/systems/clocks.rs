@@ -0,0 +1,32 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    events::{MoveMadeEvent, ProcessWinConditionEvent},
+    resources::{clocks::Clocks, game_state::GameState, pending_turn::PendingTurn},
+};
+
+/// Runs the clock of the side to move. The increment is added after the move is made.
+/// The clocks stand still while the moved piece is sliding. The bot search blocks the frame in
+/// which the piece starts to slide, `generate_bot_move` measures it and charges the bot itself
+pub fn update_clocks(
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    pending_turn: Option<Res<PendingTurn>>,
+    mut clocks: ResMut<Clocks>,
+    mut move_made_rdr: EventReader<MoveMadeEvent>,
+    mut process_win_condition_ewr: EventWriter<ProcessWinConditionEvent>,
+) {
+    if !clocks.is_timed() {
+        return;
+    }
+
+    let color = game_state.curr_color;
+    if pending_turn.is_none() && clocks.tick(&color, time.delta()) {
+        log::info!("{:?} has run out of time", color);
+        process_win_condition_ewr.send(ProcessWinConditionEvent::FlagFall(color));
+    }
+
+    for event in move_made_rdr.iter() {
+        clocks.on_move(&event.color);
+    }
+}
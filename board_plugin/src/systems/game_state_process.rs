@@ -1,17 +1,28 @@
 use bevy::{log, prelude::*};
 
 use crate::{
+    ai::alpha_beta::AlphaBetaAgent,
     events::{
-        ChangeGuideTextEvent, GenerateBotMoveEvent, NextTurnEvent, ProcessWinConditionEvent,
-        TurnProcessEvent,
+        ChangeGuideTextEvent, DrawOfferEvent, GenerateBotMoveEvent, MoveMadeEvent, NextTurnEvent,
+        ProcessWinConditionEvent, TurnProcessEvent,
     },
     resources::{
         app_state::AppState,
+        board::Board,
+        deck::Deck,
+        draw_offer::DrawOffer,
+        game_result::{GameEndReason, GameResult},
         game_state::{GameState, PlayerColor, PlayerType},
+        position_history::Position,
         tile_map::MoveResult,
     },
 };
 
+// Depth of the search with which the bots judge the draw offers
+const DRAW_OFFER_DEPTH: u8 = 3;
+// Bots accept a draw unless they evaluate their position higher than this
+const DRAW_ACCEPT_SCORE: i32 = 0;
+
 pub fn turn_process(
     game_state: Res<GameState>,
     mut turn_process_rdr: EventReader<TurnProcessEvent>,
@@ -24,6 +35,9 @@ pub fn turn_process(
             PlayerType::Human => break,
             PlayerType::Random => bot_move_ewr.send(GenerateBotMoveEvent),
             PlayerType::AlphaBeta => bot_move_ewr.send(GenerateBotMoveEvent),
+            PlayerType::External => bot_move_ewr.send(GenerateBotMoveEvent),
+            // remote moves are received by the network plugin
+            PlayerType::Remote => break,
         }
     }
 }
@@ -37,12 +51,8 @@ pub fn next_turn_event(
     for _ in next_turn_rdr.iter() {
         game_state.next_turn();
 
-        let color = match game_state.curr_color {
-            PlayerColor::Red => "Red".to_owned(),
-            PlayerColor::Blue => "Blue".to_owned(),
-        };
         change_guide_text_ewr.send(ChangeGuideTextEvent {
-            text: format!("{} to move. Select a card.", color),
+            text: format!("{} to move. Select a card.", game_state.curr_color.name()),
         });
 
         log::info!("GameState updated! {:?}", game_state);
@@ -52,34 +62,134 @@ pub fn next_turn_event(
 }
 
 pub fn process_win_condition(
+    mut commands: Commands,
     mut app_state: ResMut<State<AppState>>,
-    game_state: Res<GameState>,
+    mut game_state: ResMut<GameState>,
+    board: Res<Board>,
+    deck: Res<Deck>,
     mut check_win_condition_rdr: EventReader<ProcessWinConditionEvent>,
     mut change_guide_text_ewr: EventWriter<ChangeGuideTextEvent>,
 ) {
     for event in check_win_condition_rdr.iter() {
-        let mut is_end = false;
+        let game_result = match event {
+            ProcessWinConditionEvent::Move(MoveResult::Win) => {
+                Some(GameResult::win(game_state.curr_color, GameEndReason::Move))
+            }
+            ProcessWinConditionEvent::Move(move_result) => {
+                // the turn is not switched yet, so the enemy is the side to move in the new position
+                let position = Position::new(&board.tile_map, &deck, game_state.curr_color.enemy());
+                game_state.history.push(position);
 
-        if event.0 == MoveResult::Win {
-            let color = match game_state.curr_color {
-                PlayerColor::Red => "Red".to_owned(),
-                PlayerColor::Blue => "Blue".to_owned(),
-            };
+                // the move is counted before the turn is switched, as in the search
+                if *move_result == MoveResult::Tie || game_state.is_draw(game_state.turn + 1) {
+                    Some(GameResult::draw(GameEndReason::Tie))
+                } else {
+                    None
+                }
+            }
+            ProcessWinConditionEvent::FlagFall(color) => {
+                Some(GameResult::win(color.enemy(), GameEndReason::Timeout))
+            }
+            ProcessWinConditionEvent::Resign(color) => {
+                Some(GameResult::win(color.enemy(), GameEndReason::Resignation))
+            }
+            ProcessWinConditionEvent::Forfeit { color, reason } => Some(GameResult::win(
+                color.enemy(),
+                GameEndReason::Forfeit(reason.clone()),
+            )),
+            ProcessWinConditionEvent::DrawAgreed => {
+                Some(GameResult::draw(GameEndReason::Agreement))
+            }
+        };
+
+        // the rest of the events came after the end of the game
+        if let Some(game_result) = game_result {
+            log::info!("Game has ended: {:?}", game_result);
             change_guide_text_ewr.send(ChangeGuideTextEvent {
-                text: format!("{} has won!", color),
+                text: game_result.to_string(),
             });
-            is_end = true;
+            commands.insert_resource(game_result);
+            // the game may be ended by another system in the same frame
+            if let Err(e) = app_state.set(AppState::GameEnd) {
+                log::info!("Game is already ending: {:?}", e);
+            }
+            return;
         }
+    }
+}
 
-        if game_state.turn > 200 {
-            change_guide_text_ewr.send(ChangeGuideTextEvent {
-                text: format!("It is a tie!"),
-            });
-            is_end = true;
+/// Handles the draw offers. Bots accept the offer when their position is not better.
+/// An offer which is not answered is declined by the next move of the opponent
+pub fn process_draw_offer(
+    board: Res<Board>,
+    game_state: Res<GameState>,
+    deck: Res<Deck>,
+    mut draw_offer: ResMut<DrawOffer>,
+    mut draw_offer_rdr: EventReader<DrawOfferEvent>,
+    mut move_made_rdr: EventReader<MoveMadeEvent>,
+    mut process_win_condition_ewr: EventWriter<ProcessWinConditionEvent>,
+    mut change_guide_text_ewr: EventWriter<ChangeGuideTextEvent>,
+) {
+    for event in move_made_rdr.iter() {
+        if draw_offer.is_offered_to(&event.color) {
+            log::info!("{:?} has moved instead of accepting the draw", event.color);
+            draw_offer.offered_by = None;
         }
+    }
+
+    for event in draw_offer_rdr.iter() {
+        match *event {
+            DrawOfferEvent::Offer(color) => {
+                if draw_offer.offered_by.is_some() {
+                    log::info!("A draw is already offered");
+                    continue;
+                }
+
+                let opponent = color.enemy();
+                match game_state.get_player(&opponent).player_type {
+                    PlayerType::Human | PlayerType::Remote => {
+                        draw_offer.offered_by = Some(color);
+                        change_guide_text_ewr.send(ChangeGuideTextEvent {
+                            text: format!("{} offers a draw.", color.name()),
+                        });
+                    }
+                    _ => {
+                        // the bot looks at the position from its own side
+                        let score = AlphaBetaAgent::new(DRAW_OFFER_DEPTH)
+                            .search(&board, &game_state, &deck)
+                            .score;
+                        let score = match opponent {
+                            PlayerColor::Red => score,
+                            PlayerColor::Blue => -score,
+                        };
+                        log::info!("{:?} bot evaluates the draw offer with {}", opponent, score);
 
-        if is_end {
-            app_state.set(AppState::GameEnd).unwrap();
+                        if score <= DRAW_ACCEPT_SCORE {
+                            process_win_condition_ewr.send(ProcessWinConditionEvent::DrawAgreed);
+                        } else {
+                            change_guide_text_ewr.send(ChangeGuideTextEvent {
+                                text: format!("{} declines the draw.", opponent.name()),
+                            });
+                        }
+                    }
+                }
+            }
+            DrawOfferEvent::Accept(color) => {
+                if draw_offer.is_offered_to(&color) {
+                    draw_offer.offered_by = None;
+                    process_win_condition_ewr.send(ProcessWinConditionEvent::DrawAgreed);
+                } else {
+                    log::warn!("{:?} accepts a draw which is not offered", color);
+                }
+            }
+            DrawOfferEvent::Decline(color) => {
+                if draw_offer.is_offered_to(&color) {
+                    draw_offer.offered_by = None;
+                    change_guide_text_ewr.send(ChangeGuideTextEvent {
+                        text: format!("{} declines the draw.", color.name()),
+                    });
+                }
+            }
         }
     }
 }
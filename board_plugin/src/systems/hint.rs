@@ -0,0 +1,87 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    ai::alpha_beta::AlphaBetaAgent,
+    components::keyboard_cursor::KeyboardCursor,
+    events::{
+        ChangeGuideTextEvent, ColorSelectedCardEvent, HintEvent, PieceSelectEvent,
+        ResetAllowedMovesEvent, ResetSelectedCardColorEvent, ResetSelectedPieceColorEvent,
+    },
+    resources::{
+        board::Board,
+        deck::Deck,
+        depth::Depth,
+        game_state::{GameState, PlayerType},
+        pending_turn::PendingTurn,
+        selected::{SelectedCard, SelectedPiece},
+    },
+    systems::keyboard_input::cursor_translation,
+};
+
+/// Searches the move for the human with the depth of the bots. The card and the piece
+/// of the move are selected and the keyboard cursor is put on the destination
+pub fn show_hint(
+    board: Res<Board>,
+    deck: Res<Deck>,
+    game_state: Res<GameState>,
+    depth: Res<Depth>,
+    pending_turn: Option<Res<PendingTurn>>,
+    mut selected_card: ResMut<SelectedCard>,
+    mut selected_piece: ResMut<SelectedPiece>,
+    mut cursor_q: Query<(&mut KeyboardCursor, &mut Transform)>,
+    mut hint_rdr: EventReader<HintEvent>,
+    mut piece_select_ewr: EventWriter<PieceSelectEvent>,
+    mut color_selected_card_ewr: EventWriter<ColorSelectedCardEvent>,
+    mut reset_selected_card_color_ewr: EventWriter<ResetSelectedCardColorEvent>,
+    mut reset_selected_piece_color_ewr: EventWriter<ResetSelectedPieceColorEvent>,
+    mut reset_allowed_moves_ewr: EventWriter<ResetAllowedMovesEvent>,
+    mut change_guide_text_ewr: EventWriter<ChangeGuideTextEvent>,
+) {
+    if hint_rdr.iter().last().is_none() {
+        return;
+    }
+    if game_state.get_current_player().player_type != PlayerType::Human || pending_turn.is_some() {
+        log::info!("Hints are given only on the turn of a human");
+        return;
+    }
+
+    let result = AlphaBetaAgent::new(depth.0).search(&board, &game_state, &deck);
+    let (card, mov) = match (result.card, result.mov) {
+        (Some(card), Some(mov)) => (card, mov),
+        _ => {
+            log::warn!("No move is found for the hint");
+            return;
+        }
+    };
+    let card_name = deck.cardboards.get(&card).unwrap().card.name;
+    log::info!(
+        "Hint is {:?} with {} and score {}",
+        mov,
+        card_name,
+        result.score
+    );
+
+    // the hinted move is selected from scratch, the hinted card and piece keep their color
+    if let Some(entity) = selected_card.entity.filter(|entity| *entity != card) {
+        reset_selected_card_color_ewr.send(ResetSelectedCardColorEvent(entity));
+    }
+    if let Some(entity) = selected_piece.entity {
+        if selected_piece.coordinates != Some(mov.from) {
+            reset_selected_piece_color_ewr.send(ResetSelectedPieceColorEvent(entity));
+        }
+        reset_allowed_moves_ewr.send(ResetAllowedMovesEvent);
+        selected_piece.clear();
+    }
+    selected_card.entity = Some(card);
+    color_selected_card_ewr.send(ColorSelectedCardEvent(card));
+    piece_select_ewr.send(PieceSelectEvent(mov.from));
+
+    if let Ok((mut cursor, mut transform)) = cursor_q.get_single_mut() {
+        cursor.coordinates = mov.to;
+        transform.translation = cursor_translation(&board, &mov.to);
+    }
+
+    change_guide_text_ewr.send(ChangeGuideTextEvent {
+        text: format!("Hint: {} from {} to {}", card_name, mov.from, mov.to),
+    });
+}
@@ -0,0 +1,149 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    components::{coordinates::Coordinates, keyboard_cursor::KeyboardCursor},
+    events::{
+        ColorSelectedCardEvent, PieceSelectEvent, ResetAllowedMovesEvent,
+        ResetSelectedCardColorEvent, ResetSelectedPieceColorEvent,
+    },
+    resources::{
+        board::Board,
+        board_assets::BoardAssets,
+        deck::Deck,
+        game_state::{GameState, PlayerType},
+        pending_turn::PendingTurn,
+        selected::{SelectedCard, SelectedPiece},
+        tile_map::BOARD_SIZE,
+    },
+};
+
+/// Position of the keyboard cursor relative to the board, above the pieces
+pub fn cursor_translation(board: &Board, coordinates: &Coordinates) -> Vec3 {
+    board.tile_position(coordinates).extend(3.)
+}
+
+/// Plays the game from the keyboard: 1, 2 or Tab select a card, the arrows move the cursor
+/// over the board, Enter or Space press on the tile under it and Escape resets the selection
+pub fn keyboard_input(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    board: Res<Board>,
+    deck: Res<Deck>,
+    board_assets: Res<BoardAssets>,
+    keys: Res<Input<KeyCode>>,
+    pending_turn: Option<Res<PendingTurn>>,
+    mut selected_card: ResMut<SelectedCard>,
+    mut selected_piece: ResMut<SelectedPiece>,
+    mut cursor_q: Query<(&mut KeyboardCursor, &mut Transform)>,
+    mut piece_select_ewr: EventWriter<PieceSelectEvent>,
+    mut color_selected_card_ewr: EventWriter<ColorSelectedCardEvent>,
+    mut reset_selected_card_color_ewr: EventWriter<ResetSelectedCardColorEvent>,
+    mut reset_selected_piece_color_ewr: EventWriter<ResetSelectedPieceColorEvent>,
+    mut reset_allowed_moves_ewr: EventWriter<ResetAllowedMovesEvent>,
+) {
+    // the same players as for the mouse
+    if game_state.is_spectating()
+        || game_state.get_current_player().player_type != PlayerType::Human
+        || pending_turn.is_some()
+    {
+        return;
+    }
+
+    let [(first_card, _), (second_card, _)] = deck.get_player_cards(&game_state);
+    let card = if keys.just_pressed(KeyCode::Key1) || keys.just_pressed(KeyCode::Numpad1) {
+        Some(first_card)
+    } else if keys.just_pressed(KeyCode::Key2) || keys.just_pressed(KeyCode::Numpad2) {
+        Some(second_card)
+    } else if keys.just_pressed(KeyCode::Tab) {
+        match selected_card.entity {
+            Some(entity) if entity == first_card => Some(second_card),
+            _ => Some(first_card),
+        }
+    } else {
+        None
+    };
+    let is_cancelled = keys.just_pressed(KeyCode::Escape);
+
+    // changing the card resets the selected piece like a click does
+    if is_cancelled || (card.is_some() && card != selected_card.entity) {
+        if let Some(entity) = selected_card.entity {
+            reset_selected_card_color_ewr.send(ResetSelectedCardColorEvent(entity));
+            selected_card.entity = None;
+        }
+        if let Some(entity) = selected_piece.entity {
+            reset_selected_piece_color_ewr.send(ResetSelectedPieceColorEvent(entity));
+            reset_allowed_moves_ewr.send(ResetAllowedMovesEvent);
+            selected_piece.clear();
+        }
+        if let Some(card) = card {
+            log::info!("Selected the card {:?} from the keyboard", card);
+            selected_card.entity = Some(card);
+            color_selected_card_ewr.send(ColorSelectedCardEvent(card));
+        }
+    }
+
+    // the arrows follow the rows on the screen
+    let up = match board.is_flipped {
+        true => -1,
+        false => 1,
+    };
+    let direction = if keys.just_pressed(KeyCode::Up) {
+        Some((0, up))
+    } else if keys.just_pressed(KeyCode::Down) {
+        Some((0, -up))
+    } else if keys.just_pressed(KeyCode::Left) {
+        Some((-1, 0))
+    } else if keys.just_pressed(KeyCode::Right) {
+        Some((1, 0))
+    } else {
+        None
+    };
+
+    if let Some((dx, dy)) = direction {
+        match cursor_q.get_single_mut() {
+            Ok((mut cursor, mut transform)) => {
+                let max = BOARD_SIZE as i8 - 1;
+                cursor.coordinates = Coordinates {
+                    x: (cursor.coordinates.x as i8 + dx).clamp(0, max) as u8,
+                    y: (cursor.coordinates.y as i8 + dy).clamp(0, max) as u8,
+                };
+                transform.translation = cursor_translation(&board, &cursor.coordinates);
+            }
+            // the cursor appears in the middle of the board on the first press
+            Err(_) => {
+                let coordinates = Coordinates { x: 2, y: 2 };
+                let cursor = commands
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: board_assets.keyboard_cursor_material.color,
+                            custom_size: Some(Vec2::splat(board.tile_size)),
+                            ..Default::default()
+                        },
+                        texture: board_assets.keyboard_cursor_material.texture.clone(),
+                        transform: Transform::from_translation(cursor_translation(
+                            &board,
+                            &coordinates,
+                        )),
+                        ..Default::default()
+                    })
+                    .insert(Name::new("Keyboard cursor"))
+                    .insert(KeyboardCursor { coordinates })
+                    .id();
+                commands.entity(board.entity).push_children(&[cursor]);
+            }
+        }
+    }
+
+    let is_pressed = keys.just_pressed(KeyCode::Return)
+        || keys.just_pressed(KeyCode::NumpadEnter)
+        || keys.just_pressed(KeyCode::Space);
+    if is_pressed {
+        if let Ok((cursor, _)) = cursor_q.get_single() {
+            log::info!(
+                "Pressed to tile on {} from the keyboard",
+                cursor.coordinates
+            );
+            piece_select_ewr.send(PieceSelectEvent(cursor.coordinates));
+        }
+    }
+}
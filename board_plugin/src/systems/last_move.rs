@@ -0,0 +1,87 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    components::last_move::{LastMoveCard, LastMoveTile},
+    events::MoveMadeEvent,
+    resources::{board::Board, board_assets::BoardAssets, deck::Deck, last_move::LastMove},
+};
+
+/// Remembers the move of any player to highlight it
+pub fn record_last_move(mut commands: Commands, mut move_made_rdr: EventReader<MoveMadeEvent>) {
+    for event in move_made_rdr.iter() {
+        commands.insert_resource(LastMove {
+            mov: event.mov,
+            card: event.card_used,
+        });
+    }
+}
+
+/// Highlights the origin and the destination of the last move and frames the card played for it.
+/// The highlight is replaced when another move is made or a move is taken back
+pub fn show_last_move(
+    mut commands: Commands,
+    board: Res<Board>,
+    deck: Res<Deck>,
+    board_assets: Res<BoardAssets>,
+    last_move: Option<Res<LastMove>>,
+    markers_q: Query<Entity, Or<(With<LastMoveTile>, With<LastMoveCard>)>>,
+) {
+    let is_changed = last_move
+        .as_ref()
+        .map_or(true, |last_move| last_move.is_changed());
+    if !is_changed {
+        return;
+    }
+    for entity in markers_q.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    // there is nothing to show before the first move
+    let last_move = match last_move {
+        Some(last_move) => last_move,
+        None => return,
+    };
+    log::info!("Highlighting the last move {:?}", last_move.mov);
+
+    // the tiles are colored above the tile sprites and below the pieces
+    for coordinates in [last_move.mov.from, last_move.mov.to] {
+        let marker = commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: board_assets.last_move_tile_material.color,
+                    custom_size: Some(Vec2::splat(board.tile_size - board.padding)),
+                    ..Default::default()
+                },
+                texture: board_assets.last_move_tile_material.texture.clone(),
+                transform: Transform::from_translation(
+                    board.tile_position(&coordinates).extend(1.5),
+                ),
+                ..Default::default()
+            })
+            .insert(Name::new(format!("Last move {}", coordinates)))
+            .insert(LastMoveTile { coordinates })
+            .id();
+        commands.entity(board.entity).push_children(&[marker]);
+    }
+
+    // the frame is a child of the card, so it follows the card to its new place
+    let cardboard = match deck.cardboards.get(&last_move.card) {
+        Some(cardboard) => cardboard,
+        None => return,
+    };
+    let frame = commands
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: board_assets.last_move_card_material.color,
+                // the bounds are scaled by the layout, the card scales the frame as well
+                custom_size: Some(cardboard.bounds.size / board.scale * 1.1),
+                ..Default::default()
+            },
+            texture: board_assets.last_move_card_material.texture.clone(),
+            transform: Transform::from_xyz(0., 0., -0.5),
+            ..Default::default()
+        })
+        .insert(Name::new(format!("Last move card {}", cardboard.card.name)))
+        .insert(LastMoveCard)
+        .id();
+    commands.entity(last_move.card).push_children(&[frame]);
+}
@@ -0,0 +1,128 @@
+use bevy::{
+    log,
+    prelude::*,
+    window::{WindowId, WindowResized},
+};
+
+use crate::{
+    bounds::Bounds2,
+    components::card_animation::CardTween,
+    resources::{
+        board::Board, board_assets::BoardAssets, deck::Deck, layout::Layout,
+        text_handler::TextHandler,
+    },
+};
+
+/// Places the board, the cards and the texts for the new size of the window. The tiles keep
+/// their sizes in the board space, the board and the cards are scaled instead
+pub fn update_layout(
+    mut layout: ResMut<Layout>,
+    mut board: ResMut<Board>,
+    mut deck: ResMut<Deck>,
+    text_handler: Res<TextHandler>,
+    board_assets: Res<BoardAssets>,
+    mut transforms_q: Query<&mut Transform>,
+    mut tweens_q: Query<&mut CardTween>,
+    mut window_resized_rdr: EventReader<WindowResized>,
+) {
+    let event = match window_resized_rdr
+        .iter()
+        .filter(|event| event.id == WindowId::primary())
+        .last()
+    {
+        Some(event) => event,
+        None => return,
+    };
+
+    let old = *layout;
+    *layout = old.resized(Vec2::new(event.width, event.height));
+    log::info!(
+        "Window resized to {}x{}, layout: {:?}",
+        event.width,
+        event.height,
+        *layout
+    );
+    let scale = Vec3::new(layout.scale, layout.scale, 1.);
+
+    board.scale = layout.scale;
+    board.bounds = Bounds2 {
+        position: layout.board_position,
+        size: layout.board_size * layout.scale,
+    };
+    if let Ok(mut transform) = transforms_q.get_mut(board.entity) {
+        transform.translation = layout.board_position.extend(transform.translation.z);
+        transform.scale = scale;
+    }
+
+    // the cards keep their places around the neutral card, so a flipped deck stays flipped
+    let move_point = |point: Vec3| layout.deck_point(&old, point.truncate()).extend(point.z);
+    for (entity, cardboard) in deck.cardboards.iter_mut() {
+        let center = cardboard.bounds.position + cardboard.bounds.size / 2.;
+        let size = cardboard.bounds.size * layout.scale / old.scale;
+        cardboard.bounds = Bounds2 {
+            position: layout.deck_point(&old, center) - size / 2.,
+            size,
+        };
+
+        if let Ok(mut transform) = transforms_q.get_mut(*entity) {
+            transform.translation = move_point(transform.translation);
+            transform.scale = scale;
+        }
+        // a sliding card arrives to its slot in the new layout
+        if let Ok(mut tween) = tweens_q.get_mut(*entity) {
+            tween.start = move_point(tween.start);
+            tween.end = move_point(tween.end);
+        }
+    }
+
+    let texts = [
+        (text_handler.guide_text, layout.guide_text_position),
+        (text_handler.turn_text, layout.turn_text_position),
+        (
+            text_handler.evaluation_text,
+            layout.evaluation_text_position(board_assets.turn_text_size),
+        ),
+        (
+            text_handler.clock_text,
+            layout.clock_text_position(board_assets.turn_text_size),
+        ),
+    ];
+    for (entity, position) in texts {
+        if let Ok(mut transform) = transforms_q.get_mut(entity) {
+            transform.translation = position.extend(transform.translation.z);
+            transform.scale = scale;
+        }
+    }
+}
+
+/// Resizes the canvas to the browser window, the browser does not resize it by itself
+#[cfg(target_arch = "wasm32")]
+pub fn fit_browser_window(mut windows: ResMut<Windows>) {
+    let browser = match web_sys::window() {
+        Some(browser) => browser,
+        None => return,
+    };
+    let size = browser
+        .inner_width()
+        .ok()
+        .and_then(|width| width.as_f64())
+        .zip(
+            browser
+                .inner_height()
+                .ok()
+                .and_then(|height| height.as_f64()),
+        );
+    let (width, height) = match size {
+        Some((width, height)) => (width as f32, height as f32),
+        None => return,
+    };
+
+    let window = match windows.get_primary_mut() {
+        Some(window) => window,
+        None => return,
+    };
+    if (window.width() - width).abs() >= 1. || (window.height() - height).abs() >= 1. {
+        log::info!("Fitting the window to the browser: {}x{}", width, height);
+        window.set_resolution(width, height);
+    }
+}
@@ -3,3 +3,15 @@ pub mod card_input;
 pub mod text_change;
 pub mod game_state_process;
 pub mod ai_input;
+pub mod clocks;
+pub mod saved_game;
+pub mod piece_animation;
+pub mod piece_drag;
+pub mod keyboard_input;
+pub mod hint;
+pub mod undo;
+pub mod board_flip;
+pub mod last_move;
+pub mod layout;
+pub mod pointer;
+pub mod move_preview;
@@ -0,0 +1,114 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    components::{card_board::CardOwner, coordinates::Coordinates, move_preview::MovePreviewTile},
+    resources::{
+        board::Board,
+        board_assets::BoardAssets,
+        deck::Deck,
+        game_state::{GameState, PlayerColor},
+        pointer::Pointer,
+        tile_map::BOARD_SIZE,
+    },
+};
+
+/// Highlights the tiles reached by the card the pointer is held on, from every piece of the
+/// player owning the card. The neutral card is shown for the current player.
+/// The highlight is removed when the pointer is released or leaves the card
+pub fn preview_card_moves(
+    mut commands: Commands,
+    board: Res<Board>,
+    deck: Res<Deck>,
+    game_state: Res<GameState>,
+    board_assets: Res<BoardAssets>,
+    windows: Res<Windows>,
+    pointer: Res<Pointer>,
+    mut previewed_card: Local<Option<Entity>>,
+    owners_q: Query<&CardOwner>,
+    markers_q: Query<Entity, With<MovePreviewTile>>,
+) {
+    let window = windows.get_primary().unwrap();
+    let held_card = match pointer.is_held() {
+        true => deck
+            .cardboards
+            .iter()
+            .find(|(_, cardboard)| cardboard.in_pointer_bounds(window, &pointer))
+            .map(|(entity, _)| *entity),
+        false => None,
+    };
+    if held_card == *previewed_card {
+        return;
+    }
+    *previewed_card = held_card;
+
+    for entity in markers_q.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let (entity, cardboard) =
+        match held_card.and_then(|entity| deck.cardboards.get_key_value(&entity)) {
+            Some(card) => card,
+            None => return,
+        };
+
+    // the cards of the blue player are mirrored, the neutral card is turned to the current player
+    let color = match owners_q.get(*entity) {
+        Ok(CardOwner::Blue) => PlayerColor::Blue,
+        Ok(CardOwner::Red) => PlayerColor::Red,
+        _ => game_state.curr_color,
+    };
+    let mut card = cardboard.card.clone();
+    card.is_mirrored = color == PlayerColor::Blue;
+
+    let mut moves = vec![];
+    for y in 0..BOARD_SIZE {
+        for x in 0..BOARD_SIZE {
+            let is_own_piece = board.tile_map.map[y][x]
+                .piece
+                .map_or(false, |piece| piece.color == color);
+            if is_own_piece {
+                let from = Coordinates {
+                    x: x as u8,
+                    y: y as u8,
+                };
+                moves.extend(board.tile_map.generate_moves_of(
+                    &color,
+                    &from,
+                    &card,
+                    game_state.rules.as_ref(),
+                ));
+            }
+        }
+    }
+    moves.sort();
+    moves.dedup();
+    log::info!(
+        "Previewing the moves of {} for {}: {:?}",
+        card.name,
+        color.name(),
+        moves
+    );
+
+    // the tiles are colored above the last move and below the pieces
+    let material = &board_assets.allowed_move_tile_material;
+    let mut marker_color = material.color;
+    marker_color.set_a(0.7);
+    for coordinates in moves {
+        let marker = commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: marker_color,
+                    custom_size: Some(Vec2::splat(board.tile_size - board.padding)),
+                    ..Default::default()
+                },
+                texture: material.texture.clone(),
+                transform: Transform::from_translation(
+                    board.tile_position(&coordinates).extend(1.6),
+                ),
+                ..Default::default()
+            })
+            .insert(Name::new(format!("Move preview {}", coordinates)))
+            .insert(MovePreviewTile)
+            .id();
+        commands.entity(board.entity).push_children(&[marker]);
+    }
+}
@@ -0,0 +1,138 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    components::{
+        board_tile::BoardTile,
+        card_animation::CardTween,
+        coordinates::Coordinates,
+        piece_animation::{FadingPiece, PieceTween},
+        pieces::Piece,
+    },
+    events::NextTurnEvent,
+    resources::{board::Board, pending_turn::PendingTurn, tile_map::Move},
+};
+
+/// Moves the piece of the already made move on the board. The piece sprite is moved to the
+/// new tile and slides there from the old one, the sprite of a captured piece fades out.
+/// The turn is switched once the piece has arrived
+pub fn start_move_animation(
+    commands: &mut Commands,
+    board: &Board,
+    mov: &Move,
+    tiles_q: &Query<(Entity, &Coordinates), With<BoardTile>>,
+    children_q: &Query<&Children, With<BoardTile>>,
+    pieces_q: &Query<&Piece>,
+    transforms_q: &Query<&Transform, Without<BoardTile>>,
+) {
+    let tile = |coordinates: Coordinates| {
+        tiles_q
+            .iter()
+            .find(|(_, coords)| **coords == coordinates)
+            .map(|(entity, _)| entity)
+    };
+    let (from_tile, to_tile) = match (tile(mov.from), tile(mov.to)) {
+        (Some(from_tile), Some(to_tile)) => (from_tile, to_tile),
+        _ => {
+            log::warn!("Could not find the tiles of the move {:?}", mov);
+            return;
+        }
+    };
+    let piece = match pieces_q.get(from_tile) {
+        Ok(piece) => *piece,
+        Err(_) => {
+            log::warn!("There is no piece to move on {:?}", mov.from);
+            return;
+        }
+    };
+
+    let timer = Timer::new(board.move_duration, false);
+
+    // the captured piece is left on the board above its tile
+    if let Ok(children) = children_q.get(to_tile) {
+        let position = board.tile_position(&mov.to).extend(2.);
+        for child in children.iter() {
+            commands.entity(to_tile).remove_children(&[*child]);
+            commands.entity(board.entity).push_children(&[*child]);
+            commands
+                .entity(*child)
+                .insert(Transform::from_translation(position))
+                .insert(FadingPiece {
+                    timer: timer.clone(),
+                });
+        }
+    }
+
+    commands.entity(from_tile).remove::<Piece>();
+    commands.entity(to_tile).insert(piece);
+
+    // the moving piece is drawn above the rest of the pieces. It starts from where it is shown,
+    // a dragged piece is not on its tile
+    if let Ok(children) = children_q.get(from_tile) {
+        let offset = (board.tile_position(&mov.from) - board.tile_position(&mov.to)).extend(0.);
+        for child in children.iter() {
+            let mut start = offset
+                + transforms_q
+                    .get(*child)
+                    .map_or(Vec3::ZERO, |transform| transform.translation);
+            start.z = 2.;
+            commands.entity(from_tile).remove_children(&[*child]);
+            commands.entity(to_tile).push_children(&[*child]);
+            commands
+                .entity(*child)
+                .insert(Transform::from_translation(start))
+                .insert(PieceTween {
+                    start,
+                    end: Vec3::new(0., 0., 1.),
+                    timer: timer.clone(),
+                });
+        }
+    }
+
+    commands.insert_resource(PendingTurn);
+}
+
+pub fn animate_pieces(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tweens_q: Query<(Entity, &mut PieceTween, &mut Transform)>,
+    mut fading_q: Query<(Entity, &mut FadingPiece, &mut Sprite)>,
+) {
+    for (entity, mut tween, mut transform) in tweens_q.iter_mut() {
+        tween.timer.tick(time.delta());
+        // the piece slows down when it arrives
+        let t = 1. - (1. - tween.timer.percent()).powi(2);
+        transform.translation = tween.start.lerp(tween.end, t);
+
+        if tween.timer.finished() {
+            transform.translation = tween.end;
+            commands.entity(entity).remove::<PieceTween>();
+        }
+    }
+
+    for (entity, mut fading, mut sprite) in fading_q.iter_mut() {
+        fading.timer.tick(time.delta());
+        sprite.color.set_a(1. - fading.timer.percent());
+
+        if fading.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Switches the turn once the moved piece and the swapped cards have arrived
+pub fn finish_pending_turn(
+    mut commands: Commands,
+    pending_turn: Option<Res<PendingTurn>>,
+    tweens_q: Query<&PieceTween>,
+    card_tweens_q: Query<&CardTween>,
+    mut next_turn_ewr: EventWriter<NextTurnEvent>,
+) {
+    let is_animated = tweens_q.iter().any(|tween| !tween.timer.finished())
+        || card_tweens_q.iter().any(|tween| !tween.timer.finished());
+    if pending_turn.is_none() || is_animated {
+        return;
+    }
+
+    commands.remove_resource::<PendingTurn>();
+    next_turn_ewr.send(NextTurnEvent);
+}
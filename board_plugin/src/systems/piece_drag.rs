@@ -0,0 +1,321 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    components::{
+        allowed_move::AllowedMove, background::Background, board_tile::BoardTile,
+        card_index::CardIndex, coordinates::Coordinates, pieces::Piece,
+    },
+    events::{
+        ChangeGuideTextEvent, MovePieceEvent, ResetAllowedMovesEvent, ResetSelectedCardColorEvent,
+        ResetSelectedPieceColorEvent,
+    },
+    resources::{
+        board::Board,
+        board_assets::BoardAssets,
+        deck::Deck,
+        dragged_piece::{DraggedPiece, DropChoice},
+        game_state::{GameState, PlayerType},
+        pending_turn::PendingTurn,
+        pointer::Pointer,
+        selected::{SelectedCard, SelectedPiece},
+    },
+};
+
+/// Picks up a piece of the current player when the left button is pressed or a finger touches it.
+/// The destinations are computed for both cards of the player
+pub fn start_piece_drag(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    board: Res<Board>,
+    deck: Res<Deck>,
+    windows: Res<Windows>,
+    pointer: Res<Pointer>,
+    pending_turn: Option<Res<PendingTurn>>,
+    pieces_q: Query<(Entity, &Coordinates, &Piece), With<BoardTile>>,
+) {
+    // the same players as for the clicks
+    if game_state.is_spectating()
+        || game_state.get_current_player().player_type != PlayerType::Human
+        || pending_turn.is_some()
+        || !pointer.just_pressed
+    {
+        return;
+    }
+
+    let window = windows.get_primary().unwrap();
+    let coordinates = match board.pointer_position(window, &pointer) {
+        Some(coordinates) => coordinates,
+        None => return,
+    };
+
+    let tile = pieces_q
+        .iter()
+        .find(|(_, coords, piece)| **coords == coordinates && piece.color == game_state.curr_color)
+        .map(|(entity, _, _)| entity);
+    if let Some(tile) = tile {
+        let [(first_card_entity, first_card), (second_card_entity, second_card)] =
+            deck.get_player_cards(&game_state);
+        commands.insert_resource(DraggedPiece {
+            tile,
+            coordinates,
+            is_dragging: false,
+            is_highlighted: false,
+            cards: [first_card_entity, second_card_entity],
+            moves: [
+                board
+                    .tile_map
+                    .generate_allowed_moves(&coordinates, &first_card, &game_state),
+                board
+                    .tile_map
+                    .generate_allowed_moves(&coordinates, &second_card, &game_state),
+            ],
+        });
+    }
+}
+
+/// Moves the picked up piece with the pointer. The drag begins when the pointer leaves the tile,
+/// the selection made by clicks is reset then
+pub fn drag_piece(
+    board: Res<Board>,
+    windows: Res<Windows>,
+    pointer: Res<Pointer>,
+    dragged_piece: Option<ResMut<DraggedPiece>>,
+    mut selected_card: ResMut<SelectedCard>,
+    mut selected_piece: ResMut<SelectedPiece>,
+    children_q: Query<&Children, With<BoardTile>>,
+    mut transforms_q: Query<&mut Transform, Without<BoardTile>>,
+    mut reset_selected_card_color_ewr: EventWriter<ResetSelectedCardColorEvent>,
+    mut reset_selected_piece_color_ewr: EventWriter<ResetSelectedPieceColorEvent>,
+    mut reset_allowed_moves_ewr: EventWriter<ResetAllowedMovesEvent>,
+) {
+    let mut dragged_piece = match dragged_piece {
+        Some(dragged_piece) => dragged_piece,
+        None => return,
+    };
+
+    let window = windows.get_primary().unwrap();
+    let position = match pointer.position {
+        Some(position) => position,
+        None => return,
+    };
+
+    if !dragged_piece.is_dragging {
+        if board.pointer_position(window, &pointer) == Some(dragged_piece.coordinates) {
+            return;
+        }
+        log::info!("Dragging the piece from {}", dragged_piece.coordinates);
+        dragged_piece.is_dragging = true;
+
+        if let Some(entity) = selected_card.entity {
+            reset_selected_card_color_ewr.send(ResetSelectedCardColorEvent(entity));
+            selected_card.entity = None;
+        }
+        if let Some(entity) = selected_piece.entity {
+            reset_selected_piece_color_ewr.send(ResetSelectedPieceColorEvent(entity));
+            selected_piece.clear();
+        }
+        reset_allowed_moves_ewr.send(ResetAllowedMovesEvent);
+    }
+
+    // the piece is drawn under the pointer above the rest of the pieces
+    let offset = board.tile_offset(window, position, &dragged_piece.coordinates);
+    if let Ok(children) = children_q.get(dragged_piece.tile) {
+        for child in children.iter() {
+            if let Ok(mut transform) = transforms_q.get_mut(*child) {
+                transform.translation = offset.extend(2.);
+            }
+        }
+    }
+}
+
+/// Colors the destinations of the dragged piece and the cards reaching them. A dropped piece
+/// waiting for the card to be chosen keeps only its destination colored
+pub fn color_dragged_piece_moves(
+    mut commands: Commands,
+    board_assets: Res<BoardAssets>,
+    dragged_piece: Option<ResMut<DraggedPiece>>,
+    drop_choice: Option<Res<DropChoice>>,
+    mut tiles_q: Query<(Entity, &Coordinates, &mut Sprite, &mut Handle<Image>), With<BoardTile>>,
+    cards_q: Query<&Children, With<CardIndex>>,
+    mut backgrounds_q: Query<&mut Sprite, (With<Background>, Without<BoardTile>)>,
+) {
+    let mut highlight = None;
+    if let Some(mut dragged_piece) = dragged_piece {
+        if dragged_piece.is_dragging && !dragged_piece.is_highlighted {
+            dragged_piece.is_highlighted = true;
+            highlight = Some((dragged_piece.cards, dragged_piece.moves.clone()));
+        }
+    }
+    if let Some(drop_choice) = drop_choice {
+        if drop_choice.is_added() {
+            highlight = Some((
+                drop_choice.cards,
+                [vec![drop_choice.to], vec![drop_choice.to]],
+            ));
+        }
+    }
+    let (cards, moves) = match highlight {
+        Some(highlight) => highlight,
+        None => return,
+    };
+
+    for (entity, coordinates, mut sprite, mut texture) in tiles_q.iter_mut() {
+        let material = match (
+            moves[0].contains(coordinates),
+            moves[1].contains(coordinates),
+        ) {
+            (true, true) => &board_assets.both_cards_move_material,
+            (true, false) => &board_assets.first_card_move_material,
+            (false, true) => &board_assets.second_card_move_material,
+            (false, false) => continue,
+        };
+        sprite.color = material.color;
+        *texture = material.texture.clone();
+        commands.entity(entity).insert(AllowedMove);
+    }
+
+    let card_materials = [
+        &board_assets.first_card_move_material,
+        &board_assets.second_card_move_material,
+    ];
+    for (card, material) in cards.iter().zip(card_materials.iter()) {
+        if let Ok(children) = cards_q.get(*card) {
+            for child in children.iter() {
+                if let Ok(mut sprite) = backgrounds_q.get_mut(*child) {
+                    sprite.color = material.color;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Drops the dragged piece. The move is made when only one card reaches the tile,
+/// otherwise the piece returns to its tile
+pub fn drop_piece(
+    mut commands: Commands,
+    board: Res<Board>,
+    windows: Res<Windows>,
+    pointer: Res<Pointer>,
+    dragged_piece: Option<Res<DraggedPiece>>,
+    mut selected_card: ResMut<SelectedCard>,
+    mut selected_piece: ResMut<SelectedPiece>,
+    children_q: Query<&Children, With<BoardTile>>,
+    mut transforms_q: Query<&mut Transform, Without<BoardTile>>,
+    mut reset_allowed_moves_ewr: EventWriter<ResetAllowedMovesEvent>,
+    mut reset_selected_card_color_ewr: EventWriter<ResetSelectedCardColorEvent>,
+    mut change_guide_text_ewr: EventWriter<ChangeGuideTextEvent>,
+    mut move_piece_ewr: EventWriter<MovePieceEvent>,
+) {
+    let dragged_piece = match dragged_piece {
+        Some(dragged_piece) => dragged_piece,
+        None => return,
+    };
+    if !pointer.just_released {
+        return;
+    }
+    commands.remove_resource::<DraggedPiece>();
+
+    // the pointer is released on the same tile, the press is handled as a click
+    if !dragged_piece.is_dragging {
+        return;
+    }
+
+    reset_allowed_moves_ewr.send(ResetAllowedMovesEvent);
+    for card in dragged_piece.cards.iter() {
+        reset_selected_card_color_ewr.send(ResetSelectedCardColorEvent(*card));
+    }
+
+    let window = windows.get_primary().unwrap();
+    let to = board.pointer_position(window, &pointer);
+    let cards = to.map_or(vec![], |to| dragged_piece.cards_reaching(&to));
+
+    match (to, cards.as_slice()) {
+        (Some(to), [card]) => {
+            log::info!("Dropped the piece on {} with the card {:?}", to, card);
+            selected_card.entity = Some(*card);
+            selected_piece.entity = Some(dragged_piece.tile);
+            selected_piece.coordinates = Some(dragged_piece.coordinates);
+            // the piece slides to the tile from where it is dropped
+            move_piece_ewr.send(MovePieceEvent(to));
+            return;
+        }
+        (Some(to), [_, _]) => {
+            log::info!("Dropped the piece on {}, both cards reach it", to);
+            commands.insert_resource(DropChoice {
+                tile: dragged_piece.tile,
+                from: dragged_piece.coordinates,
+                to,
+                cards: dragged_piece.cards,
+            });
+            change_guide_text_ewr.send(ChangeGuideTextEvent {
+                text: "Both cards reach the tile. Select a card to play".to_owned(),
+            });
+        }
+        _ => log::info!(
+            "The piece from {} can not be dropped on {:?}",
+            dragged_piece.coordinates,
+            to
+        ),
+    }
+
+    // the piece returns to its tile
+    if let Ok(children) = children_q.get(dragged_piece.tile) {
+        for child in children.iter() {
+            if let Ok(mut transform) = transforms_q.get_mut(*child) {
+                transform.translation = Vec3::new(0., 0., 1.);
+            }
+        }
+    }
+}
+
+/// Makes the move of the dropped piece with the card the player has selected.
+/// Pressing anywhere else or any other key cancels the move
+pub fn choose_drop_card(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    pointer: Res<Pointer>,
+    keys: Res<Input<KeyCode>>,
+    drop_choice: Option<Res<DropChoice>>,
+    selected_card: Res<SelectedCard>,
+    mut selected_piece: ResMut<SelectedPiece>,
+    mut reset_allowed_moves_ewr: EventWriter<ResetAllowedMovesEvent>,
+    mut reset_selected_card_color_ewr: EventWriter<ResetSelectedCardColorEvent>,
+    mut change_guide_text_ewr: EventWriter<ChangeGuideTextEvent>,
+    mut move_piece_ewr: EventWriter<MovePieceEvent>,
+) {
+    let drop_choice = match drop_choice {
+        Some(drop_choice) => drop_choice,
+        None => return,
+    };
+    if !pointer.just_pressed && keys.get_just_pressed().next().is_none() {
+        return;
+    }
+    commands.remove_resource::<DropChoice>();
+    reset_allowed_moves_ewr.send(ResetAllowedMovesEvent);
+
+    match selected_card.entity {
+        Some(card) if drop_choice.cards.contains(&card) => {
+            log::info!(
+                "Moving the dropped piece to {} with the card {:?}",
+                drop_choice.to,
+                card
+            );
+            for other_card in drop_choice.cards.iter().filter(|other| **other != card) {
+                reset_selected_card_color_ewr.send(ResetSelectedCardColorEvent(*other_card));
+            }
+            selected_piece.entity = Some(drop_choice.tile);
+            selected_piece.coordinates = Some(drop_choice.from);
+            move_piece_ewr.send(MovePieceEvent(drop_choice.to));
+        }
+        _ => {
+            log::info!("The move of the dropped piece is cancelled");
+            for card in drop_choice.cards.iter() {
+                reset_selected_card_color_ewr.send(ResetSelectedCardColorEvent(*card));
+            }
+            change_guide_text_ewr.send(ChangeGuideTextEvent {
+                text: format!("{} to move. Select a card", game_state.curr_color.name()),
+            });
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use bevy::{log, prelude::*};
+
+use crate::resources::pointer::Pointer;
+
+/// Updates the pointer from the mouse and the first touch. Runs after the input of the frame
+/// is read, before the systems of any state
+pub fn update_pointer(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    mouse_button_inputs: Res<Input<MouseButton>>,
+    touches: Res<Touches>,
+    mut pointer: ResMut<Pointer>,
+) {
+    let window = match windows.get_primary() {
+        Some(window) => window,
+        None => return,
+    };
+
+    // a released touch is not pressed anymore, but the release still has a position
+    let touch = touches
+        .iter()
+        .next()
+        .or_else(|| touches.iter_just_released().next());
+    let touch_pressed = touches.iter_just_pressed().next().is_some();
+    let mouse_pressed = mouse_button_inputs.just_pressed(MouseButton::Left);
+    if touch_pressed != pointer.is_touch && (touch_pressed || mouse_pressed) {
+        log::info!(
+            "Switching the pointer to the touch input: {}",
+            touch_pressed
+        );
+        pointer.is_touch = touch_pressed;
+    }
+
+    pointer.just_pressed = mouse_pressed || touch_pressed;
+    pointer.just_released = mouse_button_inputs.just_released(MouseButton::Left)
+        || touches.iter_just_released().next().is_some();
+    pointer.is_pressed =
+        mouse_button_inputs.pressed(MouseButton::Left) || touches.iter().next().is_some();
+    pointer.position = match touch {
+        Some(touch) => Some(touch_to_window(window, touch.position())),
+        None => window.cursor_position(),
+    };
+
+    pointer.held_for = match pointer.is_pressed && !pointer.just_pressed {
+        true => pointer.held_for + time.delta(),
+        false => Default::default(),
+    };
+}
+
+/// Touches have the origin in the top left corner of the window, the cursor has it in the bottom
+/// left one. Bevy turns the touches over by itself on the mobile platforms only
+fn touch_to_window(window: &Window, position: Vec2) -> Vec2 {
+    match cfg!(any(target_os = "android", target_os = "ios")) {
+        true => position,
+        false => Vec2::new(position.x, window.height() - position.y),
+    }
+}
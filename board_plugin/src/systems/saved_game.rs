@@ -0,0 +1,88 @@
+use std::time::Duration;
+
+use bevy::{log, prelude::*};
+
+use crate::{
+    components::guide_text_timer::GuideTextTimer,
+    events::{ChangeGuideTextEvent, SaveGameEvent},
+    resources::{
+        board::Board,
+        deck::Deck,
+        depth::Depth,
+        game_result::GameResult,
+        game_state::{GameState, PlayerType},
+        pending_turn::PendingTurn,
+        saved_game::{SavedGame, SavedGameInUse},
+    },
+    rules::rule_set::RuleSetType,
+};
+
+/// Writes the unfinished game to the storage. Must run between the turns,
+/// after the move is applied and before a bot starts the next one.
+/// A request made while the moved piece is sliding waits until the turn is switched
+pub fn save_game(
+    mut commands: Commands,
+    mut is_requested: Local<bool>,
+    mut save_game_rdr: EventReader<SaveGameEvent>,
+    pending_turn: Option<Res<PendingTurn>>,
+    board: Res<Board>,
+    deck: Res<Deck>,
+    game_state: Res<GameState>,
+    depth: Res<Depth>,
+    rule_set_type: Res<RuleSetType>,
+    mut change_guide_text_ewr: EventWriter<ChangeGuideTextEvent>,
+) {
+    // a request left from the previous game is forgotten
+    if game_state.is_added() {
+        *is_requested = false;
+    }
+    if save_game_rdr.iter().last().is_some() {
+        *is_requested = true;
+    }
+    if !*is_requested || pending_turn.is_some() {
+        return;
+    }
+    *is_requested = false;
+
+    let is_network_game = game_state
+        .players
+        .iter()
+        .any(|player| player.player_type == PlayerType::Remote);
+    let text = if is_network_game {
+        log::info!("Network games can not be saved");
+        "Network games can not be saved"
+    } else {
+        let saved_game = SavedGame::new(&board, &deck, &game_state, &depth, *rule_set_type);
+        match saved_game.save() {
+            Ok(()) => {
+                log::info!("Game is saved on turn {}", game_state.turn);
+                commands.insert_resource(SavedGameInUse);
+                "Game is saved"
+            }
+            Err(e) => {
+                log::warn!("Game is not saved: {}", e);
+                "Game could not be saved"
+            }
+        }
+    };
+
+    change_guide_text_ewr.send(ChangeGuideTextEvent {
+        text: text.to_owned(),
+    });
+    commands.spawn().insert(GuideTextTimer {
+        old_text: format!("{} to move. Select a card", game_state.curr_color.name()),
+        timer: Timer::new(Duration::from_secs(1), false),
+    });
+}
+
+/// A finished game can not be continued, so its save is removed
+pub fn forget_finished_save(
+    game_result: Option<Res<GameResult>>,
+    saved_game_in_use: Option<Res<SavedGameInUse>>,
+) {
+    let is_finished = game_result.map_or(false, |game_result| game_result.is_added());
+    if is_finished && saved_game_in_use.is_some() {
+        log::info!("Saved game is finished, removing the save");
+        SavedGame::delete();
+    }
+}
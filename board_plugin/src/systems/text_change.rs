@@ -3,12 +3,13 @@ use bevy::{log, prelude::EventReader};
 use crate::{
     components::{
         guide_text_timer::GuideTextTimer,
-        texts::{GuideText, TurnText, EvaluationText},
+        texts::{ClockText, GuideText, TurnText, EvaluationText},
     },
     events::ChangeGuideTextEvent,
     resources::{
         board_assets::BoardAssets,
-        game_state::{GameState, PlayerColor}, text_handler::EvaluationResult,
+        clocks::Clocks,
+        game_state::{GameState, PlayerColor}, handicap::Handicap, text_handler::EvaluationResult,
     },
 };
 
@@ -74,10 +75,13 @@ pub fn change_turn_text(
     mut text_q: Query<&mut Text>,
 ) {
     if game_state.is_changed() {
-        let (value, color) = match game_state.curr_color {
+        let (mut value, color) = match game_state.curr_color {
             PlayerColor::Red => (format!("Red turn: {}", game_state.turn), Color::RED),
             PlayerColor::Blue => (format!("Blue turn: {}", game_state.turn), Color::BLUE),
         };
+        if game_state.handicap != Handicap::None {
+            value = format!("{}\nHandicap: {}", value, game_state.handicap.to_string());
+        }
         for children_components in parents_q.iter() {
             for child_entity in children_components.iter() {
                 match text_q.get_mut(*child_entity) {
@@ -141,3 +145,38 @@ pub fn change_evaluation_text(
     }
 }
 
+pub fn change_clock_text(
+    clocks: Res<Clocks>,
+    board_assets: Res<BoardAssets>,
+    parents_q: Query<&Children, With<ClockText>>,
+    mut text_q: Query<&mut Text>,
+) {
+    if !clocks.is_timed() || !clocks.is_changed() {
+        return;
+    }
+
+    let style = |color: Color| TextStyle {
+        color,
+        font: board_assets.font.clone(),
+        font_size: board_assets.turn_text_size,
+    };
+    let sections = vec![
+        TextSection {
+            value: format!("Red {}\n", clocks.red.to_string()),
+            style: style(Color::RED),
+        },
+        TextSection {
+            value: format!("Blue {}", clocks.blue.to_string()),
+            style: style(Color::BLUE),
+        },
+    ];
+
+    for children_components in parents_q.iter() {
+        for child_entity in children_components.iter() {
+            if let Ok(mut text) = text_q.get_mut(*child_entity) {
+                text.sections = sections.clone();
+                break;
+            }
+        }
+    }
+}
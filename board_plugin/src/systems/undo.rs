@@ -0,0 +1,171 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    components::{
+        board_tile::BoardTile, card_board::CardOwner, card_index::CardIndex,
+        coordinates::Coordinates, pieces::Piece,
+    },
+    events::{
+        ChangeGuideTextEvent, MirrorCardEvent, ResetAllowedMovesEvent, ResetSelectedCardColorEvent,
+        SaveGameEvent, TurnProcessEvent, UndoMoveEvent,
+    },
+    resources::{
+        board::Board,
+        board_assets::BoardAssets,
+        deck::{Deck, NEUTRAL_CARD_IDX},
+        dragged_piece::{DraggedPiece, DropChoice},
+        game_state::{GameState, PlayerType},
+        last_move::LastMove,
+        pending_turn::PendingTurn,
+        saved_game::SavedGameInUse,
+        selected::{SelectedCard, SelectedPiece},
+        undo_history::{TurnSnapshot, UndoHistory},
+    },
+    BoardPlugin,
+};
+
+/// Remembers the game at the beginning of every turn
+pub fn record_turn(
+    board: Res<Board>,
+    deck: Res<Deck>,
+    game_state: Res<GameState>,
+    last_move: Option<Res<LastMove>>,
+    mut undo_history: ResMut<UndoHistory>,
+    mut turn_process_rdr: EventReader<TurnProcessEvent>,
+) {
+    for _ in turn_process_rdr.iter() {
+        undo_history.push(TurnSnapshot::new(
+            &board.tile_map,
+            &deck,
+            &game_state,
+            last_move.as_deref(),
+        ));
+    }
+}
+
+/// Takes back the moves made since the previous turn of a human, the bot moves are
+/// taken back with the move of the human. The clocks keep their time.
+/// A saved game which is played on is saved again
+// a system takes at most 16 parameters, so some of them come in tuples
+pub fn take_back_move<T>(
+    mut commands: Commands,
+    board_assets: Res<BoardAssets>,
+    mut board: ResMut<Board>,
+    mut deck: ResMut<Deck>,
+    mut game_state: ResMut<GameState>,
+    mut undo_history: ResMut<UndoHistory>,
+    pending_turn: Option<Res<PendingTurn>>,
+    (mut selected_card, mut selected_piece): (ResMut<SelectedCard>, ResMut<SelectedPiece>),
+    (saved_game_in_use, mut save_game_ewr): (
+        Option<Res<SavedGameInUse>>,
+        EventWriter<SaveGameEvent>,
+    ),
+    tiles_q: Query<(Entity, &Coordinates, Option<&Children>), With<BoardTile>>,
+    mut cards_q: Query<(&mut Transform, &mut CardIndex, &mut CardOwner)>,
+    mut undo_move_rdr: EventReader<UndoMoveEvent>,
+    mut mirror_card_ewr: EventWriter<MirrorCardEvent>,
+    mut reset_selected_card_color_ewr: EventWriter<ResetSelectedCardColorEvent>,
+    mut reset_allowed_moves_ewr: EventWriter<ResetAllowedMovesEvent>,
+    mut change_guide_text_ewr: EventWriter<ChangeGuideTextEvent>,
+) {
+    if undo_move_rdr.iter().last().is_none() {
+        return;
+    }
+
+    let is_network_game = game_state
+        .players
+        .iter()
+        .any(|player| player.player_type == PlayerType::Remote);
+    if is_network_game
+        || game_state.get_current_player().player_type != PlayerType::Human
+        || pending_turn.is_some()
+    {
+        log::info!("Moves are taken back only on the turn of a human in a local game");
+        return;
+    }
+
+    let snapshot = match undo_history.take_back(&game_state) {
+        Some(snapshot) => snapshot,
+        None => {
+            log::info!("There is no move to take back");
+            return;
+        }
+    };
+    log::info!("Taking back the moves to turn {}", snapshot.turn);
+
+    // the selection is made again in the restored position
+    if let Some(entity) = selected_card.entity {
+        reset_selected_card_color_ewr.send(ResetSelectedCardColorEvent(entity));
+        selected_card.entity = None;
+    }
+    selected_piece.clear();
+    reset_allowed_moves_ewr.send(ResetAllowedMovesEvent);
+    commands.remove_resource::<DraggedPiece>();
+    commands.remove_resource::<DropChoice>();
+
+    board.tile_map = snapshot.tile_map;
+    for (entity, coordinates, children) in tiles_q.iter() {
+        if let Some(children) = children {
+            for child in children.iter() {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+        commands.entity(entity).remove::<Piece>();
+
+        let piece = board.tile_map.map[coordinates.y as usize][coordinates.x as usize].piece;
+        BoardPlugin::<T>::spawn_a_piece(
+            piece,
+            &mut commands.entity(entity),
+            &board_assets,
+            board.tile_size,
+            board.padding,
+        );
+    }
+
+    // the places of the cards stay, the cards are put back to their old places
+    let places = deck
+        .cards
+        .iter()
+        .map(|entity| {
+            let translation = cards_q
+                .get(*entity)
+                .map_or(Vec3::ZERO, |(transform, _, _)| transform.translation);
+            (translation, deck.cardboards.get(entity).unwrap().bounds)
+        })
+        .collect::<Vec<_>>();
+    deck.cards = snapshot.cards.clone();
+    for (idx, entity) in snapshot.cards.iter().enumerate() {
+        let (translation, bounds) = places[idx];
+        let cardboard = deck.cardboards.get_mut(entity).unwrap();
+        cardboard.bounds = bounds;
+        cardboard.card.is_mirrored = snapshot.mirrored[idx];
+
+        if let Ok((mut transform, mut card_index, mut card_owner)) = cards_q.get_mut(*entity) {
+            transform.translation = translation;
+            transform.rotation = Quat::IDENTITY;
+            *card_index = CardIndex(idx as u8);
+            *card_owner = match idx {
+                0 | 1 => CardOwner::Blue,
+                NEUTRAL_CARD_IDX => CardOwner::Neutral,
+                _ => CardOwner::Red,
+            };
+        }
+        mirror_card_ewr.send(MirrorCardEvent(*entity));
+    }
+
+    let first_color = game_state.first_color;
+    game_state.restore_turn(snapshot.turn, snapshot.curr_color, first_color);
+    game_state.history = snapshot.history;
+    // the move before the restored turn is highlighted again
+    match snapshot.last_move {
+        Some(last_move) => commands.insert_resource(last_move),
+        None => commands.remove_resource::<LastMove>(),
+    }
+
+    change_guide_text_ewr.send(ChangeGuideTextEvent {
+        text: format!("{} to move. Select a card", game_state.curr_color.name()),
+    });
+    if saved_game_in_use.is_some() {
+        save_game_ewr.send(SaveGameEvent);
+    }
+}
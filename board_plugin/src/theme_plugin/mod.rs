@@ -0,0 +1,152 @@
+use bevy::{log, prelude::*};
+
+use crate::{
+    components::{
+        allowed_move::AllowedMove,
+        board_tile::BoardTile,
+        keyboard_cursor::KeyboardCursor,
+        last_move::{LastMoveCard, LastMoveTile},
+        pieces::Piece,
+    },
+    events::{MirrorCardEvent, ThemeChangedEvent},
+    resources::{
+        board_assets::BoardAssets,
+        deck::Deck,
+        selected::SelectedPiece,
+        theme::{Theme, ThemeLoader},
+        theme_options::ThemeOptions,
+        tile::TempleTile,
+    },
+};
+
+/// Skins the board with the theme selected in `ThemeOptions`. The themes are applied in every state,
+/// the entities which already exist are skinned again
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Theme>()
+            .init_asset_loader::<ThemeLoader>()
+            .add_event::<ThemeChangedEvent>()
+            .add_system(apply_theme.label("apply_theme"))
+            .add_system(reskin_board.after("apply_theme"));
+    }
+}
+
+/// Replaces `BoardAssets` when another theme or palette is selected or the file of the selected
+/// theme is loaded or changed. The texts drawn with the font of the old theme get the new font
+fn apply_theme(
+    asset_server: Res<AssetServer>,
+    themes: Res<Assets<Theme>>,
+    theme_options: Res<ThemeOptions>,
+    mut board_assets: ResMut<BoardAssets>,
+    mut texts_q: Query<&mut Text>,
+    mut theme_asset_rdr: EventReader<AssetEvent<Theme>>,
+    mut theme_changed_ewr: EventWriter<ThemeChangedEvent>,
+) {
+    let (name, handle) = theme_options.selected_theme();
+    let is_loaded = theme_asset_rdr.iter().any(|event| match event {
+        AssetEvent::Created { handle: loaded } | AssetEvent::Modified { handle: loaded } => {
+            loaded == handle
+        }
+        AssetEvent::Removed { .. } => false,
+    });
+    if !is_loaded && !theme_options.is_changed() {
+        return;
+    }
+
+    // the built-in assets are colored by the palette until the theme is loaded
+    let mut new_assets = match themes.get(handle) {
+        Some(theme) => {
+            log::info!("Applying the theme {} from {}", theme.name, name);
+            theme.board_assets(&theme_options.base, &asset_server)
+        }
+        None => theme_options.base.clone(),
+    };
+    log::info!("Applying the palette {}", theme_options.palette.to_string());
+    theme_options.palette.apply(&mut new_assets, &asset_server);
+
+    for mut text in texts_q.iter_mut() {
+        for section in text.sections.iter_mut() {
+            if section.style.font == board_assets.font {
+                section.style.font = new_assets.font.clone();
+            }
+        }
+    }
+    *board_assets = new_assets;
+
+    theme_changed_ewr.send(ThemeChangedEvent);
+}
+
+/// Colors the tiles, pieces and markers of the board with the new theme. Highlighted tiles
+/// keep their colors until they are reset
+fn reskin_board(
+    board_assets: Res<BoardAssets>,
+    deck: Option<Res<Deck>>,
+    selected_piece: Option<Res<SelectedPiece>>,
+    mut tiles_q: Query<
+        (&mut Sprite, &mut Handle<Image>, Option<&TempleTile>),
+        (With<BoardTile>, Without<AllowedMove>),
+    >,
+    pieces_q: Query<(Entity, &Piece, &Children), With<BoardTile>>,
+    cursor_q: Query<Entity, With<KeyboardCursor>>,
+    last_move_tiles_q: Query<Entity, With<LastMoveTile>>,
+    last_move_cards_q: Query<Entity, With<LastMoveCard>>,
+    mut sprites_q: Query<(&mut Sprite, &mut Handle<Image>), Without<BoardTile>>,
+    mut theme_changed_rdr: EventReader<ThemeChangedEvent>,
+    mut mirror_card_ewr: EventWriter<MirrorCardEvent>,
+) {
+    if theme_changed_rdr.iter().last().is_none() {
+        return;
+    }
+
+    for (mut sprite, mut texture, temple) in tiles_q.iter_mut() {
+        let material = match temple {
+            Some(_) => &board_assets.temple_tile_material,
+            None => &board_assets.tile_material,
+        };
+        sprite.color = material.color;
+        *texture = material.texture.clone();
+    }
+
+    let selected_tile = selected_piece.and_then(|selected_piece| selected_piece.entity);
+    for (entity, piece, children) in pieces_q.iter() {
+        let material = board_assets.piece_material(piece);
+        for child in children.iter() {
+            if let Ok((mut sprite, mut texture)) = sprites_q.get_mut(*child) {
+                sprite.color = match selected_tile == Some(entity) {
+                    true => board_assets.selected_piece_material.color,
+                    false => material.color,
+                };
+                *texture = material.texture.clone();
+            }
+        }
+    }
+
+    let markers = cursor_q
+        .iter()
+        .map(|entity| (entity, &board_assets.keyboard_cursor_material))
+        .chain(
+            last_move_tiles_q
+                .iter()
+                .map(|entity| (entity, &board_assets.last_move_tile_material)),
+        )
+        .chain(
+            last_move_cards_q
+                .iter()
+                .map(|entity| (entity, &board_assets.last_move_card_material)),
+        );
+    for (entity, material) in markers {
+        if let Ok((mut sprite, mut texture)) = sprites_q.get_mut(entity) {
+            sprite.color = material.color;
+            *texture = material.texture.clone();
+        }
+    }
+
+    // the grids of the cards are colored by the board plugin
+    if let Some(deck) = deck {
+        for entity in deck.cards.iter() {
+            mirror_card_ewr.send(MirrorCardEvent(*entity));
+        }
+    }
+}
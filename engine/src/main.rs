@@ -0,0 +1,346 @@
+//! Onitama engine with a text protocol in the spirit of UCI.
+//!
+//! The engine reads commands from stdin line by line and answers on stdout,
+//! so other programs can use the alpha beta search of the game.
+//! Cards are written by their names, moves as `<card> <from x> <from y> <to x> <to y>`
+//! with the board coordinates from 0 to 4, where `(0, 0)` is the red side corner.
+//!
+//! | Command                                     | Answer                                          |
+//! |---------------------------------------------|-------------------------------------------------|
+//! | `onitama`                                   | `id name ...`, `id author ...`, `onitamaok`     |
+//! | `isready`                                   | `readyok`                                       |
+//! | `rules standard\|stream`                    | Sets the rules of the next positions            |
+//! | `cards <c1> <c2> <c3> <c4> <c5>`            | Sets five different cards: two blue, the neutral, two red. The stamp of the neutral card tells who moves first |
+//! | `position startpos [moves <move>...]`       | Starting position with the cards                |
+//! | `position board <rows> <red\|blue> [moves <move>...]` | Board with the side to move. The cards are the current ones |
+//! | `go [depth <n>\|movetime <ms>\|infinite]`   | `info` lines for every finished depth and `bestmove` |
+//! | `stop`                                      | Stops the search, which answers with `bestmove` |
+//! | `quit`                                      | Exits                                           |
+//!
+//! `rows` are the board rows from `y = 0` to `y = 4` separated with `/`:
+//! `r` and `b` are pawns, `R` and `B` are masters and `.` is an empty tile.
+//! Search progress is reported as `info depth <n> score <score> nodes <n> time <ms> pv <move>`,
+//! where the score is given for the side to move. Errors are reported as `info string <message>`.
+//!
+//! A typical session:
+//! ```text
+//! > onitama
+//! < id name onitama-engine
+//! < id author onitama-rs
+//! < onitamaok
+//! > cards Tiger Crab Monkey Crane Dragon
+//! > position startpos moves Crab 1 4 1 3 Crane 1 0 1 1
+//! > go depth 4
+//! < info depth 1 score 4 nodes 9 time 0 pv Tiger 2 4 2 2
+//! < ...
+//! < bestmove Tiger 2 4 2 2
+//! ```
+
+mod position;
+mod search;
+
+use std::{
+    io::{self, BufRead},
+    time::Duration,
+};
+
+use board_plugin::{
+    resources::{card::CARDS, depth::Depth, game_state::PlayerColor, tile_map::TileMap},
+    rules::rule_set::RuleSetType,
+};
+
+use position::{parse_card, parse_move, Position};
+use search::{Limit, Search};
+
+#[derive(Debug, Default)]
+struct Engine {
+    rule_set_type: RuleSetType,
+    cards: Option<[u8; 5]>,
+    position: Option<Position>,
+    search: Option<Search>,
+}
+
+impl Engine {
+    /// Handles one command. Returns false when the engine must exit
+    fn handle(&mut self, line: &str) -> Result<bool, String> {
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some(command) => command,
+            None => return Ok(true),
+        };
+
+        match command {
+            "onitama" => {
+                println!("id name onitama-engine");
+                println!("id author onitama-rs");
+                println!("onitamaok");
+            }
+            "isready" => println!("readyok"),
+            "rules" => {
+                self.rule_set_type = match words.next() {
+                    Some("standard") => RuleSetType::Standard,
+                    Some("stream") => RuleSetType::WayOfTheStream,
+                    word => return Err(format!("Unknown rules: {:?}", word)),
+                };
+            }
+            "cards" => {
+                let mut cards = Vec::with_capacity(5);
+                for _ in 0..5 {
+                    let card =
+                        parse_card(words.next().ok_or_else(|| "Missing a card".to_owned())?)?;
+                    if cards.contains(&card) {
+                        return Err(format!("Card {} is given twice", CARDS[card as usize].name));
+                    }
+                    cards.push(card);
+                }
+                self.cards = cards.try_into().ok();
+            }
+            "position" => {
+                self.stop_search();
+                self.position = Some(self.parse_position(&mut words)?);
+            }
+            "go" => {
+                self.stop_search();
+                let position = self
+                    .position
+                    .clone()
+                    .ok_or_else(|| "Set the position first".to_owned())?;
+                if position.is_finished {
+                    println!("bestmove none");
+                    return Ok(true);
+                }
+                let limit = parse_limit(&mut words)?;
+                self.search = Some(Search::start(position, limit));
+            }
+            "stop" => self.stop_search(),
+            "quit" => {
+                self.stop_search();
+                return Ok(false);
+            }
+            _ => return Err(format!("Unknown command: {}", command)),
+        }
+
+        Ok(true)
+    }
+
+    fn stop_search(&mut self) {
+        if let Some(search) = self.search.take() {
+            search.stop();
+        }
+    }
+
+    fn parse_position<'a>(
+        &self,
+        words: &mut impl Iterator<Item = &'a str>,
+    ) -> Result<Position, String> {
+        let cards = self.cards.ok_or_else(|| "Set the cards first".to_owned())?;
+
+        let mut position = match words.next() {
+            Some("startpos") => Position::new(cards, self.rule_set_type),
+            Some("board") => {
+                let rows = words.next().ok_or_else(|| "Missing the board".to_owned())?;
+                let tile_map = TileMap::from_rows(rows)?;
+                let color = match words.next() {
+                    Some("red") => PlayerColor::Red,
+                    Some("blue") => PlayerColor::Blue,
+                    word => return Err(format!("Unknown side to move: {:?}", word)),
+                };
+                Position::with_tile_map(tile_map, cards, self.rule_set_type, Some(color))
+            }
+            word => return Err(format!("Unknown position: {:?}", word)),
+        };
+
+        match words.next() {
+            Some("moves") => {}
+            None => return Ok(position),
+            Some(word) => return Err(format!("Expected moves, got {}", word)),
+        }
+
+        while let Some(card) = words.next() {
+            let card = CARDS[parse_card(card)? as usize].name;
+            let mov = parse_move(words)?;
+            position.play(card, mov)?;
+        }
+        Ok(position)
+    }
+}
+
+fn parse_limit<'a>(words: &mut impl Iterator<Item = &'a str>) -> Result<Limit, String> {
+    let limit = match words.next() {
+        // the same depth as the bots of the game
+        None => Limit::Depth(Depth::default().0),
+        Some("infinite") => Limit::Infinite,
+        Some("depth") => Limit::Depth(parse_number(words.next())?.clamp(1, u8::MAX as u64) as u8),
+        Some("movetime") => Limit::MoveTime(Duration::from_millis(parse_number(words.next())?)),
+        Some(word) => return Err(format!("Unknown limit: {}", word)),
+    };
+    Ok(limit)
+}
+
+fn parse_number(word: Option<&str>) -> Result<u64, String> {
+    let word = word.ok_or_else(|| "Missing a number".to_owned())?;
+    word.parse::<u64>()
+        .map_err(|_| format!("Not a number: {}", word))
+}
+
+fn main() {
+    let mut engine = Engine::default();
+
+    for line in io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+
+        match engine.handle(line.trim()) {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => println!("info string {}", e),
+        }
+    }
+
+    // stdin is closed, the last search is finished before the exit
+    engine.stop_search();
+}
+
+#[cfg(test)]
+mod tests {
+    use board_plugin::resources::deck::NEUTRAL_CARD_IDX;
+
+    use super::*;
+
+    fn engine_with_cards() -> Engine {
+        let mut engine = Engine::default();
+        engine
+            .handle("cards Tiger Crab Monkey Crane Dragon")
+            .unwrap();
+        engine
+    }
+
+    #[test]
+    fn parses_the_commands() {
+        let mut engine = Engine::default();
+        assert_eq!(engine.handle(""), Ok(true));
+        assert_eq!(engine.handle("isready"), Ok(true));
+        assert_eq!(engine.handle("rules stream"), Ok(true));
+        assert_eq!(engine.rule_set_type, RuleSetType::WayOfTheStream);
+        assert!(engine.handle("rules chess").is_err());
+        assert!(engine.handle("castle").is_err());
+
+        assert!(engine.handle("cards Tiger Crab").is_err());
+        assert!(engine
+            .handle("cards Tiger Crab Monkey Crane Unicorn")
+            .is_err());
+        assert!(engine
+            .handle("cards Tiger Crab Monkey Crane tiger")
+            .is_err());
+        assert_eq!(engine.cards, None);
+        assert_eq!(
+            engine.handle("cards tiger crab monkey crane dragon"),
+            Ok(true)
+        );
+        assert_eq!(
+            engine.cards,
+            Some([
+                parse_card("Tiger").unwrap(),
+                parse_card("Crab").unwrap(),
+                parse_card("Monkey").unwrap(),
+                parse_card("Crane").unwrap(),
+                parse_card("Dragon").unwrap(),
+            ])
+        );
+
+        assert_eq!(engine.handle("quit"), Ok(false));
+    }
+
+    #[test]
+    fn parses_the_limits() {
+        let limit = |line: &str| parse_limit(&mut line.split_whitespace());
+        assert!(matches!(limit(""), Ok(Limit::Depth(depth)) if depth == Depth::default().0));
+        assert!(matches!(limit("depth 3"), Ok(Limit::Depth(3))));
+        assert!(matches!(limit("depth 0"), Ok(Limit::Depth(1))));
+        assert!(
+            matches!(limit("movetime 500"), Ok(Limit::MoveTime(time)) if time == Duration::from_millis(500))
+        );
+        assert!(matches!(limit("infinite"), Ok(Limit::Infinite)));
+        assert!(limit("depth").is_err());
+        assert!(limit("movetime soon").is_err());
+        assert!(limit("nodes 100").is_err());
+    }
+
+    #[test]
+    fn needs_the_cards_and_the_position() {
+        let mut engine = Engine::default();
+        assert!(engine.handle("position startpos").is_err());
+        assert!(engine.handle("go depth 1").is_err());
+
+        let mut engine = engine_with_cards();
+        assert!(engine.handle("position middlegame").is_err());
+        assert!(engine
+            .handle("position startpos move Crab 1 4 1 3")
+            .is_err());
+        assert_eq!(engine.handle("position startpos"), Ok(true));
+    }
+
+    #[test]
+    fn replays_the_moves() {
+        let mut engine = engine_with_cards();
+        assert_eq!(
+            engine.handle("position startpos moves Crab 1 4 1 3 Crane 1 0 1 1"),
+            Ok(true)
+        );
+
+        let position = engine.position.as_ref().unwrap();
+        assert_eq!(
+            position.board.tile_map.to_rows(),
+            "r.Rrr/.r.../...../.b.../b.Bbb"
+        );
+        assert_eq!(position.game_state.curr_color, PlayerColor::Blue);
+        assert_eq!(
+            position.card_name(&position.deck.cards[NEUTRAL_CARD_IDX]),
+            "Crane"
+        );
+        assert!(!position.is_finished);
+    }
+
+    #[test]
+    fn rejects_the_illegal_moves() {
+        let mut engine = engine_with_cards();
+        // Monkey has the blue stamp, so blue moves first
+        assert!(engine
+            .handle("position startpos moves Crane 1 0 1 1")
+            .is_err());
+        // red does not hold Tiger
+        assert!(engine
+            .handle("position startpos moves Crab 1 4 1 3 Tiger 2 0 2 2")
+            .is_err());
+        // Crab does not move diagonally
+        assert!(engine
+            .handle("position startpos moves Crab 1 4 0 3")
+            .is_err());
+        assert!(engine.handle("position startpos moves Crab 1 4 1").is_err());
+        assert!(engine
+            .handle("position startpos moves Crab 1 4 1 9")
+            .is_err());
+    }
+
+    #[test]
+    fn replays_the_moves_from_a_board() {
+        let mut engine = engine_with_cards();
+        assert!(engine
+            .handle("position board rrRrr/...../...../...../bbBbb green")
+            .is_err());
+        assert!(engine.handle("position board rrRrr/bbBbb red").is_err());
+        assert_eq!(
+            engine.handle("position board rrRrr/...../...../...../bbBbb red moves Crane 1 0 1 1"),
+            Ok(true)
+        );
+
+        let position = engine.position.as_ref().unwrap();
+        assert_eq!(
+            position.board.tile_map.to_rows(),
+            "r.Rrr/.r.../...../...../bbBbb"
+        );
+        assert_eq!(position.game_state.curr_color, PlayerColor::Blue);
+    }
+}
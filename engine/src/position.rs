@@ -0,0 +1,186 @@
+use bevy::{prelude::Entity, utils::HashMap};
+use board_plugin::{
+    ai::alpha_beta::AlphaBetaAgent,
+    bounds::Bounds2,
+    components::{card_board::CardBoard, coordinates::Coordinates},
+    resources::{
+        board::Board,
+        card::CARDS,
+        deck::{Deck, NEUTRAL_CARD_IDX},
+        draw_rules::DrawRules,
+        game_state::{GameState, Player, PlayerColor, PlayerType},
+        handicap::Handicap,
+        tile_map::{Move, MoveResult, TileMap, BOARD_SIZE},
+    },
+    rules::rule_set::RuleSetType,
+};
+
+/// Position searched by the engine. It is made of the same resources as the game,
+/// but the entities only identify the cards
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub board: Board,
+    pub game_state: GameState,
+    pub deck: Deck,
+    /// The last move has won the game
+    pub is_finished: bool,
+}
+
+impl Position {
+    /// Starting position with the cards in the deck order: two blue cards, the neutral card and two red cards
+    pub fn new(cards: [u8; 5], rule_set_type: RuleSetType) -> Self {
+        Self::with_tile_map(TileMap::new(), cards, rule_set_type, None)
+    }
+
+    /// Position from the rows of the board, see `TileMap::from_rows`
+    pub fn with_tile_map(
+        tile_map: TileMap,
+        cards: [u8; 5],
+        rule_set_type: RuleSetType,
+        curr_color: Option<PlayerColor>,
+    ) -> Self {
+        let mut cardboards = HashMap::default();
+        let mut entities = Vec::with_capacity(5);
+        for (i, card_idx) in cards.iter().enumerate() {
+            let mut card = CARDS[*card_idx as usize].clone();
+            // blue cards are seen from the other side of the table
+            card.is_mirrored = i < NEUTRAL_CARD_IDX;
+
+            let entity = Entity::from_raw(i as u32);
+            cardboards.insert(
+                entity,
+                CardBoard {
+                    card,
+                    bounds: Bounds2 {
+                        position: Default::default(),
+                        size: Default::default(),
+                    },
+                },
+            );
+            entities.push(entity);
+        }
+        let deck = Deck {
+            cardboards,
+            cards: entities,
+        };
+
+        let board = Board {
+            bounds: Bounds2 {
+                position: Default::default(),
+                size: Default::default(),
+            },
+            tile_size: 0.,
+            scale: 1.,
+            padding: 0.,
+            move_duration: Default::default(),
+            tile_map,
+            entity: Entity::from_raw(0),
+            is_flipped: false,
+        };
+
+        // agents are not used, the engine runs the search itself
+        let player = || Player {
+            agent: Box::new(AlphaBetaAgent::new(1)),
+            player_type: PlayerType::AlphaBeta,
+        };
+        let mut game_state = GameState::new(
+            player(),
+            player(),
+            Handicap::None,
+            rule_set_type.build(DrawRules::default()),
+        );
+        game_state.set_first_player(&deck);
+        if let Some(color) = curr_color {
+            game_state.first_color = color;
+            game_state.clear();
+        }
+        game_state.push_position(&board.tile_map, &deck);
+
+        Self {
+            board,
+            game_state,
+            deck,
+            is_finished: false,
+        }
+    }
+
+    /// Name of the card in the deck
+    #[inline]
+    pub fn card_name(&self, entity: &Entity) -> &'static str {
+        self.deck.cardboards.get(entity).unwrap().card.name
+    }
+
+    /// Plays the move after checking it with the rules
+    pub fn play(&mut self, card: &str, mov: Move) -> Result<(), String> {
+        if self.is_finished {
+            return Err("The game is over".to_owned());
+        }
+
+        let (entity, card) = self
+            .deck
+            .get_player_cards(&self.game_state)
+            .into_iter()
+            .find(|(_, c)| c.name == card)
+            .ok_or_else(|| format!("{} is not in the hand of the side to move", card))?;
+
+        let possible_moves = self.board.tile_map.generate_possible_moves_for_card(
+            &self.game_state.curr_color,
+            &card,
+            self.game_state.rules.as_ref(),
+        );
+        if !possible_moves.contains(&mov) {
+            return Err(format!("Illegal move {} {}", card.name, format_move(&mov)));
+        }
+
+        let result =
+            self.board
+                .tile_map
+                .make_a_move(mov.from, mov.to, self.game_state.rules.as_ref());
+        let card_idx = self.deck.cards.iter().position(|e| *e == entity).unwrap();
+        self.deck.swap_card_with_neutral(card_idx);
+        self.game_state.next_turn();
+        self.game_state
+            .push_position(&self.board.tile_map, &self.deck);
+        self.is_finished = result == MoveResult::Win;
+        Ok(())
+    }
+}
+
+#[inline]
+pub fn format_move(mov: &Move) -> String {
+    format!("{} {} {} {}", mov.from.x, mov.from.y, mov.to.x, mov.to.y)
+}
+
+/// Returns the index of the card in `CARDS`
+pub fn parse_card(word: &str) -> Result<u8, String> {
+    CARDS
+        .iter()
+        .position(|card| card.name.eq_ignore_ascii_case(word))
+        .map(|idx| idx as u8)
+        .ok_or_else(|| format!("Unknown card: {}", word))
+}
+
+/// Reads `<from x> <from y> <to x> <to y>`
+pub fn parse_move<'a>(words: &mut impl Iterator<Item = &'a str>) -> Result<Move, String> {
+    let mut coordinates = [0u8; 4];
+    for value in coordinates.iter_mut() {
+        let word = words
+            .next()
+            .ok_or_else(|| "Missing a coordinate".to_owned())?;
+        *value = word
+            .parse::<u8>()
+            .ok()
+            .filter(|v| (*v as usize) < BOARD_SIZE)
+            .ok_or_else(|| format!("Bad coordinate: {}", word))?;
+    }
+    Ok(Move {
+        from: Coordinates {
+            x: coordinates[0],
+            y: coordinates[1],
+        },
+        to: Coordinates {
+            x: coordinates[2],
+            y: coordinates[3],
+        },
+    })
+}
@@ -0,0 +1,117 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use board_plugin::{ai::alpha_beta::AlphaBetaAgent, resources::game_state::PlayerColor};
+
+use crate::position::{format_move, Position};
+
+// Deepest iteration of the searches limited only by time
+const MAX_DEPTH: u8 = 32;
+// Evaluation of a won position, deeper iterations can not find anything better
+const WIN_SCORE: i32 = 10000;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Limit {
+    Depth(u8),
+    MoveTime(Duration),
+    Infinite,
+}
+
+/// Search running in its own thread, so the commands are still read meanwhile
+#[derive(Debug)]
+pub struct Search {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl Search {
+    pub fn start(position: Position, limit: Limit) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+
+        if let Limit::MoveTime(time) = limit {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                thread::sleep(time);
+                stop.store(true, Ordering::Relaxed);
+            });
+        }
+
+        let handle = {
+            let stop = stop.clone();
+            thread::spawn(move || iterative_deepening(&position, limit, stop))
+        };
+
+        Self { stop, handle }
+    }
+
+    /// Stops the search and waits for its `bestmove`
+    pub fn stop(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if self.handle.join().is_err() {
+            println!("info string Search has failed");
+        }
+    }
+}
+
+/// Searches one ply deeper every iteration and reports it with an `info` line.
+/// A stopped iteration is thrown away, so `bestmove` is from the deepest finished one
+fn iterative_deepening(position: &Position, limit: Limit, stop: Arc<AtomicBool>) {
+    let started = Instant::now();
+    let max_depth = match limit {
+        Limit::Depth(depth) => depth,
+        Limit::MoveTime(_) | Limit::Infinite => MAX_DEPTH,
+    };
+
+    let mut best_move = None;
+    for depth in 1..=max_depth {
+        // the first iteration is never stopped, so there is always a move to play
+        let agent = match depth {
+            1 => AlphaBetaAgent::new(depth),
+            _ => AlphaBetaAgent::with_stop(depth, stop.clone()),
+        };
+        let result = agent.search(&position.board, &position.game_state, &position.deck);
+        if depth > 1 && stop.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let (card, mov) = match (result.card, result.mov) {
+            (Some(card), Some(mov)) => (card, mov),
+            _ => break,
+        };
+
+        // scores are reported for the side to move
+        let score = match position.game_state.curr_color {
+            PlayerColor::Red => result.score,
+            PlayerColor::Blue => -result.score,
+        };
+        println!(
+            "info depth {} score {} nodes {} time {} pv {} {}",
+            depth,
+            score,
+            result.positions,
+            started.elapsed().as_millis(),
+            position.card_name(&card),
+            format_move(&mov)
+        );
+
+        best_move = Some((card, mov));
+        if score.abs() >= WIN_SCORE {
+            break;
+        }
+    }
+
+    match best_move {
+        Some((card, mov)) => println!(
+            "bestmove {} {}",
+            position.card_name(&card),
+            format_move(&mov)
+        ),
+        None => println!("bestmove none"),
+    }
+}
@@ -0,0 +1,143 @@
+//! Game server for the network games.
+//!
+//! Hosts the game rooms over WebSockets, so the web build can play network games too.
+//! Every move is checked with the rules engine before it is relayed to the opponent.
+//! The messages are the same as in the direct TCP games,
+//! see `board_plugin::network_plugin::protocol`.
+//!
+//! By default the server listens only on the loopback address
+//! and needs nothing else to run, which is enough for local development.
+
+mod room;
+
+use std::{
+    env,
+    io::ErrorKind,
+    net::{TcpListener, TcpStream},
+    process,
+    sync::{
+        mpsc::{self, Receiver},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use board_plugin::network_plugin::protocol::Message;
+use tungstenite::{error::ProtocolError, Message as Frame, WebSocket};
+
+use room::{Client, Rooms};
+
+const LOCAL_ADDRESS: &str = "127.0.0.1:9001";
+const PUBLIC_ADDRESS: &str = "0.0.0.0:9001";
+// how long the client thread waits for a frame before it writes the relayed messages
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+fn main() {
+    let address = parse_args();
+    let listener = match TcpListener::bind(&address) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Could not listen on {}: {}", address, e);
+            process::exit(1);
+        }
+    };
+    println!("Listening on ws://{}", address);
+
+    let rooms = Arc::new(Mutex::new(Rooms::default()));
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let rooms = rooms.clone();
+                thread::spawn(move || {
+                    if let Err(e) = serve(stream, rooms) {
+                        eprintln!("Client has failed: {}", e);
+                    }
+                });
+            }
+            Err(e) => eprintln!("Could not accept a connection: {}", e),
+        }
+    }
+}
+
+fn parse_args() -> String {
+    let mut address = LOCAL_ADDRESS.to_owned();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--local" => address = LOCAL_ADDRESS.to_owned(),
+            "--public" => address = PUBLIC_ADDRESS.to_owned(),
+            "--address" => match args.next() {
+                Some(value) => address = value,
+                None => usage(),
+            },
+            _ => usage(),
+        }
+    }
+    address
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: onitama-server [--local | --public | --address <host:port>]");
+    eprintln!("  --local    listen on {} (default)", LOCAL_ADDRESS);
+    eprintln!("  --public   listen on {}", PUBLIC_ADDRESS);
+    process::exit(2);
+}
+
+fn serve(stream: TcpStream, rooms: Arc<Mutex<Rooms>>) -> Result<(), String> {
+    let peer = stream.peer_addr().map_err(|e| e.to_string())?;
+    let mut socket = tungstenite::accept(stream).map_err(|e| e.to_string())?;
+    socket
+        .get_ref()
+        .set_read_timeout(Some(POLL_INTERVAL))
+        .map_err(|e| e.to_string())?;
+    println!("Client {} has connected", peer);
+
+    let (outgoing, incoming) = mpsc::channel();
+    let mut client = Client::new(outgoing);
+    let result = serve_client(&mut socket, &mut client, &incoming, &rooms);
+
+    // the seat stays in the room, so the player can rejoin
+    rooms.lock().unwrap().leave(&mut client);
+    println!("Client {} has disconnected", peer);
+    result
+}
+
+fn serve_client(
+    socket: &mut WebSocket<TcpStream>,
+    client: &mut Client,
+    incoming: &Receiver<String>,
+    rooms: &Mutex<Rooms>,
+) -> Result<(), String> {
+    loop {
+        match socket.read_message() {
+            Ok(Frame::Text(text)) => {
+                for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                    match Message::parse(line) {
+                        Ok(message) => rooms.lock().unwrap().handle(client, message),
+                        Err(reason) => client.reply(&Message::Error { reason }),
+                    }
+                }
+            }
+            Ok(Frame::Close(_)) => return Ok(()),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if e.kind() == ErrorKind::WouldBlock || e.kind() == ErrorKind::TimedOut => {}
+            // a dropped socket is a normal way to leave, the player may rejoin later
+            Err(tungstenite::Error::ConnectionClosed)
+            | Err(tungstenite::Error::Protocol(ProtocolError::ResetWithoutClosingHandshake)) => {
+                return Ok(())
+            }
+            Err(tungstenite::Error::Io(e)) if e.kind() == ErrorKind::ConnectionReset => {
+                return Ok(())
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+
+        for line in incoming.try_iter() {
+            socket
+                .write_message(Frame::Text(line))
+                .map_err(|e| e.to_string())?;
+        }
+    }
+}
@@ -0,0 +1,596 @@
+use std::{collections::HashMap, sync::mpsc::Sender};
+
+use board_plugin::{
+    network_plugin::protocol::{Message, PROTOCOL_VERSION},
+    resources::{
+        card::CARDS,
+        deck::NEUTRAL_CARD_IDX,
+        draw_rules::DrawRules,
+        game_state::PlayerColor,
+        tile_map::{Move, MoveResult, TileMap},
+    },
+    rules::rule_set::{RuleSet, RuleSetType},
+};
+use rand::Rng;
+
+const HOST_SEAT: usize = 0;
+const GUEST_SEAT: usize = 1;
+
+/// Connected client. Messages pushed to `outgoing` are written to its socket
+#[derive(Debug)]
+pub struct Client {
+    outgoing: Sender<String>,
+    /// Room id and the seat index
+    seat: Option<(String, usize)>,
+}
+
+impl Client {
+    pub fn new(outgoing: Sender<String>) -> Self {
+        Self {
+            outgoing,
+            seat: None,
+        }
+    }
+
+    pub fn reply(&self, message: &Message) {
+        // the socket thread owns the receiver, so it is alive while the client is handled
+        let _ = self.outgoing.send(message.to_string());
+    }
+
+    fn error(&self, reason: &str) {
+        self.reply(&Message::Error {
+            reason: reason.to_owned(),
+        });
+    }
+}
+
+/// Player's place in the room. The token lets the player take it back after a dropped connection
+#[derive(Debug, Default)]
+struct Seat {
+    token: Option<String>,
+    outgoing: Option<Sender<String>>,
+}
+
+impl Seat {
+    fn take(&mut self, client: &Client) -> String {
+        let token = self
+            .token
+            .get_or_insert_with(|| format!("{:016x}", rand::thread_rng().gen::<u64>()))
+            .clone();
+        self.outgoing = Some(client.outgoing.clone());
+        token
+    }
+
+    fn send(&self, message: &Message) {
+        // messages to a disconnected player are lost, except the moves kept by the room
+        if let Some(outgoing) = self.outgoing.as_ref() {
+            let _ = outgoing.send(message.to_string());
+        }
+    }
+}
+
+/// Game state kept by the server to check the moves
+#[derive(Debug)]
+struct Game {
+    tile_map: TileMap,
+    /// Card indices in the deck order
+    cards: [u8; 5],
+    rules: Box<dyn RuleSet>,
+    guest_color: PlayerColor,
+    curr_color: PlayerColor,
+    /// Started without a guest, the host plays both sides for the spectators
+    is_watched_only: bool,
+    is_finished: bool,
+}
+
+impl Game {
+    fn new(
+        guest_color: PlayerColor,
+        rule_set_type: RuleSetType,
+        cards: [u8; 5],
+        is_watched_only: bool,
+    ) -> Self {
+        let rules = rule_set_type.build(DrawRules::default());
+        let curr_color = rules.first_player(&CARDS[cards[NEUTRAL_CARD_IDX] as usize]);
+        Self {
+            tile_map: TileMap::new(),
+            cards,
+            rules,
+            guest_color,
+            curr_color,
+            is_watched_only,
+            is_finished: false,
+        }
+    }
+
+    #[inline]
+    fn seat_color(&self, seat: usize) -> PlayerColor {
+        match seat {
+            _ if self.is_watched_only => self.curr_color,
+            GUEST_SEAT => self.guest_color,
+            _ => self.guest_color.enemy(),
+        }
+    }
+
+    /// Checks the move with the same move generation as the clients and plays it
+    fn play(&mut self, color: PlayerColor, card: &str, mov: Move) -> Result<(), String> {
+        if self.is_finished {
+            return Err("The game is over".to_owned());
+        }
+        if color != self.curr_color {
+            return Err("Not your turn".to_owned());
+        }
+
+        let hand = match color {
+            PlayerColor::Red => [3, 4],
+            PlayerColor::Blue => [0, 1],
+        };
+        let card_idx = hand
+            .into_iter()
+            .find(|idx| CARDS[self.cards[*idx] as usize].name == card)
+            .ok_or_else(|| format!("{} is not in your hand", card))?;
+
+        let mut card = CARDS[self.cards[card_idx] as usize].clone();
+        // blue cards are mirrored, as in the deck of the clients
+        card.is_mirrored = color == PlayerColor::Blue;
+        let possible_moves =
+            self.tile_map
+                .generate_possible_moves_for_card(&color, &card, self.rules.as_ref());
+        if !possible_moves.contains(&mov) {
+            return Err(format!("Illegal move {} {:?}", card.name, mov));
+        }
+
+        let result = self
+            .tile_map
+            .make_a_move(mov.from, mov.to, self.rules.as_ref());
+        self.cards.swap(card_idx, NEUTRAL_CARD_IDX);
+        self.is_finished = result == MoveResult::Win;
+        self.curr_color = color.enemy();
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Room {
+    seats: [Seat; 2],
+    spectators: Vec<Sender<String>>,
+    game: Option<Game>,
+    /// `START` of the game, sent to the spectators who come later
+    start: Option<Message>,
+    /// Valid moves of the game, resent to the rejoining players
+    moves: Vec<Message>,
+}
+
+impl Room {
+    fn send_to_spectators(&mut self, message: &Message) {
+        let line = message.to_string();
+        // the receiver is dropped when the spectator has disconnected
+        self.spectators
+            .retain(|spectator| spectator.send(line.clone()).is_ok());
+    }
+
+    /// Nobody is connected, so nobody can come back
+    #[inline]
+    fn is_abandoned(&self) -> bool {
+        self.seats.iter().all(|seat| seat.outgoing.is_none())
+    }
+}
+
+/// All rooms of the server. Must be locked while a message is handled
+#[derive(Debug, Default)]
+pub struct Rooms {
+    rooms: HashMap<String, Room>,
+}
+
+impl Rooms {
+    pub fn handle(&mut self, client: &mut Client, message: Message) {
+        match message {
+            Message::Hello { version } if version != PROTOCOL_VERSION => {
+                client.error(&format!("Unsupported protocol version {}", version));
+            }
+            // the server greets the players when the room is full
+            Message::Hello { .. } => {}
+            Message::Create
+            | Message::Join { .. }
+            | Message::Rejoin { .. }
+            | Message::Watch { .. }
+                if client.seat.is_some() =>
+            {
+                client.error("Already in a room");
+            }
+            Message::Create => self.create(client),
+            Message::Join { room } => self.join(client, room),
+            Message::Rejoin { room, token, moves } => {
+                self.rejoin(client, room, &token, moves as usize)
+            }
+            Message::Watch { room, moves } => self.watch(client, room, moves as usize),
+            Message::Room { .. } => client.error("ROOM is sent only by the server"),
+            message => self.play(client, message),
+        }
+    }
+
+    /// Frees the connection of the seat. The player can rejoin while the room exists
+    pub fn leave(&mut self, client: &mut Client) {
+        if let Some((id, seat)) = client.seat.take() {
+            if let Some(room) = self.rooms.get_mut(&id) {
+                room.seats[seat].outgoing = None;
+                if room.is_abandoned() {
+                    room.send_to_spectators(&Message::Bye);
+                    println!("Room {} is closed", id);
+                    self.rooms.remove(&id);
+                }
+            }
+        }
+    }
+
+    fn create(&mut self, client: &mut Client) {
+        let mut rng = rand::thread_rng();
+        let id = loop {
+            let id = format!("{:04}", rng.gen_range(0..10000));
+            if !self.rooms.contains_key(&id) {
+                break id;
+            }
+        };
+
+        let mut room = Room::default();
+        let token = room.seats[HOST_SEAT].take(client);
+        self.rooms.insert(id.clone(), room);
+        client.seat = Some((id.clone(), HOST_SEAT));
+        println!("Room {} is created", id);
+        client.reply(&Message::Room { room: id, token });
+    }
+
+    fn join(&mut self, client: &mut Client, id: String) {
+        let room = match self.rooms.get_mut(&id) {
+            Some(room) => room,
+            None => return client.error(&format!("No room {}", id)),
+        };
+        if room.seats[GUEST_SEAT].token.is_some() {
+            return client.error(&format!("Room {} is full", id));
+        }
+        if room.game.is_some() {
+            return client.error(&format!("Game in the room {} has started. Watch it", id));
+        }
+
+        let token = room.seats[GUEST_SEAT].take(client);
+        client.seat = Some((id.clone(), GUEST_SEAT));
+        println!("Room {} is full", id);
+        client.reply(&Message::Room { room: id, token });
+
+        let hello = Message::Hello {
+            version: PROTOCOL_VERSION,
+        };
+        room.seats.iter().for_each(|seat| seat.send(&hello));
+    }
+
+    fn rejoin(&mut self, client: &mut Client, id: String, token: &str, moves: usize) {
+        let room = match self.rooms.get_mut(&id) {
+            Some(room) => room,
+            None => return client.error(&format!("No room {}", id)),
+        };
+        let seat = match room
+            .seats
+            .iter()
+            .position(|seat| seat.token.as_deref() == Some(token))
+        {
+            Some(seat) => seat,
+            None => return client.error("Wrong token"),
+        };
+
+        let token = room.seats[seat].take(client);
+        client.seat = Some((id.clone(), seat));
+        println!("Player has rejoined the room {}", id);
+        client.reply(&Message::Room { room: id, token });
+
+        // moves which were played while the player was away
+        for message in room.moves.iter().skip(moves) {
+            client.reply(message);
+        }
+    }
+
+    /// Spectators have no seat, they get the game so far and then the relayed messages
+    fn watch(&mut self, client: &mut Client, id: String, moves: usize) {
+        let room = match self.rooms.get_mut(&id) {
+            Some(room) => room,
+            None => return client.error(&format!("No room {}", id)),
+        };
+
+        room.spectators.push(client.outgoing.clone());
+        println!("Spectator is watching the room {}", id);
+        client.reply(&Message::Room {
+            room: id,
+            token: "-".to_owned(),
+        });
+        client.reply(&Message::Hello {
+            version: PROTOCOL_VERSION,
+        });
+
+        if let Some(start) = room.start.as_ref() {
+            client.reply(start);
+        }
+        for message in room.moves.iter().skip(moves) {
+            client.reply(message);
+        }
+    }
+
+    fn play(&mut self, client: &mut Client, message: Message) {
+        let (id, seat) = match client.seat.clone() {
+            Some(seat) => seat,
+            None => return client.error("Create or join a room first"),
+        };
+        let room = match self.rooms.get_mut(&id) {
+            Some(room) => room,
+            None => return client.error(&format!("No room {}", id)),
+        };
+
+        match &message {
+            Message::Start {
+                color,
+                rules,
+                cards,
+            } => {
+                if seat != HOST_SEAT {
+                    return client.error("Only the host starts the game");
+                }
+                if room.game.is_some() {
+                    return client.error("The game has already started");
+                }
+                let is_watched_only = room.seats[GUEST_SEAT].token.is_none();
+                room.game = Some(Game::new(*color, *rules, *cards, is_watched_only));
+                room.start = Some(message.clone());
+            }
+            Message::Move { card, mov } => {
+                let game = match room.game.as_mut() {
+                    Some(game) => game,
+                    None => return client.error("The game has not started"),
+                };
+                let color = game.seat_color(seat);
+                if let Err(reason) = game.play(color, card, *mov) {
+                    return client.error(&reason);
+                }
+                room.moves.push(message.clone());
+            }
+            Message::Resign => {
+                if let Some(game) = room.game.as_mut() {
+                    game.is_finished = true;
+                }
+            }
+            Message::Bye => {
+                // the player leaves for good, the seat can not be taken back
+                room.seats[seat] = Seat::default();
+                room.seats[1 - seat].send(&message);
+                room.send_to_spectators(&message);
+                client.seat = None;
+                if room.is_abandoned() {
+                    println!("Room {} is closed", id);
+                    self.rooms.remove(&id);
+                }
+                return;
+            }
+            _ => {}
+        }
+
+        room.seats[1 - seat].send(&message);
+        if let Message::Start { .. }
+        | Message::Move { .. }
+        | Message::Resign
+        | Message::DrawAccept = message
+        {
+            room.send_to_spectators(&message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc::{self, Receiver};
+
+    use board_plugin::components::coordinates::Coordinates;
+
+    use super::*;
+
+    fn card(name: &str) -> u8 {
+        CARDS.iter().position(|card| card.name == name).unwrap() as u8
+    }
+
+    fn mov(from: (u8, u8), to: (u8, u8)) -> Move {
+        Move {
+            from: Coordinates {
+                x: from.0,
+                y: from.1,
+            },
+            to: Coordinates { x: to.0, y: to.1 },
+        }
+    }
+
+    fn client() -> (Client, Receiver<String>) {
+        let (outgoing, incoming) = mpsc::channel();
+        (Client::new(outgoing), incoming)
+    }
+
+    fn received(incoming: &Receiver<String>) -> Vec<Message> {
+        incoming
+            .try_iter()
+            .map(|line| Message::parse(&line).unwrap())
+            .collect()
+    }
+
+    fn send(rooms: &mut Rooms, client: &mut Client, line: &str) {
+        rooms.handle(client, Message::parse(line).unwrap());
+    }
+
+    fn is_error(messages: &[Message]) -> bool {
+        matches!(messages, [Message::Error { .. }])
+    }
+
+    /// Blue guest and red host in a started game, Monkey gives the first move to blue
+    fn started_room() -> (Rooms, [(Client, Receiver<String>); 2], String, String) {
+        let mut rooms = Rooms::default();
+        let (mut host, host_incoming) = client();
+        let (mut guest, guest_incoming) = client();
+
+        send(&mut rooms, &mut host, "CREATE");
+        let room = match received(&host_incoming).as_slice() {
+            [Message::Room { room, .. }] => room.clone(),
+            messages => panic!("Expected a room, got {:?}", messages),
+        };
+        send(&mut rooms, &mut guest, &format!("JOIN {}", room));
+        let token = match received(&guest_incoming).as_slice() {
+            [Message::Room { token, .. }, Message::Hello { .. }] => token.clone(),
+            messages => panic!("Expected a seat, got {:?}", messages),
+        };
+        send(
+            &mut rooms,
+            &mut host,
+            "START blue standard Tiger Crab Monkey Crane Dragon",
+        );
+        received(&host_incoming);
+        received(&guest_incoming);
+
+        (
+            rooms,
+            [(host, host_incoming), (guest, guest_incoming)],
+            room,
+            token,
+        )
+    }
+
+    #[test]
+    fn game_rejects_illegal_moves() {
+        let cards = [
+            card("Tiger"),
+            card("Crab"),
+            card("Monkey"),
+            card("Crane"),
+            card("Dragon"),
+        ];
+        let mut game = Game::new(PlayerColor::Blue, RuleSetType::Standard, cards, false);
+        assert_eq!(game.curr_color, PlayerColor::Blue);
+
+        // Crab does not move diagonally
+        assert!(game
+            .play(PlayerColor::Blue, "Crab", mov((1, 4), (0, 3)))
+            .is_err());
+        // Crane is in the hand of red
+        assert!(game
+            .play(PlayerColor::Blue, "Crane", mov((1, 4), (1, 3)))
+            .is_err());
+        assert!(game
+            .play(PlayerColor::Blue, "Unicorn", mov((1, 4), (1, 3)))
+            .is_err());
+        // nothing has changed after the rejected moves
+        assert_eq!(game.tile_map.to_rows(), TileMap::new().to_rows());
+        assert_eq!(game.curr_color, PlayerColor::Blue);
+
+        assert_eq!(
+            game.play(PlayerColor::Blue, "Crab", mov((1, 4), (1, 3))),
+            Ok(())
+        );
+        assert_eq!(game.curr_color, PlayerColor::Red);
+        // the used card has become the neutral card
+        assert_eq!(game.cards[NEUTRAL_CARD_IDX], card("Crab"));
+    }
+
+    #[test]
+    fn game_rejects_moves_of_the_wrong_side() {
+        let cards = [
+            card("Tiger"),
+            card("Crab"),
+            card("Monkey"),
+            card("Crane"),
+            card("Dragon"),
+        ];
+        let mut game = Game::new(PlayerColor::Blue, RuleSetType::Standard, cards, false);
+        assert_eq!(
+            game.play(PlayerColor::Red, "Crane", mov((1, 0), (1, 1))),
+            Err("Not your turn".to_owned())
+        );
+
+        game.is_finished = true;
+        assert!(game
+            .play(PlayerColor::Blue, "Crab", mov((1, 4), (1, 3)))
+            .is_err());
+    }
+
+    #[test]
+    fn room_does_not_relay_rejected_moves() {
+        let (mut rooms, [(mut host, host_incoming), (mut guest, guest_incoming)], room, _) =
+            started_room();
+
+        // the host plays red, but blue moves first
+        send(&mut rooms, &mut host, "MOVE Crane 1 0 1 1");
+        assert!(is_error(&received(&host_incoming)));
+        send(&mut rooms, &mut guest, "MOVE Crab 1 4 0 3");
+        assert!(is_error(&received(&guest_incoming)));
+        assert!(received(&host_incoming).is_empty());
+        assert!(rooms.rooms[&room].moves.is_empty());
+
+        send(&mut rooms, &mut guest, "MOVE Crab 1 4 1 3");
+        assert!(received(&guest_incoming).is_empty());
+        assert_eq!(
+            received(&host_incoming),
+            vec![Message::parse("MOVE Crab 1 4 1 3").unwrap()]
+        );
+        assert_eq!(rooms.rooms[&room].moves.len(), 1);
+    }
+
+    #[test]
+    fn rejoin_returns_the_missed_moves() {
+        let (mut rooms, [(mut host, host_incoming), (mut guest, _)], room, token) = started_room();
+        send(&mut rooms, &mut guest, "MOVE Crab 1 4 1 3");
+        rooms.leave(&mut guest);
+        received(&host_incoming);
+
+        let (mut guest, guest_incoming) = client();
+        send(
+            &mut rooms,
+            &mut guest,
+            &format!("REJOIN {} {} 0", room, token),
+        );
+        assert_eq!(
+            received(&guest_incoming),
+            vec![
+                Message::Room {
+                    room: room.clone(),
+                    token,
+                },
+                Message::parse("MOVE Crab 1 4 1 3").unwrap(),
+            ]
+        );
+
+        send(&mut rooms, &mut host, "MOVE Crane 1 0 1 1");
+        assert_eq!(received(&guest_incoming).len(), 1);
+    }
+
+    #[test]
+    fn rejoin_rejects_stale_tokens() {
+        let (mut rooms, [_, (mut guest, _)], room, token) = started_room();
+
+        let (mut stranger, stranger_incoming) = client();
+        send(
+            &mut rooms,
+            &mut stranger,
+            &format!("REJOIN {} 0123 0", room),
+        );
+        assert!(is_error(&received(&stranger_incoming)));
+        assert!(stranger.seat.is_none());
+
+        // the token is forgotten once the player has left for good
+        send(&mut rooms, &mut guest, "BYE");
+        let (mut guest, guest_incoming) = client();
+        send(
+            &mut rooms,
+            &mut guest,
+            &format!("REJOIN {} {} 0", room, token),
+        );
+        assert!(is_error(&received(&guest_incoming)));
+        assert!(guest.seat.is_none());
+
+        send(
+            &mut rooms,
+            &mut guest,
+            &format!("REJOIN 9999{} {} 0", room, token),
+        );
+        assert!(is_error(&received(&guest_incoming)));
+    }
+}
@@ -3,14 +3,28 @@ use bevy::prelude::*;
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::WorldInspectorPlugin;
 use board_plugin::button_plugin::ButtonPlugin;
+use board_plugin::draft_plugin::DraftPlugin;
 use board_plugin::menu_plugin::MainMenuPlugin;
+use board_plugin::network_plugin::NetworkPlugin;
 use board_plugin::resources::app_state::AppState;
 use board_plugin::resources::board_assets::{BoardAssets, SpriteMaterial};
 use board_plugin::resources::board_options::{BoardOptions, TileSize};
 use board_plugin::resources::deck_options::DeckOptions;
 use board_plugin::resources::depth::Depth;
+use board_plugin::resources::draft_options::DraftOptions;
+use board_plugin::resources::draw_rules::DrawRules;
+use board_plugin::resources::engine_options::EngineOptions;
+use board_plugin::resources::handicap::Handicap;
+use board_plugin::resources::menu_settings::MenuSettings;
+use board_plugin::resources::network_options::NetworkOptions;
 use board_plugin::resources::physical_deck::PhysicalDeck;
 use board_plugin::resources::selected::SelectedPlayers;
+use board_plugin::resources::statistics::Statistics;
+use board_plugin::resources::theme_options::ThemeOptions;
+use board_plugin::resources::time_control::TimeControl;
+use board_plugin::rules::rule_set::RuleSetType;
+use board_plugin::stats_plugin::StatsPlugin;
+use board_plugin::theme_plugin::ThemePlugin;
 use board_plugin::BoardPlugin;
 
 fn main() {
@@ -27,9 +41,21 @@ fn main() {
     app.insert_resource(PhysicalDeck::new());
     app.insert_resource(SelectedPlayers::default());
     app.insert_resource(Depth::default());
+    app.insert_resource(Handicap::default());
+    app.insert_resource(DrawRules::default());
+    app.insert_resource(RuleSetType::default());
+    app.insert_resource(NetworkOptions::default());
+    app.insert_resource(DraftOptions::default());
+    app.insert_resource(EngineOptions::load());
+    app.insert_resource(TimeControl::default());
+    app.insert_resource(Statistics::load());
+    app.insert_resource(MenuSettings::load());
 
     app.add_state(AppState::MainMenu);
     app.add_plugin(MainMenuPlugin);
+    app.add_plugin(DraftPlugin);
+    app.add_plugin(NetworkPlugin);
+    app.add_plugin(StatsPlugin);
 
     app.add_plugin(BoardPlugin {
         running_state: AppState::InProgress,
@@ -38,6 +64,7 @@ fn main() {
     .add_startup_system(setup_board);
 
     app.add_plugin(ButtonPlugin);
+    app.add_plugin(ThemePlugin);
 
     #[cfg(feature = "debug")]
     app.add_plugin(WorldInspectorPlugin::new());
@@ -57,21 +84,19 @@ fn setup_board(mut commands: Commands, asset_server: Res<AssetServer>) {
     // Board plugin options
     commands.insert_resource(BoardOptions {
         tile_padding: 3.0,
-        position: Vec3::new(-550.0, -230.0, 0.),
         ..Default::default()
     });
 
     commands.insert_resource(DeckOptions {
         tile_padding: 3.0,
-        position: Vec3::new(350.0, 30., 0.),
         tile_size: TileSize::Adaptive {
             min: 10.0,
             max: 30.0,
         },
     });
 
-    // Board assets
-    commands.insert_resource(BoardAssets {
+    // Board assets, the themes are applied over them
+    let board_assets = BoardAssets {
         label: "Default".to_string(),
         board_material: SpriteMaterial {
             color: Color::WHITE,
@@ -117,18 +142,48 @@ fn setup_board(mut commands: Commands, asset_server: Res<AssetServer>) {
             color: Color::RED,
             ..Default::default()
         },
+        selected_neutral_card_material: SpriteMaterial {
+            color: Color::LIME_GREEN,
+            ..Default::default()
+        },
         selected_piece_material: SpriteMaterial {
             color: Color::AZURE,
             ..Default::default()
         },
         allowed_move_tile_material: SpriteMaterial {
-            color: Color::CYAN,
+            color: Color::TOMATO,
+            ..Default::default()
+        },
+        first_card_move_material: SpriteMaterial {
+            color: Color::ORANGE,
+            ..Default::default()
+        },
+        second_card_move_material: SpriteMaterial {
+            color: Color::VIOLET,
+            ..Default::default()
+        },
+        both_cards_move_material: SpriteMaterial {
+            color: Color::LIME_GREEN,
+            ..Default::default()
+        },
+        keyboard_cursor_material: SpriteMaterial {
+            color: Color::rgba(1., 1., 0., 0.4),
+            ..Default::default()
+        },
+        last_move_tile_material: SpriteMaterial {
+            color: Color::rgba(1., 0.65, 0., 0.45),
+            ..Default::default()
+        },
+        last_move_card_material: SpriteMaterial {
+            color: Color::ORANGE,
             ..Default::default()
         },
         guide_text_size: 80.,
         turn_text_size: 40.,
         font: asset_server.load("fonts/pixeled.ttf"),
-    });
+    };
+    commands.insert_resource(ThemeOptions::load(&asset_server, board_assets.clone()));
+    commands.insert_resource(board_assets);
     // Plugin activation
     // state.set(AppState::InProgress).unwrap();
 }